@@ -0,0 +1,67 @@
+use crate::DyadicFraction;
+use core::fmt;
+use core::sync::atomic::{AtomicU64, Ordering};
+
+/// A dyadic fraction that can be shared lock-free between the main loop and
+/// interrupt handlers.
+///
+/// The value is stored in its packed [`DyadicFraction::to_bits`] form.
+#[derive(Default)]
+pub struct AtomicDF {
+    bits: AtomicU64,
+}
+
+impl AtomicDF {
+    pub const fn new(val: DyadicFraction) -> Self {
+        Self {
+            bits: AtomicU64::new(val.to_bits()),
+        }
+    }
+
+    pub fn load(&self, order: Ordering) -> DyadicFraction {
+        DyadicFraction::from_bits(self.bits.load(order))
+    }
+
+    pub fn store(&self, val: DyadicFraction, order: Ordering) {
+        self.bits.store(val.to_bits(), order)
+    }
+
+    pub fn swap(&self, val: DyadicFraction, order: Ordering) -> DyadicFraction {
+        DyadicFraction::from_bits(self.bits.swap(val.to_bits(), order))
+    }
+
+    /// Applies `f` until the update succeeds or `f` returns `None`, see
+    /// [`AtomicU64::fetch_update`].
+    pub fn fetch_update<F>(
+        &self,
+        set_order: Ordering,
+        fetch_order: Ordering,
+        mut f: F,
+    ) -> Result<DyadicFraction, DyadicFraction>
+    where
+        F: FnMut(DyadicFraction) -> Option<DyadicFraction>,
+    {
+        self.bits
+            .fetch_update(set_order, fetch_order, |bits| {
+                f(DyadicFraction::from_bits(bits)).map(DyadicFraction::to_bits)
+            })
+            .map(DyadicFraction::from_bits)
+            .map_err(DyadicFraction::from_bits)
+    }
+
+    pub fn into_inner(self) -> DyadicFraction {
+        DyadicFraction::from_bits(self.bits.into_inner())
+    }
+}
+
+impl From<DyadicFraction> for AtomicDF {
+    fn from(val: DyadicFraction) -> Self {
+        Self::new(val)
+    }
+}
+
+impl fmt::Debug for AtomicDF {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.load(Ordering::Relaxed), f)
+    }
+}
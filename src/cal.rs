@@ -0,0 +1,297 @@
+//! Sensor calibration from raw readings to engineering units.
+
+use crate::poly;
+use crate::wide::Wide;
+use crate::{DyadicFraction, Rounding};
+
+/// Gain and offset calibration, `engineering = raw * gain + offset`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Linear {
+    pub gain: DyadicFraction,
+    pub offset: DyadicFraction,
+}
+
+impl Linear {
+    pub const fn new(gain: DyadicFraction, offset: DyadicFraction) -> Self {
+        Self { gain, offset }
+    }
+
+    /// Fits the line through two `(raw, engineering)` points, or `None` when
+    /// both raw readings are equal.
+    ///
+    /// The gain is rounded to nearest at the finest power that fits, and the
+    /// offset is then chosen so that the line passes through the first
+    /// point as closely as the rounded gain allows.
+    pub fn from_points(
+        (raw0, eng0): (DyadicFraction, DyadicFraction),
+        (raw1, eng1): (DyadicFraction, DyadicFraction),
+    ) -> Option<Self> {
+        if raw0 == raw1 {
+            return None;
+        }
+        let mut rise = Wide::from_df(eng1);
+        rise.sub(Wide::from_df(eng0));
+        let mut run = Wide::from_df(raw1);
+        run.sub(Wide::from_df(raw0));
+        let gain = rise.div(run, i8::MAX);
+
+        let mut offset = Wide::from_df(eng0);
+        offset.sub_product(raw0, gain);
+        Some(Self::new(gain, offset.round(Rounding::Nearest)))
+    }
+
+    /// Converts a raw reading to engineering units with a single rounding to
+    /// nearest.
+    pub fn apply(&self, raw: DyadicFraction) -> DyadicFraction {
+        let mut acc = Wide::product(raw, self.gain);
+        acc.add(Wide::from_df(self.offset));
+        acc.round(Rounding::Nearest)
+    }
+
+    /// Converts engineering units back to a raw reading, rounded to nearest
+    /// at the finest power that fits. Saturates when the gain is zero.
+    pub fn invert(&self, eng: DyadicFraction) -> DyadicFraction {
+        let mut acc = Wide::from_df(eng);
+        acc.sub(Wide::from_df(self.offset));
+        acc.div(Wide::from_df(self.gain), i8::MAX)
+    }
+}
+
+/// Polynomial calibration for nonlinear sensors,
+/// `engineering = coeffs[0] + coeffs[1] * raw + coeffs[2] * raw^2 + ...`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Poly<const N: usize> {
+    pub coeffs: [DyadicFraction; N],
+}
+
+impl<const N: usize> Poly<N> {
+    pub const fn new(coeffs: [DyadicFraction; N]) -> Self {
+        Self { coeffs }
+    }
+
+    /// Converts a raw reading to engineering units, see [`poly::eval`].
+    pub fn apply(&self, raw: DyadicFraction) -> DyadicFraction {
+        poly::eval(&self.coeffs, raw)
+    }
+
+    /// Least-squares fit through `(raw, engineering)` points, for computing
+    /// calibrations on the host.
+    ///
+    /// Raw readings are scaled into `[-1, 1]` before the normal equations are
+    /// solved in `f64`, and the coefficients keep their top 31 bits. Returns
+    /// `None` with fewer than `N` points, a singular system, or coefficients
+    /// outside the dyadic fraction range.
+    #[cfg(feature = "std")]
+    #[allow(clippy::needless_range_loop)]
+    pub fn fit(points: &[(DyadicFraction, DyadicFraction)]) -> Option<Self> {
+        if N == 0 || points.len() < N {
+            return None;
+        }
+        let scale = points
+            .iter()
+            .map(|&(raw, _)| f64::from(raw).abs())
+            .fold(0.0, f64::max);
+        let scale = if scale > 0.0 { scale } else { 1.0 };
+
+        let mut a = [[0.0; N]; N];
+        let mut b = [0.0; N];
+        for &(raw, eng) in points {
+            let mut powers = [1.0; N];
+            for k in 1..N {
+                powers[k] = powers[k - 1] * f64::from(raw) / scale;
+            }
+            for i in 0..N {
+                b[i] += powers[i] * f64::from(eng);
+                for j in 0..N {
+                    a[i][j] += powers[i] * powers[j];
+                }
+            }
+        }
+
+        // Gaussian elimination with partial pivoting.
+        let tolerance = a[0][0] * 1e-12;
+        for col in 0..N {
+            let pivot = (col..N).max_by(|&i, &j| a[i][col].abs().total_cmp(&a[j][col].abs()))?;
+            if a[pivot][col].abs() <= tolerance {
+                return None;
+            }
+            a.swap(col, pivot);
+            b.swap(col, pivot);
+            for row in col + 1..N {
+                let factor = a[row][col] / a[col][col];
+                for k in col..N {
+                    a[row][k] -= factor * a[col][k];
+                }
+                b[row] -= factor * b[col];
+            }
+        }
+        let mut coeffs = [DyadicFraction::zero(); N];
+        for row in (0..N).rev() {
+            let mut val = b[row];
+            for k in row + 1..N {
+                val -= a[row][k] * b[k];
+            }
+            b[row] = val / a[row][row];
+            coeffs[row] = DyadicFraction::try_from(b[row] / scale.powi(row as i32)).ok()?;
+        }
+        Some(Self::new(coeffs))
+    }
+}
+
+/// `0 °C` in kelvin.
+const KELVIN: DyadicFraction = DyadicFraction::from_ratio(5463, 20, 22);
+
+/// NTC thermistor conversion from a voltage divider reading to °C.
+///
+/// The thermistor sits between the ADC input and ground, with the series
+/// resistor to the reference voltage, so the reading as a fraction of full
+/// scale is `ntc / (ntc + series)`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Ntc {
+    series: DyadicFraction,
+    model: NtcModel,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum NtcModel {
+    Beta {
+        nominal: DyadicFraction,
+        kelvin: DyadicFraction,
+        beta: DyadicFraction,
+    },
+    SteinhartHart {
+        a: DyadicFraction,
+        b: DyadicFraction,
+        c: DyadicFraction,
+    },
+}
+
+impl Ntc {
+    /// B-parameter model, `1/T = 1/T0 + ln(R/R0)/B`, from the resistance
+    /// `nominal` at `nominal_temp` °C. Resistances are in the same unit.
+    pub fn beta(
+        nominal: DyadicFraction,
+        nominal_temp: DyadicFraction,
+        beta: DyadicFraction,
+        series: DyadicFraction,
+    ) -> Self {
+        let mut kelvin = Wide::from_df(nominal_temp);
+        kelvin.add(Wide::from_df(KELVIN));
+        Self {
+            series,
+            model: NtcModel::Beta {
+                nominal,
+                kelvin: kelvin.round(Rounding::Nearest),
+                beta,
+            },
+        }
+    }
+
+    /// Steinhart–Hart model, `1/T = a + b ln(R) + c ln(R)^3`, with the
+    /// resistance in ohms.
+    pub const fn steinhart_hart(
+        a: DyadicFraction,
+        b: DyadicFraction,
+        c: DyadicFraction,
+        series: DyadicFraction,
+    ) -> Self {
+        Self {
+            series,
+            model: NtcModel::SteinhartHart { a, b, c },
+        }
+    }
+
+    /// Thermistor resistance for a reading in `(0, 1)` of full scale, or
+    /// `None` for a shorted or open sensor.
+    pub fn resistance(&self, ratio: DyadicFraction) -> Option<DyadicFraction> {
+        if !ratio.is_positive() || ratio >= DyadicFraction::from(1) {
+            return None;
+        }
+        let mut rest = Wide::from_df(DyadicFraction::from(1));
+        rest.sub(Wide::from_df(ratio));
+        Some(Wide::product(self.series, ratio).div(rest, i8::MAX))
+    }
+
+    /// Temperature in °C for a reading in `(0, 1)` of full scale, rounded to
+    /// nearest at a denominator of at most `2^max_power`, or `None` for a
+    /// shorted or open sensor.
+    pub fn temperature(&self, ratio: DyadicFraction, max_power: i8) -> Option<DyadicFraction> {
+        self.temperature_from_resistance(self.resistance(ratio)?, max_power)
+    }
+
+    /// Temperature in °C for a thermistor resistance, rounded to nearest at a
+    /// denominator of at most `2^max_power`, or `None` unless the resistance
+    /// is positive.
+    ///
+    /// The logarithm is resolved to 30 fractional bits, well below the
+    /// tolerance of any thermistor.
+    pub fn temperature_from_resistance(
+        &self,
+        resistance: DyadicFraction,
+        max_power: i8,
+    ) -> Option<DyadicFraction> {
+        if !resistance.is_positive() {
+            return None;
+        }
+        // Both models reduce to `T = num / den` in kelvin.
+        let (mut num, den) = match self.model {
+            NtcModel::Beta {
+                nominal,
+                kelvin,
+                beta,
+            } => {
+                let rel = Wide::from_df(resistance).div(Wide::from_df(nominal), i8::MAX);
+                let mut den = Wide::from_df(beta);
+                den.add_product(kelvin, rel.ln(30));
+                (Wide::product(kelvin, beta), den)
+            }
+            NtcModel::SteinhartHart { a, b, c } => {
+                let log = resistance.ln(30);
+                let mut den = Wide::from_df(a);
+                den.add_product(b, log);
+                den.add(Wide::product(log, log).mul_df(log).mul_df(c));
+                (Wide::from_df(DyadicFraction::from(1)), den)
+            }
+        };
+        num.sub(den.mul_df(KELVIN));
+        Some(num.div(den, max_power))
+    }
+}
+
+/// Conversion of raw ADC counts to engineering units,
+/// `value = counts / full_scale * reference * gain`.
+///
+/// The factor per count is resolved once, rounded to nearest at the finest
+/// power that fits, so every conversion is a single wide multiply.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AdcScaler {
+    resolution: DyadicFraction,
+}
+
+impl AdcScaler {
+    /// Creates a scaler for an ADC reading `full_scale` counts at the
+    /// `reference` voltage, followed by a sensor with `gain` units per volt.
+    /// A zero `full_scale` saturates.
+    pub fn new(
+        full_scale: DyadicFraction,
+        reference: DyadicFraction,
+        gain: DyadicFraction,
+    ) -> Self {
+        Self {
+            resolution: Wide::product(reference, gain).div(Wide::from_df(full_scale), i8::MAX),
+        }
+    }
+
+    /// Engineering units per count.
+    pub const fn resolution(&self) -> DyadicFraction {
+        self.resolution
+    }
+
+    /// Converts a raw reading, rounded to nearest when it needs more than 31
+    /// significant bits.
+    pub fn convert(&self, counts: u16) -> DyadicFraction {
+        Wide::product(DyadicFraction::from(counts), self.resolution).round(Rounding::Nearest)
+    }
+}
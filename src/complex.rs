@@ -0,0 +1,150 @@
+use crate::{DyadicFraction, Rounding};
+use core::fmt;
+use core::ops::*;
+
+/// A complex number with dyadic fraction components.
+///
+/// Products are accumulated exactly and narrowed once, rounding towards
+/// negative infinity when the result does not fit.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Complex {
+    pub re: DyadicFraction,
+    pub im: DyadicFraction,
+}
+
+impl Complex {
+    pub const fn new(re: DyadicFraction, im: DyadicFraction) -> Self {
+        Self { re, im }
+    }
+
+    pub const fn zero() -> Self {
+        Self::new(DyadicFraction::zero(), DyadicFraction::zero())
+    }
+
+    pub fn conj(self) -> Self {
+        Self::new(self.re, -self.im)
+    }
+
+    /// `re^2 + im^2`.
+    pub fn norm_sqr(self) -> DyadicFraction {
+        let (num, power) = self.wide_norm_sqr();
+        DyadicFraction::from_wide(num as i128, power)
+    }
+
+    /// Magnitude rounded down to a denominator of at most `2^max_power`.
+    pub fn norm(self, max_power: i8) -> DyadicFraction {
+        let (num, power) = self.wide_norm_sqr();
+        DyadicFraction::sqrt_wide(num, power, max_power)
+    }
+
+    fn wide_norm_sqr(self) -> (u128, i32) {
+        let re_power = 2 * self.re.denominator_power() as i32;
+        let im_power = 2 * self.im.denominator_power() as i32;
+        if (re_power - im_power).abs() > 60 {
+            let re = self.re.mul_rounded(self.re, Rounding::Floor);
+            let im = self.im.mul_rounded(self.im, Rounding::Floor);
+            let sum = re.add_rounded(im, Rounding::Floor);
+            return (sum.numerator() as u128, sum.denominator_power() as i32);
+        }
+        let power = re_power.max(im_power);
+        let re = (self.re.numerator() as i128).pow(2) << (power - re_power);
+        let im = (self.im.numerator() as i128).pow(2) << (power - im_power);
+        ((re + im) as u128, power)
+    }
+
+    /// Computes `a * b - c * d` exactly before narrowing.
+    fn cross(
+        a: DyadicFraction,
+        b: DyadicFraction,
+        c: DyadicFraction,
+        d: DyadicFraction,
+    ) -> DyadicFraction {
+        let lhs = a.mul_rounded(b, Rounding::Floor);
+        let rhs = c.mul_rounded(d, Rounding::Floor);
+        let lhs_power = a.denominator_power() as i32 + b.denominator_power() as i32;
+        let rhs_power = c.denominator_power() as i32 + d.denominator_power() as i32;
+        if (lhs_power - rhs_power).abs() > 60 {
+            return lhs.add_rounded(rhs.neg_rounded(Rounding::Floor), Rounding::Floor);
+        }
+        let power = lhs_power.max(rhs_power);
+        let lhs = (a.numerator() as i128 * b.numerator() as i128) << (power - lhs_power);
+        let rhs = (c.numerator() as i128 * d.numerator() as i128) << (power - rhs_power);
+        DyadicFraction::from_wide(lhs - rhs, power)
+    }
+}
+
+impl From<DyadicFraction> for Complex {
+    fn from(re: DyadicFraction) -> Self {
+        Self::new(re, DyadicFraction::zero())
+    }
+}
+
+impl Add for Complex {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(self.re + other.re, self.im + other.im)
+    }
+}
+
+impl AddAssign for Complex {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Complex {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.re - other.re, self.im - other.im)
+    }
+}
+
+impl SubAssign for Complex {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul for Complex {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        Self::new(
+            Self::cross(self.re, other.re, self.im, other.im),
+            Self::cross(self.re, other.im, -self.im, other.re),
+        )
+    }
+}
+
+impl MulAssign for Complex {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl Mul<DyadicFraction> for Complex {
+    type Output = Self;
+
+    fn mul(self, other: DyadicFraction) -> Self {
+        Self::new(self.re * other, self.im * other)
+    }
+}
+
+impl Neg for Complex {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::new(-self.re, -self.im)
+    }
+}
+
+impl fmt::Display for Complex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.re, f)?;
+        f.write_str(if self.im.is_negative() { " - " } else { " + " })?;
+        fmt::Display::fmt(&self.im.abs(), f)?;
+        f.write_str("i")
+    }
+}
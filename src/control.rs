@@ -0,0 +1,677 @@
+//! Building blocks for shaping controller setpoints and outputs.
+
+use crate::sim::FirstOrderPlant;
+use crate::wide::Wide;
+use crate::{DyadicFraction, Rounding};
+
+/// Limits how fast a signal may change, with separate rates for rising and
+/// falling edges, to protect actuators from step changes.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct SlewLimiter {
+    rise: DyadicFraction,
+    fall: DyadicFraction,
+    value: DyadicFraction,
+}
+
+impl SlewLimiter {
+    /// Creates a limiter starting at zero that moves up by at most `rise` and
+    /// down by at most `fall` per update. Both rates are taken by magnitude.
+    pub const fn new(rise: DyadicFraction, fall: DyadicFraction) -> Self {
+        Self {
+            rise: rise.abs(),
+            fall: fall.abs(),
+            value: DyadicFraction::zero(),
+        }
+    }
+
+    /// Moves towards `target` by no more than the rate limits and returns the
+    /// new output.
+    pub fn update(&mut self, target: DyadicFraction) -> DyadicFraction {
+        self.value = step_toward(self.value, target, self.rise, self.fall);
+        self.value
+    }
+
+    pub const fn value(&self) -> DyadicFraction {
+        self.value
+    }
+
+    /// Jumps to `value` without limiting.
+    pub fn reset(&mut self, value: DyadicFraction) {
+        self.value = value;
+    }
+
+    pub const fn rise(&self) -> DyadicFraction {
+        self.rise
+    }
+
+    pub const fn fall(&self) -> DyadicFraction {
+        self.fall
+    }
+
+    pub fn set_rates(&mut self, rise: DyadicFraction, fall: DyadicFraction) {
+        self.rise = rise.abs();
+        self.fall = fall.abs();
+    }
+}
+
+/// Soft-start generator moving its output towards a target at a fixed rate
+/// per update, for bringing heaters and motor drives up gradually.
+///
+/// The output can serve directly as the setpoint of a controller.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Ramp {
+    rate: DyadicFraction,
+    target: DyadicFraction,
+    value: DyadicFraction,
+}
+
+impl Ramp {
+    /// Creates a ramp at rest at zero that moves by at most `rate`, taken by
+    /// magnitude, per update.
+    pub const fn new(rate: DyadicFraction) -> Self {
+        Self {
+            rate: rate.abs(),
+            target: DyadicFraction::zero(),
+            value: DyadicFraction::zero(),
+        }
+    }
+
+    /// Advances one step towards the target and returns the new output.
+    pub fn update(&mut self) -> DyadicFraction {
+        self.value = step_toward(self.value, self.target, self.rate, self.rate);
+        self.value
+    }
+
+    pub const fn value(&self) -> DyadicFraction {
+        self.value
+    }
+
+    pub const fn target(&self) -> DyadicFraction {
+        self.target
+    }
+
+    /// Sets a new target, approached from the current output.
+    pub fn set_target(&mut self, target: DyadicFraction) {
+        self.target = target;
+    }
+
+    /// Whether the output has reached the target.
+    pub fn is_done(&self) -> bool {
+        self.value == self.target
+    }
+
+    /// Jumps the output and the target to `value`.
+    pub fn reset(&mut self, value: DyadicFraction) {
+        self.value = value;
+        self.target = value;
+    }
+
+    pub const fn rate(&self) -> DyadicFraction {
+        self.rate
+    }
+
+    pub fn set_rate(&mut self, rate: DyadicFraction) {
+        self.rate = rate.abs();
+    }
+}
+
+/// Numerical integration rule of an [`Integrator`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Integration {
+    /// Backward rectangle, `y[n] = y[n-1] + dt * x[n]`.
+    #[default]
+    Rectangular,
+    /// Trapezoid, `y[n] = y[n-1] + dt * (x[n] + x[n-1]) / 2`.
+    Trapezoidal,
+}
+
+/// Discrete integrator with output clamping for anti-windup.
+///
+/// The sum is kept exactly and clamped to the limits on every update, so it
+/// never winds up beyond them; the output is rounded to nearest.
+#[derive(Copy, Clone, Debug)]
+pub struct Integrator {
+    method: Integration,
+    dt: DyadicFraction,
+    min: DyadicFraction,
+    max: DyadicFraction,
+    sum: Wide,
+    input: DyadicFraction,
+    frozen: bool,
+}
+
+impl Integrator {
+    /// Creates an unlimited integrator at zero with time step `dt`.
+    pub const fn new(method: Integration, dt: DyadicFraction) -> Self {
+        Self {
+            method,
+            dt,
+            min: DyadicFraction::saturated(true),
+            max: DyadicFraction::saturated(false),
+            sum: Wide::new(0, 0),
+            input: DyadicFraction::zero(),
+            frozen: false,
+        }
+    }
+
+    /// Integrates `input` over one time step and returns the new output.
+    /// While frozen, the input is recorded but the output holds.
+    pub fn update(&mut self, input: DyadicFraction) -> DyadicFraction {
+        if !self.frozen {
+            match self.method {
+                Integration::Rectangular => self.sum.add_product(self.dt, input),
+                Integration::Trapezoidal => {
+                    let mut area = Wide::product(self.dt, input);
+                    area.add_product(self.dt, self.input);
+                    self.sum.add(area.mul_df(DyadicFraction::new(1, 1)));
+                }
+            }
+            self.clamp();
+        }
+        self.input = input;
+        self.value()
+    }
+
+    pub fn value(&self) -> DyadicFraction {
+        self.sum.round(Rounding::Nearest)
+    }
+
+    /// Sets the output to `value`, clamped to the limits, for bumpless
+    /// transfer.
+    pub fn preload(&mut self, value: DyadicFraction) {
+        self.sum = Wide::from_df(value);
+        self.clamp();
+    }
+
+    /// Clears the output and the previous input.
+    pub fn reset(&mut self) {
+        self.sum = Wide::default();
+        self.input = DyadicFraction::zero();
+        self.clamp();
+    }
+
+    pub const fn is_frozen(&self) -> bool {
+        self.frozen
+    }
+
+    /// Holds the output while `frozen`, for conditional integration.
+    pub fn set_frozen(&mut self, frozen: bool) {
+        self.frozen = frozen;
+    }
+
+    pub const fn dt(&self) -> DyadicFraction {
+        self.dt
+    }
+
+    pub fn set_dt(&mut self, dt: DyadicFraction) {
+        self.dt = dt;
+    }
+
+    /// Output limits `(min, max)`.
+    pub const fn limits(&self) -> (DyadicFraction, DyadicFraction) {
+        (self.min, self.max)
+    }
+
+    /// Clamps the output to `[min, max]`, given in any order.
+    pub fn set_limits(&mut self, min: DyadicFraction, max: DyadicFraction) {
+        self.min = DyadicFraction::min(min, max);
+        self.max = DyadicFraction::max(min, max);
+        self.clamp();
+    }
+
+    fn clamp(&mut self) {
+        let value = self.value();
+        if value < self.min {
+            self.sum = Wide::from_df(self.min);
+        } else if value > self.max {
+            self.sum = Wide::from_df(self.max);
+        }
+    }
+}
+
+/// Band-limited differentiator, the derivative followed by a first-order
+/// low-pass with time constant `tau`, `D(s) = s / (tau s + 1)`.
+///
+/// Discretized with the backward difference as
+/// `y[n] = (tau y[n-1] + x[n] - x[n-1]) / (tau + dt)`, so a zero `tau`
+/// gives the raw difference quotient. The update is computed exactly and
+/// rounded to nearest once.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Differentiator {
+    dt: DyadicFraction,
+    tau: DyadicFraction,
+    pole: DyadicFraction,
+    gain: DyadicFraction,
+    input: DyadicFraction,
+    output: DyadicFraction,
+}
+
+impl Differentiator {
+    /// Creates a differentiator with time step `dt` and filter time constant
+    /// `tau`, in the same unit; the pole sits at `-1 / tau`.
+    pub fn new(dt: DyadicFraction, tau: DyadicFraction) -> Self {
+        let mut diff = Self::default();
+        diff.set_params(dt, tau);
+        diff
+    }
+
+    /// Feeds `sample` and returns the filtered rate of change.
+    pub fn update(&mut self, sample: DyadicFraction) -> DyadicFraction {
+        let mut delta = Wide::from_df(sample);
+        delta.sub(Wide::from_df(self.input));
+        let mut acc = Wide::product(self.pole, self.output);
+        acc.add(delta.mul_df(self.gain));
+        self.input = sample;
+        self.output = acc.round(Rounding::Nearest);
+        self.output
+    }
+
+    pub const fn value(&self) -> DyadicFraction {
+        self.output
+    }
+
+    /// Restarts at rest assuming the input has been at `value`, so the first
+    /// sample does not produce a spike.
+    pub fn reset(&mut self, value: DyadicFraction) {
+        self.input = value;
+        self.output = DyadicFraction::zero();
+    }
+
+    pub const fn dt(&self) -> DyadicFraction {
+        self.dt
+    }
+
+    pub const fn tau(&self) -> DyadicFraction {
+        self.tau
+    }
+
+    /// Changes the time step and the filter time constant, keeping the state.
+    /// The coefficients are rounded to nearest at the finest power that fits.
+    pub fn set_params(&mut self, dt: DyadicFraction, tau: DyadicFraction) {
+        let mut den = Wide::from_df(dt);
+        den.add(Wide::from_df(tau));
+        self.dt = dt;
+        self.tau = tau;
+        self.pole = Wide::from_df(tau).div(den, i8::MAX);
+        self.gain = Wide::new(1, 0).div(den, i8::MAX);
+    }
+}
+
+/// Linear difference equation
+/// `y[n] = b0 x[n] + ... + b[NB-1] x[n-NB+1] - a1 y[n-1] - ... - a[NA] y[n-NA]`,
+/// for compensators discretized offline, e.g. with the Tustin transform.
+///
+/// Coefficients are normalized so that `a0 = 1`. All products are
+/// accumulated exactly and rounded to nearest once per sample.
+#[derive(Copy, Clone, Debug)]
+pub struct Discrete<const NB: usize, const NA: usize> {
+    b: [DyadicFraction; NB],
+    a: [DyadicFraction; NA],
+    inputs: [DyadicFraction; NB],
+    outputs: [DyadicFraction; NA],
+    output: DyadicFraction,
+}
+
+impl<const NB: usize, const NA: usize> Discrete<NB, NA> {
+    /// Creates a system from the feed-forward `[b0, b1, ...]` and feedback
+    /// `[a1, a2, ...]` coefficients.
+    pub const fn new(b: [DyadicFraction; NB], a: [DyadicFraction; NA]) -> Self {
+        Self {
+            b,
+            a,
+            inputs: [DyadicFraction::zero(); NB],
+            outputs: [DyadicFraction::zero(); NA],
+            output: DyadicFraction::zero(),
+        }
+    }
+
+    /// Feeds `sample` and returns the new output.
+    pub fn update(&mut self, sample: DyadicFraction) -> DyadicFraction {
+        push(&mut self.inputs, sample);
+        let mut feedback = Wide::default();
+        feedback.add_dot(&self.a, &self.outputs);
+        let mut acc = Wide::default();
+        acc.add_dot(&self.b, &self.inputs);
+        acc.sub(feedback);
+        self.output = acc.round(Rounding::Nearest);
+        push(&mut self.outputs, self.output);
+        self.output
+    }
+
+    pub const fn value(&self) -> DyadicFraction {
+        self.output
+    }
+
+    /// Clears the input and output histories.
+    pub fn reset(&mut self) {
+        *self = Self::new(self.b, self.a);
+    }
+
+    /// Feed-forward coefficients `[b0, b1, ...]`.
+    pub const fn b(&self) -> &[DyadicFraction; NB] {
+        &self.b
+    }
+
+    /// Feedback coefficients `[a1, a2, ...]`.
+    pub const fn a(&self) -> &[DyadicFraction; NA] {
+        &self.a
+    }
+}
+
+/// Shifts a newest-first history by one sample and stores `val` in front.
+fn push(history: &mut [DyadicFraction], val: DyadicFraction) {
+    if let Some(last) = history.len().checked_sub(1) {
+        history.copy_within(..last, 1);
+        history[0] = val;
+    }
+}
+
+/// Output change reported by [`Hysteresis::update`], also used for the
+/// direction of a [`Crossing`](crate::dsp::Crossing).
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Transition {
+    /// The output switched on.
+    Rising,
+    /// The output switched off.
+    Falling,
+}
+
+/// Schmitt trigger comparator with separate thresholds for each direction.
+///
+/// The output switches on once the input reaches the rising threshold and
+/// off once it drops to the falling threshold, and holds in between.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Hysteresis {
+    falling: DyadicFraction,
+    rising: DyadicFraction,
+    state: bool,
+}
+
+impl Hysteresis {
+    /// Creates a comparator that is off, with thresholds given in any order.
+    pub fn new(falling: DyadicFraction, rising: DyadicFraction) -> Self {
+        let mut comparator = Self::default();
+        comparator.set_thresholds(falling, rising);
+        comparator
+    }
+
+    /// Feeds `input` and returns the transition it caused, if any.
+    pub fn update(&mut self, input: DyadicFraction) -> Option<Transition> {
+        if !self.state && input >= self.rising {
+            self.state = true;
+            Some(Transition::Rising)
+        } else if self.state && input <= self.falling {
+            self.state = false;
+            Some(Transition::Falling)
+        } else {
+            None
+        }
+    }
+
+    pub const fn state(&self) -> bool {
+        self.state
+    }
+
+    /// Forces the output to `state` without reporting a transition.
+    pub fn reset(&mut self, state: bool) {
+        self.state = state;
+    }
+
+    /// Thresholds `(falling, rising)`.
+    pub const fn thresholds(&self) -> (DyadicFraction, DyadicFraction) {
+        (self.falling, self.rising)
+    }
+
+    /// Sets the thresholds, given in any order; the lower one switches off.
+    pub fn set_thresholds(&mut self, falling: DyadicFraction, rising: DyadicFraction) {
+        self.falling = DyadicFraction::min(falling, rising);
+        self.rising = DyadicFraction::max(falling, rising);
+    }
+}
+
+/// On/off controller for relays and compressors, switching on a dead band
+/// around zero error with minimum on and off times.
+///
+/// Feed the control error, `setpoint - measurement`: the output switches on
+/// once the error reaches half the dead band and off once it drops to minus
+/// half of it, but only after it has held its state for the minimum number
+/// of updates.
+#[derive(Copy, Clone, Debug)]
+pub struct BangBang {
+    comparator: Hysteresis,
+    min_on: u32,
+    min_off: u32,
+    held: u32,
+}
+
+impl BangBang {
+    /// Creates a controller that is off and free to switch.
+    pub fn new(dead_band: DyadicFraction, min_on: u32, min_off: u32) -> Self {
+        let mut controller = Self {
+            comparator: Hysteresis::default(),
+            min_on,
+            min_off,
+            held: u32::MAX,
+        };
+        controller.set_dead_band(dead_band);
+        controller
+    }
+
+    /// Feeds `error` for one tick and returns whether the output is on.
+    pub fn update(&mut self, error: DyadicFraction) -> bool {
+        self.held = self.held.saturating_add(1);
+        let min_held = if self.state() {
+            self.min_on
+        } else {
+            self.min_off
+        };
+        if self.held >= min_held && self.comparator.update(error).is_some() {
+            self.held = 0;
+        }
+        self.state()
+    }
+
+    pub const fn state(&self) -> bool {
+        self.comparator.state()
+    }
+
+    /// Forces the output to `state` and lets it switch on the next update.
+    pub fn reset(&mut self, state: bool) {
+        self.comparator.reset(state);
+        self.held = u32::MAX;
+    }
+
+    /// Width of the band between the switching thresholds.
+    pub fn dead_band(&self) -> DyadicFraction {
+        let (falling, rising) = self.comparator.thresholds();
+        rising.add_rounded(falling.neg_rounded(Rounding::Nearest), Rounding::Nearest)
+    }
+
+    /// Sets the dead band, centred on zero error; its sign is ignored.
+    pub fn set_dead_band(&mut self, dead_band: DyadicFraction) {
+        let half = dead_band
+            .abs()
+            .mul_rounded(DyadicFraction::new(1, 1), Rounding::Nearest);
+        self.comparator
+            .set_thresholds(half.neg_rounded(Rounding::Nearest), half);
+    }
+
+    /// Minimum number of updates the output stays on.
+    pub const fn min_on(&self) -> u32 {
+        self.min_on
+    }
+
+    /// Minimum number of updates the output stays off.
+    pub const fn min_off(&self) -> u32 {
+        self.min_off
+    }
+
+    pub fn set_min_times(&mut self, min_on: u32, min_off: u32) {
+        self.min_on = min_on;
+        self.min_off = min_off;
+    }
+}
+
+/// Take-back-half velocity controller, an integrating controller that
+/// halves back to the last crossing whenever the error changes sign.
+///
+/// Each update adds `gain * error` to the output; on a sign change the
+/// output is set halfway between itself and the output at the previous
+/// crossing. Outputs are rounded to nearest and clamped to the limits.
+#[derive(Copy, Clone, Debug)]
+pub struct TakeBackHalf {
+    gain: DyadicFraction,
+    min: DyadicFraction,
+    max: DyadicFraction,
+    output: DyadicFraction,
+    take_back: DyadicFraction,
+    error: DyadicFraction,
+}
+
+impl TakeBackHalf {
+    /// Creates an unlimited controller at zero output.
+    pub const fn new(gain: DyadicFraction) -> Self {
+        Self {
+            gain,
+            min: DyadicFraction::saturated(true),
+            max: DyadicFraction::saturated(false),
+            output: DyadicFraction::zero(),
+            take_back: DyadicFraction::zero(),
+            error: DyadicFraction::zero(),
+        }
+    }
+
+    /// Feeds `error`, `setpoint - measurement`, and returns the new output.
+    pub fn update(&mut self, error: DyadicFraction) -> DyadicFraction {
+        let crossed = error.is_negative() != self.error.is_negative();
+        let mut acc = Wide::from_df(self.output);
+        acc.add_product(self.gain, error);
+        if crossed {
+            acc.add(Wide::from_df(self.take_back));
+            acc = acc.mul_df(DyadicFraction::new(1, 1));
+        }
+        self.output = self.clamp(acc.round(Rounding::Nearest));
+        if crossed {
+            self.take_back = self.output;
+        }
+        self.error = error;
+        self.output
+    }
+
+    pub const fn value(&self) -> DyadicFraction {
+        self.output
+    }
+
+    /// Restarts from `output`, clamped to the limits, which also serves as
+    /// the take-back value of the first crossing. An estimate of the output
+    /// holding the setpoint speeds up the first approach.
+    pub fn reset(&mut self, output: DyadicFraction) {
+        self.output = self.clamp(output);
+        self.take_back = self.output;
+        self.error = DyadicFraction::zero();
+    }
+
+    pub const fn gain(&self) -> DyadicFraction {
+        self.gain
+    }
+
+    pub fn set_gain(&mut self, gain: DyadicFraction) {
+        self.gain = gain;
+    }
+
+    /// Output limits `(min, max)`.
+    pub const fn limits(&self) -> (DyadicFraction, DyadicFraction) {
+        (self.min, self.max)
+    }
+
+    /// Clamps the output to `[min, max]`, given in any order.
+    pub fn set_limits(&mut self, min: DyadicFraction, max: DyadicFraction) {
+        self.min = DyadicFraction::min(min, max);
+        self.max = DyadicFraction::max(min, max);
+        self.output = self.clamp(self.output);
+        self.take_back = self.clamp(self.take_back);
+    }
+
+    fn clamp(&self, val: DyadicFraction) -> DyadicFraction {
+        DyadicFraction::min(DyadicFraction::max(val, self.min), self.max)
+    }
+}
+
+/// Smith predictor for processes with a long transport delay, modelled as a
+/// first-order lag followed by `DELAY` updates of dead time.
+///
+/// The controller acts on [`SmithPredictor::feedback`] instead of the raw
+/// measurement, which replaces the delayed model response by the undelayed
+/// one, so it can be tuned for the lag alone. Each controller output is
+/// passed back through [`SmithPredictor::update`].
+#[derive(Copy, Clone, Debug)]
+pub struct SmithPredictor<const DELAY: usize> {
+    model: FirstOrderPlant,
+    history: [DyadicFraction; DELAY],
+    head: usize,
+}
+
+impl<const DELAY: usize> SmithPredictor<DELAY> {
+    /// Creates a predictor at rest for a process with static `gain` and time
+    /// constant `tau`, updated every `dt`.
+    pub fn new(gain: DyadicFraction, tau: DyadicFraction, dt: DyadicFraction) -> Self {
+        Self {
+            model: FirstOrderPlant::new(gain, tau, dt),
+            history: [DyadicFraction::zero(); DELAY],
+            head: 0,
+        }
+    }
+
+    /// Measurement corrected by the model, `measurement + model - delayed
+    /// model`, rounded to nearest.
+    pub fn feedback(&self, measurement: DyadicFraction) -> DyadicFraction {
+        let delayed = self.history.get(self.head).copied();
+        let mut acc = Wide::from_df(measurement);
+        acc.add(Wide::from_df(self.model.value()));
+        acc.sub(Wide::from_df(delayed.unwrap_or(self.model.value())));
+        acc.round(Rounding::Nearest)
+    }
+
+    /// Advances the model by one update with the controller `output`.
+    pub fn update(&mut self, output: DyadicFraction) {
+        if let Some(slot) = self.history.get_mut(self.head) {
+            *slot = self.model.value();
+            self.head = (self.head + 1) % DELAY;
+        }
+        self.model.update(output);
+    }
+
+    /// Undelayed model output.
+    pub const fn prediction(&self) -> DyadicFraction {
+        self.model.value()
+    }
+
+    /// Returns the model to rest.
+    pub fn reset(&mut self) {
+        self.model.reset();
+        self.history = [DyadicFraction::zero(); DELAY];
+        self.head = 0;
+    }
+
+    pub const fn model(&self) -> &FirstOrderPlant {
+        &self.model
+    }
+}
+
+/// Steps from `value` to `target`, moving up by at most `rise` and down by
+/// at most `fall`. Steps are rounded to nearest rather than saturating.
+fn step_toward(
+    value: DyadicFraction,
+    target: DyadicFraction,
+    rise: DyadicFraction,
+    fall: DyadicFraction,
+) -> DyadicFraction {
+    let diff = target.add_rounded(value.neg_rounded(Rounding::Nearest), Rounding::Nearest);
+    if diff > rise {
+        value.add_rounded(rise, Rounding::Nearest)
+    } else if diff < fall.neg_rounded(Rounding::Nearest) {
+        value.add_rounded(fall.neg_rounded(Rounding::Nearest), Rounding::Nearest)
+    } else {
+        target
+    }
+}
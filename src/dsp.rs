@@ -0,0 +1,522 @@
+//! Signal sources for excitation, self-test and synthesis, and analysis
+//! such as oscillator tracking and level and crossing detection.
+
+use crate::control::Transition;
+use crate::trig;
+use crate::wide::Wide;
+use crate::{DyadicFraction, Rounding};
+
+/// Pseudo-random binary sequence from a 32-bit Galois LFSR.
+///
+/// The feedback polynomial `x^32 + x^22 + x^2 + x + 1` is maximal, so the
+/// bit sequence repeats only after `2^32 - 1` steps.
+#[derive(Copy, Clone, Debug)]
+pub struct Prbs {
+    state: u32,
+    low: DyadicFraction,
+    high: DyadicFraction,
+}
+
+impl Prbs {
+    const TAPS: u32 = 0x8020_0003;
+
+    /// Creates a source producing values in `[low, high)`. A zero `seed`,
+    /// which would lock the register, is replaced by one.
+    pub const fn new(seed: u32, low: DyadicFraction, high: DyadicFraction) -> Self {
+        Self {
+            state: if seed == 0 { 1 } else { seed },
+            low,
+            high,
+        }
+    }
+
+    /// Advances the register by one step and returns the output bit.
+    pub fn next_bit(&mut self) -> bool {
+        let bit = self.state & 1 != 0;
+        self.state >>= 1;
+        if bit {
+            self.state ^= Self::TAPS;
+        }
+        bit
+    }
+
+    /// Two-level excitation: `high` or `low` depending on the next bit.
+    pub fn next_level(&mut self) -> DyadicFraction {
+        if self.next_bit() {
+            self.high
+        } else {
+            self.low
+        }
+    }
+
+    /// Uniformly distributed value in `[low, high)` from the next 32 bits,
+    /// rounded towards negative infinity.
+    pub fn next_uniform(&mut self) -> DyadicFraction {
+        for _ in 0..32 {
+            self.next_bit();
+        }
+        let unit = DyadicFraction::from_wide(self.state as i128, 32);
+        let mut span = Wide::from_df(self.high);
+        span.sub(Wide::from_df(self.low));
+        let mut acc = span.mul_df(unit);
+        acc.add(Wide::from_df(self.low));
+        acc.to_df()
+    }
+}
+
+/// Yields [`Prbs::next_uniform`] values endlessly.
+impl Iterator for Prbs {
+    type Item = DyadicFraction;
+
+    fn next(&mut self) -> Option<DyadicFraction> {
+        Some(self.next_uniform())
+    }
+}
+
+/// Wrapping phase accumulator, the core of a direct digital synthesizer.
+///
+/// The phase is held as a 64-bit fraction of a turn, so it wraps exactly
+/// and the increment can be changed between ticks without a phase jump.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct PhaseAccumulator {
+    phase: u64,
+    step: u64,
+}
+
+impl PhaseAccumulator {
+    /// Creates an accumulator at phase zero advancing by `increment` turns
+    /// per tick; negative increments run backwards.
+    pub fn new(increment: DyadicFraction) -> Self {
+        Self {
+            phase: 0,
+            step: trig::turn_fraction(increment),
+        }
+    }
+
+    /// Creates an accumulator producing `frequency` at `sample_rate`, both
+    /// in the same unit.
+    pub fn from_frequency(frequency: DyadicFraction, sample_rate: DyadicFraction) -> Self {
+        let increment = Wide::from_df(frequency).div(Wide::from_df(sample_rate), i8::MAX);
+        Self::new(increment)
+    }
+
+    /// Advances the phase by one increment and returns `true` when it
+    /// wrapped past a whole turn, e.g. to emit a step pulse.
+    pub fn tick(&mut self) -> bool {
+        let (phase, carry) = self.phase.overflowing_add(self.step);
+        self.phase = phase;
+        if (self.step as i64) < 0 {
+            !carry
+        } else {
+            carry
+        }
+    }
+
+    /// Phase in turns, in `[0, 1)`.
+    pub const fn turns(&self) -> DyadicFraction {
+        DyadicFraction::from_wide(self.phase as i128, 64)
+    }
+
+    /// Phase in radians, in `[0, τ)`.
+    pub const fn radians(&self) -> DyadicFraction {
+        DyadicFraction::from_wide((self.phase as i128 * trig::TAU) >> 64, 60)
+    }
+
+    /// Increment in turns per tick, in `[-1/2, 1/2)`.
+    pub const fn increment(&self) -> DyadicFraction {
+        DyadicFraction::from_wide(self.step as i64 as i128, 64)
+    }
+
+    /// Changes the increment, keeping the current phase.
+    pub fn set_increment(&mut self, increment: DyadicFraction) {
+        self.step = trig::turn_fraction(increment);
+    }
+
+    /// Jumps to `turns`, wrapped into `[0, 1)`.
+    pub fn set_turns(&mut self, turns: DyadicFraction) {
+        self.phase = trig::turn_fraction(turns);
+    }
+}
+
+/// Phase-locked loop: a [`PhaseAccumulator`] steered by a proportional-
+/// integral loop filter to follow a reference.
+///
+/// Phase errors are in turns, positive when the reference leads. The
+/// oscillator runs at the centre increment plus `kp * error` plus the
+/// integral of `ki * error`, in turns per tick.
+#[derive(Copy, Clone, Debug)]
+pub struct Pll {
+    osc: PhaseAccumulator,
+    center: DyadicFraction,
+    kp: DyadicFraction,
+    ki: DyadicFraction,
+    integral: DyadicFraction,
+    error: DyadicFraction,
+}
+
+impl Pll {
+    /// Creates a loop free-running at `center` turns per tick.
+    pub fn new(center: DyadicFraction, kp: DyadicFraction, ki: DyadicFraction) -> Self {
+        Self {
+            osc: PhaseAccumulator::new(center),
+            center,
+            kp,
+            ki,
+            integral: DyadicFraction::zero(),
+            error: DyadicFraction::zero(),
+        }
+    }
+
+    /// Corrects the frequency by the measured `phase_error`, advances the
+    /// oscillator by one tick and returns its phase in turns.
+    pub fn update(&mut self, phase_error: DyadicFraction) -> DyadicFraction {
+        let mut integral = Wide::from_df(self.integral);
+        integral.add_product(self.ki, phase_error);
+        self.integral = integral.round(Rounding::Nearest);
+        let mut increment = Wide::from_df(self.center);
+        increment.add(Wide::from_df(self.integral));
+        increment.add_product(self.kp, phase_error);
+        self.osc.set_increment(increment.round(Rounding::Nearest));
+        self.error = phase_error;
+        self.osc.tick();
+        self.osc.turns()
+    }
+
+    /// Advances by one tick given whether the reference crossed zero
+    /// upwards since the last tick, and returns the phase in turns.
+    ///
+    /// A crossing is compared against phase zero and corrects the
+    /// frequency; between crossings the oscillator keeps its increment.
+    pub fn update_crossing(&mut self, crossed: bool) -> DyadicFraction {
+        if crossed {
+            let lag = (self.osc.phase as i64).wrapping_neg();
+            self.update(DyadicFraction::narrow(lag as i128, 64, Rounding::Nearest))
+        } else {
+            self.osc.tick();
+            self.osc.turns()
+        }
+    }
+
+    /// Phase in turns, in `[0, 1)`.
+    pub const fn phase(&self) -> DyadicFraction {
+        self.osc.turns()
+    }
+
+    /// Current increment in turns per tick.
+    pub const fn increment(&self) -> DyadicFraction {
+        self.osc.increment()
+    }
+
+    /// Last phase error applied, in turns.
+    pub const fn phase_error(&self) -> DyadicFraction {
+        self.error
+    }
+
+    pub const fn oscillator(&self) -> &PhaseAccumulator {
+        &self.osc
+    }
+
+    /// Returns to free-running at the centre increment from phase zero.
+    pub fn reset(&mut self) {
+        *self = Self::new(self.center, self.kp, self.ki);
+    }
+
+    pub const fn gains(&self) -> (DyadicFraction, DyadicFraction) {
+        (self.kp, self.ki)
+    }
+
+    /// Changes the loop filter gains, keeping the integral.
+    pub fn set_gains(&mut self, kp: DyadicFraction, ki: DyadicFraction) {
+        self.kp = kp;
+        self.ki = ki;
+    }
+}
+
+/// Shape of a [`Waveform`], each in phase with a sine: zero and rising at
+/// phase zero.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum Shape {
+    #[default]
+    Sine,
+    Triangle,
+    /// Ramps up from zero to one at half a turn, then jumps to minus one.
+    Sawtooth,
+    /// One for the first half turn and minus one for the second.
+    Square,
+}
+
+/// Periodic signal generator driven by a [`PhaseAccumulator`].
+///
+/// Each sample is `offset + amplitude * shape(phase)`, with the unit shape
+/// in `[-1, 1]`, rounded to nearest.
+#[derive(Copy, Clone, Debug)]
+pub struct Waveform {
+    osc: PhaseAccumulator,
+    shape: Shape,
+    amplitude: DyadicFraction,
+    offset: DyadicFraction,
+}
+
+impl Waveform {
+    pub const fn new(
+        shape: Shape,
+        oscillator: PhaseAccumulator,
+        amplitude: DyadicFraction,
+        offset: DyadicFraction,
+    ) -> Self {
+        Self {
+            osc: oscillator,
+            shape,
+            amplitude,
+            offset,
+        }
+    }
+
+    /// Sample at the current phase, without advancing.
+    pub fn sample(&self) -> DyadicFraction {
+        let mut acc = Wide::from_df(self.offset);
+        acc.add_product(self.amplitude, self.unit());
+        acc.round(Rounding::Nearest)
+    }
+
+    /// Returns the sample at the current phase and advances by one tick.
+    pub fn next_sample(&mut self) -> DyadicFraction {
+        let sample = self.sample();
+        self.osc.tick();
+        sample
+    }
+
+    pub const fn shape(&self) -> Shape {
+        self.shape
+    }
+
+    pub fn set_shape(&mut self, shape: Shape) {
+        self.shape = shape;
+    }
+
+    pub const fn amplitude(&self) -> DyadicFraction {
+        self.amplitude
+    }
+
+    pub fn set_amplitude(&mut self, amplitude: DyadicFraction) {
+        self.amplitude = amplitude;
+    }
+
+    pub const fn offset(&self) -> DyadicFraction {
+        self.offset
+    }
+
+    pub fn set_offset(&mut self, offset: DyadicFraction) {
+        self.offset = offset;
+    }
+
+    pub const fn oscillator(&self) -> &PhaseAccumulator {
+        &self.osc
+    }
+
+    /// Gives access to the phase and increment, e.g. to sweep the frequency.
+    pub fn oscillator_mut(&mut self) -> &mut PhaseAccumulator {
+        &mut self.osc
+    }
+
+    /// Unit shape at the current phase.
+    fn unit(&self) -> DyadicFraction {
+        // Phase and result with 64 fractional bits.
+        let phase = self.osc.phase as i128;
+        let one = 1i128 << 64;
+        let unit = match self.shape {
+            Shape::Sine => return trig::sin_cos_turns(self.osc.turns(), i8::MAX).0,
+            Shape::Triangle if phase < one / 4 => 4 * phase,
+            Shape::Triangle if phase < 3 * one / 4 => 2 * one - 4 * phase,
+            Shape::Triangle => 4 * phase - 4 * one,
+            Shape::Sawtooth if phase < one / 2 => 2 * phase,
+            Shape::Sawtooth => 2 * phase - 2 * one,
+            Shape::Square if phase < one / 2 => one,
+            Shape::Square => -one,
+        };
+        DyadicFraction::narrow(unit, 64, Rounding::Nearest)
+    }
+}
+
+/// Yields [`Waveform::next_sample`] values endlessly.
+impl Iterator for Waveform {
+    type Item = DyadicFraction;
+
+    fn next(&mut self) -> Option<DyadicFraction> {
+        Some(self.next_sample())
+    }
+}
+
+/// Envelope follower over the rectified input, for level metering and for
+/// catching current spikes ahead of protection logic.
+///
+/// The envelope moves `attack` of the way towards `|x|` when the input
+/// rises above it and `release` of the way when it falls below, both in
+/// `[0, 1]`; a fast attack with a slow release holds peaks. The update is
+/// computed exactly and rounded to nearest once.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct EnvelopeFollower {
+    attack: DyadicFraction,
+    release: DyadicFraction,
+    envelope: DyadicFraction,
+}
+
+impl EnvelopeFollower {
+    pub const fn new(attack: DyadicFraction, release: DyadicFraction) -> Self {
+        Self {
+            attack,
+            release,
+            envelope: DyadicFraction::zero(),
+        }
+    }
+
+    /// Feeds `sample` and returns the new envelope.
+    pub fn update(&mut self, sample: DyadicFraction) -> DyadicFraction {
+        let mut diff = Wide::from_df(sample);
+        if sample.is_negative() {
+            diff = diff.neg();
+        }
+        diff.sub(Wide::from_df(self.envelope));
+        let coeff = if diff.is_negative() {
+            self.release
+        } else {
+            self.attack
+        };
+        let mut acc = Wide::from_df(self.envelope);
+        acc.add(diff.mul_df(coeff));
+        self.envelope = acc.round(Rounding::Nearest);
+        self.envelope
+    }
+
+    pub const fn value(&self) -> DyadicFraction {
+        self.envelope
+    }
+
+    /// Restarts the envelope from `value`.
+    pub fn reset(&mut self, value: DyadicFraction) {
+        self.envelope = value;
+    }
+
+    /// Coefficients `(attack, release)`.
+    pub const fn coefficients(&self) -> (DyadicFraction, DyadicFraction) {
+        (self.attack, self.release)
+    }
+
+    pub fn set_coefficients(&mut self, attack: DyadicFraction, release: DyadicFraction) {
+        self.attack = attack;
+        self.release = release;
+    }
+}
+
+/// Zero crossing reported by [`ZeroCross::update`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Crossing {
+    pub transition: Transition,
+    /// Samples between the interpolated crossing and the current sample.
+    pub delay: DyadicFraction,
+}
+
+/// Zero-crossing detector with hysteresis, for frequency measurement and
+/// mains phase synchronization.
+///
+/// A crossing is confirmed once the signal goes above `hysteresis` after
+/// having been below `-hysteresis`, or the other way round, so noise around
+/// zero does not produce extra crossings. Its position is the last sign
+/// change before confirmation, interpolated linearly between the two
+/// samples around it, and may lie several samples back.
+#[derive(Copy, Clone, Debug)]
+pub struct ZeroCross {
+    hysteresis: DyadicFraction,
+    positive: Option<bool>,
+    prev: Option<DyadicFraction>,
+    /// Delay of the last sign change, if one is pending.
+    pending: Option<DyadicFraction>,
+    /// Samples since the last rising crossing and its delay then.
+    since_rising: Option<(u32, DyadicFraction)>,
+    period: Option<DyadicFraction>,
+}
+
+impl ZeroCross {
+    /// Creates a detector that reports nothing until the signal has left
+    /// the band `[-hysteresis, hysteresis]` once.
+    pub const fn new(hysteresis: DyadicFraction) -> Self {
+        Self {
+            hysteresis: hysteresis.abs(),
+            positive: None,
+            prev: None,
+            pending: None,
+            since_rising: None,
+            period: None,
+        }
+    }
+
+    /// Feeds `sample` and returns the crossing it confirmed, if any.
+    pub fn update(&mut self, sample: DyadicFraction) -> Option<Crossing> {
+        if let Some(delay) = self.pending {
+            self.pending = Some(delay_by(Wide::from_df(delay), 1));
+        }
+        if let Some((count, delay)) = self.since_rising {
+            self.since_rising = Some((count.saturating_add(1), delay));
+        }
+        if let Some(prev) = self.prev {
+            if prev.is_negative() != sample.is_negative() {
+                // The crossing sits `prev / (prev - sample)` past `prev`.
+                let mut span = Wide::from_df(prev);
+                span.sub(Wide::from_df(sample));
+                let fraction = Wide::from_df(prev).div(span, i8::MAX);
+                self.pending = Some(delay_by(Wide::from_df(fraction).neg(), 1));
+            }
+        }
+        self.prev = Some(sample);
+
+        let positive = if sample > self.hysteresis {
+            true
+        } else if sample < -self.hysteresis {
+            false
+        } else {
+            return None;
+        };
+        let armed = self.positive.replace(positive)?;
+        if armed == positive {
+            return None;
+        }
+        let delay = self.pending.take().unwrap_or_default();
+        let transition = if positive {
+            if let Some((count, last)) = self.since_rising {
+                let mut period = Wide::from_df(last);
+                period.sub(Wide::from_df(delay));
+                self.period = Some(delay_by(period, count));
+            }
+            self.since_rising = Some((0, delay));
+            Transition::Rising
+        } else {
+            Transition::Falling
+        };
+        Some(Crossing { transition, delay })
+    }
+
+    /// Samples between the last two rising crossings, once there are two.
+    pub const fn period(&self) -> Option<DyadicFraction> {
+        self.period
+    }
+
+    /// Forgets the signal history and the period.
+    pub fn reset(&mut self) {
+        *self = Self::new(self.hysteresis);
+    }
+
+    pub const fn hysteresis(&self) -> DyadicFraction {
+        self.hysteresis
+    }
+
+    /// Changes the hysteresis, taking its magnitude.
+    pub fn set_hysteresis(&mut self, hysteresis: DyadicFraction) {
+        self.hysteresis = hysteresis.abs();
+    }
+}
+
+/// `delay + samples`, rounded to nearest.
+fn delay_by(mut delay: Wide, samples: u32) -> DyadicFraction {
+    delay.add(Wide::new(samples as i128, 0));
+    delay.round(Rounding::Nearest)
+}
@@ -0,0 +1,232 @@
+use crate::{bit_len, shr_round, DyadicFraction, Rounding};
+
+/// `2^(2^-i)` for `i` in `1..=32`, with 62 fractional bits.
+const EXP2_BITS: [u64; 32] = [
+    6521908912666391106,
+    5484249825272419512,
+    5029079263719320435,
+    4815862801830788490,
+    4712668792719003884,
+    4661903986662671290,
+    4636727017470743990,
+    4624189567668517720,
+    4617933561212708776,
+    4614808732577250068,
+    4613247111281068008,
+    4612466498810092975,
+    4612076242109103707,
+    4611881126141011236,
+    4611783571252412754,
+    4611734794581956353,
+    4611710406440186476,
+    4611698212417665819,
+    4611692115418496524,
+    4611689066921934630,
+    4611687542674409371,
+    4611686780550835664,
+    4611686399489096040,
+    4611686208958238036,
+    4611686113692811986,
+    4611686066060099699,
+    4611686042243743740,
+    4611686030335565807,
+    4611686024381476851,
+    4611686021404432377,
+    4611686019915910140,
+    4611686019171649022,
+];
+
+/// `ln(2)` with 62 fractional bits.
+const LN_2: u128 = 3196577161300663915;
+
+/// `ln(2)` with 58 fractional bits.
+const LN_2_Q58: i128 = 199786072581291495;
+
+/// `log2(e)` with 62 fractional bits.
+const LOG2_E: i128 = 6653256548922161246;
+
+/// `20 log10(2)`, decibels of amplitude per octave, with 54 fractional bits.
+const DB_PER_OCTAVE: i128 = 108457486103971819;
+
+/// `10 log10(2)`, decibels of power per octave, with 54 fractional bits.
+const DB_POWER_PER_OCTAVE: i128 = 54228743051985909;
+
+/// `log2(10) / 20`, octaves of amplitude per decibel, with 62 fractional bits.
+const OCTAVES_PER_DB: i128 = 765984467470658906;
+
+/// `log2(10) / 10`, octaves of power per decibel, with 62 fractional bits.
+const OCTAVES_PER_DB_POWER: i128 = 1531968934941317811;
+
+const ONE: u128 = 1 << 62;
+
+impl DyadicFraction {
+    /// `2^self` rounded to nearest at a denominator of at most `2^max_power`.
+    ///
+    /// The integer part of the exponent is exact; the fractional part is
+    /// resolved to about 60 bits, so the result carries the full 31 bits of
+    /// precision. Results beyond the representable range saturate.
+    pub const fn exp2(self, max_power: i8) -> Self {
+        exp2_fixed(self.scaled(62, Rounding::Floor), max_power)
+    }
+
+    /// `e^self` rounded to nearest at a denominator of at most `2^max_power`.
+    ///
+    /// Computed as `2^(self * log2(e))` with `log2(e)` to 62 bits, so the
+    /// result stays within one unit of the last place of the exact value.
+    /// Results beyond the representable range saturate.
+    pub const fn exp(self, max_power: i8) -> Self {
+        self.exp2_times(LOG2_E, max_power)
+    }
+
+    /// Base-2 logarithm rounded to nearest at a denominator of at most
+    /// `2^max_power`.
+    ///
+    /// The integer part comes from a bit scan and the fraction from repeated
+    /// squaring, one bit per step. Non-positive values saturate to the most
+    /// negative value.
+    pub fn log2(self, max_power: i8) -> Self {
+        if self.num <= 0 {
+            return Self::saturated(true);
+        }
+        let frac_bits = (max_power as i32 + 2).clamp(0, 60);
+        let (int, mantissa) = self.log2_parts();
+        round_fixed(
+            (int << frac_bits) + log2_mantissa(mantissa, frac_bits),
+            frac_bits,
+            max_power,
+        )
+    }
+
+    /// Natural logarithm rounded to nearest at a denominator of at most
+    /// `2^max_power`.
+    ///
+    /// Computed as `log2(self) * ln(2)` with three guard bits, which keeps the
+    /// error within one unit of `2^-max_power` for `max_power` up to 55.
+    /// Non-positive values saturate to the most negative value.
+    pub fn ln(self, max_power: i8) -> Self {
+        if self.num <= 0 {
+            return Self::saturated(true);
+        }
+        let frac_bits = (max_power as i32 + 3).clamp(0, 60);
+        let (int, mantissa) = self.log2_parts();
+        let log2 = (int << frac_bits) + log2_mantissa(mantissa, frac_bits);
+        round_fixed(log2 * LN_2_Q58, frac_bits + 58, max_power)
+    }
+
+    /// Amplitude ratio in decibels, `20 log10(self)`, rounded to nearest at
+    /// a denominator of at most `2^max_power`.
+    ///
+    /// Computed from `log2(self)` with six guard bits. Non-positive values
+    /// saturate to the most negative value.
+    pub fn to_db(self, max_power: i8) -> Self {
+        self.log2_times(DB_PER_OCTAVE, max_power)
+    }
+
+    /// Power ratio in decibels, `10 log10(self)`, see
+    /// [`DyadicFraction::to_db`].
+    pub fn to_db_power(self, max_power: i8) -> Self {
+        self.log2_times(DB_POWER_PER_OCTAVE, max_power)
+    }
+
+    /// Amplitude ratio of `db` decibels, `10^(db / 20)`, rounded to nearest
+    /// at a denominator of at most `2^max_power`. Results beyond the
+    /// representable range saturate.
+    pub fn from_db(db: Self, max_power: i8) -> Self {
+        db.exp2_times(OCTAVES_PER_DB, max_power)
+    }
+
+    /// Power ratio of `db` decibels, `10^(db / 10)`, see
+    /// [`DyadicFraction::from_db`].
+    pub fn from_db_power(db: Self, max_power: i8) -> Self {
+        db.exp2_times(OCTAVES_PER_DB_POWER, max_power)
+    }
+
+    /// `2^(self * factor)` for a factor with 62 fractional bits.
+    const fn exp2_times(self, factor: i128, max_power: i8) -> Self {
+        let product = self.num as i128 * factor;
+        let exponent = if self.power >= 0 {
+            product >> self.power
+        } else if self.power > -32 {
+            product << -self.power
+        } else if self.num < 0 {
+            return Self::zero();
+        } else {
+            return Self::saturated(false);
+        };
+        exp2_fixed(exponent, max_power)
+    }
+
+    /// `log2(self) * factor` for a factor below 8 with 54 fractional bits.
+    fn log2_times(self, factor: i128, max_power: i8) -> Self {
+        if self.num <= 0 {
+            return Self::saturated(true);
+        }
+        let frac_bits = (max_power as i32 + 6).clamp(0, 60);
+        let (int, mantissa) = self.log2_parts();
+        let log2 = (int << frac_bits) + log2_mantissa(mantissa, frac_bits);
+        round_fixed(log2 * factor, frac_bits + 54, max_power)
+    }
+
+    /// Splits a positive value into `2^int * mantissa` with the mantissa in
+    /// `[1, 2)` with 62 fractional bits.
+    pub(crate) const fn log2_parts(self) -> (i128, u128) {
+        let top = bit_len(self.num as u128) - 1;
+        let mantissa = (self.num as u128) << (62 - top);
+        (top as i128 - self.power as i128, mantissa)
+    }
+}
+
+/// `2^exponent` for an exponent with 62 fractional bits, rounded to nearest
+/// at a denominator of at most `2^max_power`.
+const fn exp2_fixed(exponent: i128, max_power: i8) -> DyadicFraction {
+    let int = exponent >> 62;
+    if int > 160 {
+        return DyadicFraction::saturated(false);
+    }
+    if int < -200 {
+        return DyadicFraction::zero();
+    }
+    let frac = (exponent & (ONE as i128 - 1)) as u128;
+
+    // Each set bit of the fraction contributes a factor `2^(2^-i)`;
+    // below the table, `2^f ≈ 1 + f * ln(2)` to well within 2^-62.
+    let mut mantissa = ONE;
+    let mut i = 0;
+    while i < EXP2_BITS.len() {
+        if frac & (1 << (61 - i)) != 0 {
+            mantissa = (mantissa * EXP2_BITS[i] as u128) >> 62;
+        }
+        i += 1;
+    }
+    let tail = frac & ((1 << 30) - 1);
+    mantissa += (mantissa * ((tail * LN_2) >> 62)) >> 62;
+
+    round_fixed(mantissa as i128, 62 - int as i32, max_power)
+}
+
+/// `log2(mantissa)` for a mantissa in `[1, 2)` with 62 fractional bits,
+/// truncated to `frac_bits` fractional bits.
+pub(crate) fn log2_mantissa(mut mantissa: u128, frac_bits: i32) -> i128 {
+    let mut frac = 0;
+    for _ in 0..frac_bits {
+        mantissa = (mantissa * mantissa) >> 62;
+        frac <<= 1;
+        if mantissa >= 2 * ONE {
+            mantissa >>= 1;
+            frac |= 1;
+        }
+    }
+    frac
+}
+
+/// Rounds `val / 2^frac_bits` to nearest at a denominator of at most
+/// `2^max_power`.
+pub(crate) const fn round_fixed(val: i128, frac_bits: i32, max_power: i8) -> DyadicFraction {
+    let power = if (max_power as i32) < frac_bits {
+        max_power as i32
+    } else {
+        frac_bits
+    };
+    let num = shr_round(val, frac_bits - power, Rounding::Nearest);
+    DyadicFraction::narrow(num, power, Rounding::Nearest)
+}
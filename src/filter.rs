@@ -0,0 +1,708 @@
+//! Filters for streams of dyadic fraction samples.
+
+use crate::ring::Ring;
+use crate::trig;
+use crate::wide::Wide;
+use crate::{consts, DyadicFraction, Rounding};
+
+/// Average of the last `N` samples.
+///
+/// The running sum is kept exactly, so each update costs one addition, one
+/// subtraction and one rounded division regardless of `N`. Until the window
+/// fills up, the output averages the samples seen so far.
+#[derive(Copy, Clone, Debug)]
+pub struct MovingAverage<const N: usize> {
+    window: Ring<N>,
+    sum: Wide,
+}
+
+impl<const N: usize> MovingAverage<N> {
+    pub const fn new() -> Self {
+        Self {
+            window: Ring::new(),
+            sum: Wide::new(0, 0),
+        }
+    }
+
+    /// Feeds `sample` and returns the new average, rounded to nearest at the
+    /// finest power that fits.
+    pub fn update(&mut self, sample: DyadicFraction) -> DyadicFraction {
+        self.sum.add(Wide::from_df(sample));
+        if let Some(oldest) = self.window.push(sample) {
+            self.sum.sub(Wide::from_df(oldest));
+        }
+        self.value()
+    }
+
+    /// Current average, zero before the first sample.
+    pub fn value(&self) -> DyadicFraction {
+        self.sum.average(self.window.len())
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+}
+
+impl<const N: usize> Default for MovingAverage<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Median of the last `N` samples, for rejecting single-sample spikes.
+///
+/// The window is kept insertion-sorted, which is cheapest for the small odd
+/// sizes (3, 5, 7) this is meant for. While the window holds an even number
+/// of samples, the upper of the two middle samples is returned.
+#[derive(Copy, Clone, Debug)]
+pub struct Median<const N: usize> {
+    window: Ring<N>,
+    sorted: [DyadicFraction; N],
+}
+
+impl<const N: usize> Median<N> {
+    pub const fn new() -> Self {
+        Self {
+            window: Ring::new(),
+            sorted: [DyadicFraction::zero(); N],
+        }
+    }
+
+    /// Feeds `sample` and returns the new median.
+    pub fn update(&mut self, sample: DyadicFraction) -> DyadicFraction {
+        let len = self.window.len();
+        let len = match self.window.push(sample) {
+            Some(oldest) => match self.sorted[..len].iter().position(|&val| val == oldest) {
+                Some(pos) => {
+                    self.sorted.copy_within(pos + 1..len, pos);
+                    len - 1
+                }
+                None => len,
+            },
+            None => len,
+        };
+        if len < N {
+            let pos = self.sorted[..len].partition_point(|&val| val < sample);
+            self.sorted.copy_within(pos..len, pos + 1);
+            self.sorted[pos] = sample;
+        }
+        self.value()
+    }
+
+    /// Current median, zero before the first sample.
+    pub fn value(&self) -> DyadicFraction {
+        match self.window.len() {
+            0 => DyadicFraction::zero(),
+            len => self.sorted[len / 2],
+        }
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+}
+
+impl<const N: usize> Default for Median<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Rolling minimum and maximum of the last `N` samples.
+///
+/// Each extreme is tracked with a monotonic deque, so updates cost O(1)
+/// amortized comparisons.
+#[derive(Copy, Clone, Debug)]
+pub struct WindowMinMax<const N: usize> {
+    index: usize,
+    min: Deque<N>,
+    max: Deque<N>,
+}
+
+impl<const N: usize> WindowMinMax<N> {
+    pub const fn new() -> Self {
+        Self {
+            index: 0,
+            min: Deque::new(),
+            max: Deque::new(),
+        }
+    }
+
+    /// Feeds `sample` and returns the new `(min, max)` pair.
+    pub fn update(&mut self, sample: DyadicFraction) -> (DyadicFraction, DyadicFraction) {
+        self.min.push(self.index, sample, |back| back >= sample);
+        self.max.push(self.index, sample, |back| back <= sample);
+        self.index = self.index.wrapping_add(1);
+        (self.min(), self.max())
+    }
+
+    /// Smallest sample in the window, zero before the first sample.
+    pub fn min(&self) -> DyadicFraction {
+        self.min.front()
+    }
+
+    /// Largest sample in the window, zero before the first sample.
+    pub fn max(&self) -> DyadicFraction {
+        self.max.front()
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+}
+
+impl<const N: usize> Default for WindowMinMax<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// First-order IIR low-pass filter, `y += alpha * (x - y)`.
+///
+/// The update is computed exactly and rounded to nearest once.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct LowPass1 {
+    alpha: DyadicFraction,
+    state: DyadicFraction,
+}
+
+impl LowPass1 {
+    /// Creates a filter with smoothing factor `alpha` in `[0, 1]`; larger
+    /// values track the input faster.
+    pub const fn new(alpha: DyadicFraction) -> Self {
+        Self {
+            alpha,
+            state: DyadicFraction::zero(),
+        }
+    }
+
+    /// Creates a filter from a cutoff frequency and the sample rate, in the
+    /// same unit, with `alpha = 2πfc / (2πfc + fs)`.
+    pub fn from_cutoff(cutoff: DyadicFraction, sample_rate: DyadicFraction) -> Self {
+        let omega = Wide::product(consts::TAU, cutoff);
+        let mut den = omega;
+        den.add(Wide::from_df(sample_rate));
+        Self::new(omega.div(den, i8::MAX))
+    }
+
+    /// Feeds `sample` and returns the filtered value.
+    pub fn update(&mut self, sample: DyadicFraction) -> DyadicFraction {
+        let mut acc = Wide::from_df(self.state);
+        acc.add_product(self.alpha, sample);
+        acc.sub_product(self.alpha, self.state);
+        self.state = acc.round(Rounding::Nearest);
+        self.state
+    }
+
+    pub const fn value(&self) -> DyadicFraction {
+        self.state
+    }
+
+    /// Restarts the filter from `value`, e.g. the first sample, to skip the
+    /// initial settling.
+    pub fn reset(&mut self, value: DyadicFraction) {
+        self.state = value;
+    }
+
+    pub const fn alpha(&self) -> DyadicFraction {
+        self.alpha
+    }
+
+    pub fn set_alpha(&mut self, alpha: DyadicFraction) {
+        self.alpha = alpha;
+    }
+}
+
+/// DC blocker, the high-pass filter `y[n] = x[n] - x[n-1] + r * y[n-1]`.
+///
+/// A pole `r` just below one, such as `0.995`, keeps the passband flat down
+/// to low frequencies. The update is computed exactly and rounded to nearest
+/// once.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct DcBlocker {
+    pole: DyadicFraction,
+    input: DyadicFraction,
+    output: DyadicFraction,
+}
+
+impl DcBlocker {
+    pub const fn new(pole: DyadicFraction) -> Self {
+        Self {
+            pole,
+            input: DyadicFraction::zero(),
+            output: DyadicFraction::zero(),
+        }
+    }
+
+    /// Feeds `sample` and returns the filtered value.
+    pub fn update(&mut self, sample: DyadicFraction) -> DyadicFraction {
+        let mut acc = Wide::from_df(sample);
+        acc.sub(Wide::from_df(self.input));
+        acc.add_product(self.pole, self.output);
+        self.input = sample;
+        self.output = acc.round(Rounding::Nearest);
+        self.output
+    }
+
+    pub const fn value(&self) -> DyadicFraction {
+        self.output
+    }
+
+    /// Restarts the filter assuming the input has been at `offset`, so a
+    /// known DC level does not produce a step at the output.
+    pub fn reset(&mut self, offset: DyadicFraction) {
+        self.input = offset;
+        self.output = DyadicFraction::zero();
+    }
+
+    pub const fn pole(&self) -> DyadicFraction {
+        self.pole
+    }
+
+    pub fn set_pole(&mut self, pole: DyadicFraction) {
+        self.pole = pole;
+    }
+}
+
+/// Second-order IIR section in Direct Form I,
+/// `y[n] = b0 x[n] + b1 x[n-1] + b2 x[n-2] - a1 y[n-1] - a2 y[n-2]`.
+///
+/// Coefficients are normalized so that `a0 = 1`. All five products are
+/// accumulated exactly and rounded to nearest once per sample.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Biquad {
+    b: [DyadicFraction; 3],
+    a: [DyadicFraction; 2],
+    inputs: [DyadicFraction; 2],
+    outputs: [DyadicFraction; 2],
+}
+
+impl Biquad {
+    /// Creates a section from the feed-forward `[b0, b1, b2]` and feedback
+    /// `[a1, a2]` coefficients.
+    pub const fn new(b: [DyadicFraction; 3], a: [DyadicFraction; 2]) -> Self {
+        Self {
+            b,
+            a,
+            inputs: [DyadicFraction::zero(); 2],
+            outputs: [DyadicFraction::zero(); 2],
+        }
+    }
+
+    /// Band-stop section rejecting `f0_over_fs`, the notch frequency as a
+    /// fraction of the sample rate; a higher quality factor `q` narrows the
+    /// stop band.
+    pub fn notch(f0_over_fs: DyadicFraction, q: DyadicFraction) -> Self {
+        let (cos, alpha) = prewarp(f0_over_fs, q);
+        let one = Wide::from_df(DyadicFraction::from(1));
+        let mid = Wide::product(cos, DyadicFraction::from(-2));
+        Self::from_cookbook([one, mid, one], cos, alpha)
+    }
+
+    /// Second-order low-pass section with cutoff `f0_over_fs`, as a fraction
+    /// of the sample rate, and quality factor `q`.
+    pub fn low_pass(f0_over_fs: DyadicFraction, q: DyadicFraction) -> Self {
+        let (cos, alpha) = prewarp(f0_over_fs, q);
+        let mut mid = Wide::from_df(DyadicFraction::from(1));
+        mid.sub(Wide::from_df(cos));
+        let side = mid.mul_df(DyadicFraction::new(1, 1));
+        Self::from_cookbook([side, mid, side], cos, alpha)
+    }
+
+    /// Second-order high-pass section with cutoff `f0_over_fs`, as a fraction
+    /// of the sample rate, and quality factor `q`.
+    pub fn high_pass(f0_over_fs: DyadicFraction, q: DyadicFraction) -> Self {
+        let (cos, alpha) = prewarp(f0_over_fs, q);
+        let mut mid = Wide::from_df(DyadicFraction::from(1));
+        mid.add(Wide::from_df(cos));
+        let side = mid.mul_df(DyadicFraction::new(1, 1));
+        Self::from_cookbook([side, mid.neg(), side], cos, alpha)
+    }
+
+    /// Normalizes the Audio EQ Cookbook coefficients by `a0 = 1 + alpha`.
+    fn from_cookbook(b: [Wide; 3], cos: DyadicFraction, alpha: DyadicFraction) -> Self {
+        let one = Wide::from_df(DyadicFraction::from(1));
+        let mut a0 = one;
+        a0.add(Wide::from_df(alpha));
+        let mut a2 = one;
+        a2.sub(Wide::from_df(alpha));
+        let a1 = Wide::product(cos, DyadicFraction::from(-2));
+        Self::new(
+            b.map(|val| val.div(a0, i8::MAX)),
+            [a1.div(a0, i8::MAX), a2.div(a0, i8::MAX)],
+        )
+    }
+
+    /// Feeds `sample` and returns the filtered value.
+    pub fn update(&mut self, sample: DyadicFraction) -> DyadicFraction {
+        let [x1, x2] = self.inputs;
+        let [y1, y2] = self.outputs;
+        let mut acc = Wide::product(self.b[0], sample);
+        acc.add_product(self.b[1], x1);
+        acc.add_product(self.b[2], x2);
+        acc.sub_product(self.a[0], y1);
+        acc.sub_product(self.a[1], y2);
+        let output = acc.round(Rounding::Nearest);
+        self.inputs = [sample, x1];
+        self.outputs = [output, y1];
+        output
+    }
+
+    pub const fn value(&self) -> DyadicFraction {
+        self.outputs[0]
+    }
+
+    /// Clears the delay lines.
+    pub fn reset(&mut self) {
+        *self = Self::new(self.b, self.a);
+    }
+
+    /// Feed-forward coefficients `[b0, b1, b2]`.
+    pub const fn b(&self) -> [DyadicFraction; 3] {
+        self.b
+    }
+
+    /// Feedback coefficients `[a1, a2]`.
+    pub const fn a(&self) -> [DyadicFraction; 2] {
+        self.a
+    }
+}
+
+/// Finite impulse response filter with `N` taps,
+/// `y[n] = h[0] x[n] + h[1] x[n-1] + ... + h[N-1] x[n-N+1]`.
+///
+/// The convolution is accumulated exactly and rounded to nearest once per
+/// sample.
+#[derive(Copy, Clone, Debug)]
+pub struct Fir<const N: usize> {
+    taps: [DyadicFraction; N],
+    delay: [DyadicFraction; N],
+    head: usize,
+    output: DyadicFraction,
+}
+
+impl<const N: usize> Fir<N> {
+    pub const fn new(taps: [DyadicFraction; N]) -> Self {
+        Self {
+            taps,
+            delay: [DyadicFraction::zero(); N],
+            head: 0,
+            output: DyadicFraction::zero(),
+        }
+    }
+
+    /// Feeds `sample` and returns the filtered value.
+    pub fn update(&mut self, sample: DyadicFraction) -> DyadicFraction {
+        if N == 0 {
+            return self.output;
+        }
+        // The delay line is written backwards, so it reads newest-first from
+        // `head` and wraps around once.
+        self.head = (self.head + N - 1) % N;
+        self.delay[self.head] = sample;
+        let (older, newer) = self.delay.split_at(self.head);
+        let (front, back) = self.taps.split_at(N - self.head);
+        let mut acc = Wide::default();
+        acc.add_dot(front, newer);
+        acc.add_dot(back, older);
+        self.output = acc.round(Rounding::Nearest);
+        self.output
+    }
+
+    pub const fn value(&self) -> DyadicFraction {
+        self.output
+    }
+
+    /// Clears the delay line.
+    pub fn reset(&mut self) {
+        *self = Self::new(self.taps);
+    }
+
+    pub const fn taps(&self) -> &[DyadicFraction; N] {
+        &self.taps
+    }
+}
+
+/// Polynomial degree of a [`SavGol`] fit.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SavGolFit {
+    /// Quadratic fit, for windows of 5, 7, 9 or 11 samples. A cubic fit
+    /// gives the same smoothed value.
+    Quadratic,
+    /// Quartic fit, for windows of 7, 9 or 11 samples. Keeps narrow peaks
+    /// better at the cost of less noise reduction.
+    Quartic,
+}
+
+/// Savitzky–Golay filter: a least-squares polynomial fit over the last `N`
+/// samples, evaluated at the centre of the window.
+///
+/// Smooths noise while keeping the height and width of peaks far better
+/// than a [`MovingAverage`] of the same length, and gives the slope of the
+/// fit as a low-noise derivative estimate, e.g. ahead of a derivative term.
+/// Both refer to the centre sample, `(N - 1) / 2` samples behind the input.
+///
+/// The tabulated weights are integers over a common divisor, so each
+/// output is computed exactly and rounded to nearest once.
+#[derive(Copy, Clone, Debug)]
+pub struct SavGol<const N: usize> {
+    fit: SavGolFit,
+    smooth: &'static [i32],
+    smooth_div: i32,
+    slope: &'static [i32],
+    slope_div: i32,
+    window: [DyadicFraction; N],
+    head: usize,
+    filled: bool,
+}
+
+impl<const N: usize> SavGol<N> {
+    /// Creates a filter, or returns `None` when no weights are tabulated
+    /// for the window length and fit.
+    pub fn new(fit: SavGolFit) -> Option<Self> {
+        let (smooth, smooth_div, slope, slope_div): (&[i32], _, &[i32], _) = match (fit, N) {
+            (SavGolFit::Quadratic, 5) => (&[-3, 12, 17, 12, -3], 35, &[-2, -1, 0, 1, 2], 10),
+            (SavGolFit::Quadratic, 7) => {
+                (&[-2, 3, 6, 7, 6, 3, -2], 21, &[-3, -2, -1, 0, 1, 2, 3], 28)
+            }
+            (SavGolFit::Quadratic, 9) => (
+                &[-21, 14, 39, 54, 59, 54, 39, 14, -21],
+                231,
+                &[-4, -3, -2, -1, 0, 1, 2, 3, 4],
+                60,
+            ),
+            (SavGolFit::Quadratic, 11) => (
+                &[-36, 9, 44, 69, 84, 89, 84, 69, 44, 9, -36],
+                429,
+                &[-5, -4, -3, -2, -1, 0, 1, 2, 3, 4, 5],
+                110,
+            ),
+            (SavGolFit::Quartic, 7) => (
+                &[5, -30, 75, 131, 75, -30, 5],
+                231,
+                &[22, -67, -58, 0, 58, 67, -22],
+                252,
+            ),
+            (SavGolFit::Quartic, 9) => (
+                &[15, -55, 30, 135, 179, 135, 30, -55, 15],
+                429,
+                &[86, -142, -193, -126, 0, 126, 193, 142, -86],
+                1188,
+            ),
+            (SavGolFit::Quartic, 11) => (
+                &[18, -45, -10, 60, 120, 143, 120, 60, -10, -45, 18],
+                429,
+                &[300, -294, -532, -503, -296, 0, 296, 503, 532, 294, -300],
+                5148,
+            ),
+            _ => return None,
+        };
+        Some(Self {
+            fit,
+            smooth,
+            smooth_div,
+            slope,
+            slope_div,
+            window: [DyadicFraction::zero(); N],
+            head: 0,
+            filled: false,
+        })
+    }
+
+    /// Feeds `sample` and returns the smoothed centre sample. The window
+    /// starts out filled with the first sample.
+    pub fn update(&mut self, sample: DyadicFraction) -> DyadicFraction {
+        if !self.filled {
+            self.window = [sample; N];
+            self.filled = true;
+        }
+        self.window[self.head] = sample;
+        self.head = (self.head + 1) % N;
+        self.value()
+    }
+
+    /// Smoothed centre sample, zero before the first sample.
+    pub fn value(&self) -> DyadicFraction {
+        self.weighted(self.smooth)
+            .div(Wide::new(self.smooth_div as i128, 0), i8::MAX)
+    }
+
+    /// Slope of the fit at the centre sample per unit of time, for samples
+    /// `dt` apart.
+    pub fn derivative(&self, dt: DyadicFraction) -> DyadicFraction {
+        let den = Wide::new(self.slope_div as i128, 0).mul_df(dt);
+        self.weighted(self.slope).div(den, i8::MAX)
+    }
+
+    /// Clears the window.
+    pub fn reset(&mut self) {
+        self.window = [DyadicFraction::zero(); N];
+        self.head = 0;
+        self.filled = false;
+    }
+
+    pub const fn fit(&self) -> SavGolFit {
+        self.fit
+    }
+
+    /// Samples between the input and the centre sample the outputs refer to.
+    pub const fn delay(&self) -> usize {
+        (N - 1) / 2
+    }
+
+    /// Sum of `weights` times the window, oldest sample first.
+    fn weighted(&self, weights: &[i32]) -> Wide {
+        let (newer, older) = self.window.split_at(self.head);
+        let mut acc = Wide::default();
+        for (&weight, &sample) in weights.iter().zip(older.iter().chain(newer)) {
+            acc.add_product(DyadicFraction::from(weight), sample);
+        }
+        acc
+    }
+}
+
+/// Scalar Kalman filter for a constant-value model, such as a slowly
+/// drifting temperature or state of charge.
+///
+/// The gain is rounded to nearest at the finest power that fits; the
+/// estimate and variance updates are computed exactly and rounded once.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Kalman1d {
+    estimate: DyadicFraction,
+    variance: DyadicFraction,
+    process_noise: DyadicFraction,
+    measurement_noise: DyadicFraction,
+}
+
+impl Kalman1d {
+    /// Creates a filter starting from `estimate` with uncertainty
+    /// `variance`, with the given process and measurement noise variances.
+    pub const fn new(
+        estimate: DyadicFraction,
+        variance: DyadicFraction,
+        process_noise: DyadicFraction,
+        measurement_noise: DyadicFraction,
+    ) -> Self {
+        Self {
+            estimate,
+            variance,
+            process_noise,
+            measurement_noise,
+        }
+    }
+
+    /// Advances one time step, growing the variance by the process noise.
+    pub fn predict(&mut self) {
+        let mut variance = Wide::from_df(self.variance);
+        variance.add(Wide::from_df(self.process_noise));
+        self.variance = variance.round(Rounding::Nearest);
+    }
+
+    /// Folds in `measurement` and returns the new estimate.
+    pub fn update(&mut self, measurement: DyadicFraction) -> DyadicFraction {
+        let mut total = Wide::from_df(self.variance);
+        total.add(Wide::from_df(self.measurement_noise));
+        let gain = if total.is_zero() {
+            DyadicFraction::zero()
+        } else {
+            Wide::from_df(self.variance).div(total, i8::MAX)
+        };
+
+        let mut estimate = Wide::from_df(self.estimate);
+        estimate.add_product(gain, measurement);
+        estimate.sub_product(gain, self.estimate);
+        self.estimate = estimate.round(Rounding::Nearest);
+
+        let mut variance = Wide::from_df(self.variance);
+        variance.sub_product(gain, self.variance);
+        self.variance = variance.round(Rounding::Nearest);
+        self.estimate
+    }
+
+    pub const fn estimate(&self) -> DyadicFraction {
+        self.estimate
+    }
+
+    pub const fn variance(&self) -> DyadicFraction {
+        self.variance
+    }
+
+    /// Restarts the filter from `estimate` with uncertainty `variance`.
+    pub fn reset(&mut self, estimate: DyadicFraction, variance: DyadicFraction) {
+        self.estimate = estimate;
+        self.variance = variance;
+    }
+
+    pub const fn process_noise(&self) -> DyadicFraction {
+        self.process_noise
+    }
+
+    pub fn set_process_noise(&mut self, process_noise: DyadicFraction) {
+        self.process_noise = process_noise;
+    }
+
+    pub const fn measurement_noise(&self) -> DyadicFraction {
+        self.measurement_noise
+    }
+
+    pub fn set_measurement_noise(&mut self, measurement_noise: DyadicFraction) {
+        self.measurement_noise = measurement_noise;
+    }
+}
+
+/// `cos(w0)` and `alpha = sin(w0) / 2q` for `w0 = 2π * f0_over_fs`.
+fn prewarp(f0_over_fs: DyadicFraction, q: DyadicFraction) -> (DyadicFraction, DyadicFraction) {
+    let (sin, cos) = trig::sin_cos_turns(f0_over_fs, 31);
+    let alpha = Wide::from_df(sin).div(Wide::product(q, DyadicFraction::from(2)), i8::MAX);
+    (cos, alpha)
+}
+
+/// Indexed samples of the last `N` updates, with monotonic values from front
+/// to back.
+#[derive(Copy, Clone, Debug)]
+struct Deque<const N: usize> {
+    buf: [(usize, DyadicFraction); N],
+    head: usize,
+    len: usize,
+}
+
+impl<const N: usize> Deque<N> {
+    const fn new() -> Self {
+        Self {
+            buf: [(0, DyadicFraction::zero()); N],
+            head: 0,
+            len: 0,
+        }
+    }
+
+    /// Expires samples older than `N` updates, drops the samples at the back
+    /// that `dominated` says can no longer be the extreme, and appends.
+    fn push(
+        &mut self,
+        index: usize,
+        sample: DyadicFraction,
+        dominated: impl Fn(DyadicFraction) -> bool,
+    ) {
+        if N == 0 {
+            return;
+        }
+        while self.len > 0 && index.wrapping_sub(self.buf[self.head].0) >= N {
+            self.head = (self.head + 1) % N;
+            self.len -= 1;
+        }
+        while self.len > 0 && dominated(self.buf[(self.head + self.len - 1) % N].1) {
+            self.len -= 1;
+        }
+        self.buf[(self.head + self.len) % N] = (index, sample);
+        self.len += 1;
+    }
+
+    fn front(&self) -> DyadicFraction {
+        if self.len == 0 {
+            DyadicFraction::zero()
+        } else {
+            self.buf[self.head].1
+        }
+    }
+}
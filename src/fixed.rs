@@ -0,0 +1,124 @@
+use crate::{DyadicFraction, Rounding};
+use core::fmt;
+use core::ops::*;
+
+/// A fixed-point value whose denominator power `P` is part of the type.
+///
+/// The value is stored as a bare `i32` numerator scaled by `2^-P`, so a
+/// buffer of samples is guaranteed to share one resolution. Arithmetic
+/// saturates to the `i32` range and products round towards negative infinity.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct DyadicFixed<const P: i8> {
+    raw: i32,
+}
+
+impl<const P: i8> DyadicFixed<P> {
+    pub const ZERO: Self = Self { raw: 0 };
+    pub const MIN: Self = Self { raw: i32::MIN };
+    pub const MAX: Self = Self { raw: i32::MAX };
+
+    pub const fn from_raw(raw: i32) -> Self {
+        Self { raw }
+    }
+
+    pub const fn raw(self) -> i32 {
+        self.raw
+    }
+
+    /// Converts from a dyadic fraction with the given rounding, saturating
+    /// to the representable range.
+    pub fn from_df(val: DyadicFraction, rounding: Rounding) -> Self {
+        let raw = val.scaled(P as i32, rounding);
+        Self::from_wide(raw)
+    }
+
+    pub const fn to_df(self) -> DyadicFraction {
+        DyadicFraction::from_wide(self.raw as i128, P as i32)
+    }
+
+    pub const fn abs(self) -> Self {
+        Self::from_raw(self.raw.saturating_abs())
+    }
+
+    fn from_wide(raw: i128) -> Self {
+        Self::from_raw(raw.clamp(i32::MIN as i128, i32::MAX as i128) as i32)
+    }
+}
+
+impl<const P: i8> From<DyadicFixed<P>> for DyadicFraction {
+    fn from(val: DyadicFixed<P>) -> Self {
+        val.to_df()
+    }
+}
+
+/// Rounds towards negative infinity, saturating to the representable range.
+impl<const P: i8> From<DyadicFraction> for DyadicFixed<P> {
+    fn from(val: DyadicFraction) -> Self {
+        Self::from_df(val, Rounding::Floor)
+    }
+}
+
+impl<const P: i8> Add for DyadicFixed<P> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::from_raw(self.raw.saturating_add(other.raw))
+    }
+}
+
+impl<const P: i8> AddAssign for DyadicFixed<P> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<const P: i8> Sub for DyadicFixed<P> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::from_raw(self.raw.saturating_sub(other.raw))
+    }
+}
+
+impl<const P: i8> SubAssign for DyadicFixed<P> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<const P: i8> Mul for DyadicFixed<P> {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        let product = self.raw as i128 * other.raw as i128;
+        let raw = match P {
+            0.. => product >> P.unsigned_abs().min(127),
+            _ if P > -64 => product << -P,
+            _ if product > 0 => i128::MAX,
+            _ if product < 0 => i128::MIN,
+            _ => 0,
+        };
+        Self::from_wide(raw)
+    }
+}
+
+impl<const P: i8> MulAssign for DyadicFixed<P> {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl<const P: i8> Neg for DyadicFixed<P> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::from_raw(self.raw.saturating_neg())
+    }
+}
+
+impl<const P: i8> fmt::Display for DyadicFixed<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.to_df(), f)
+    }
+}
@@ -0,0 +1,126 @@
+//! Process identification from recorded responses, for tuning controllers
+//! and parameterizing plant models.
+
+use crate::wide::Wide;
+use crate::{DyadicFraction, Rounding};
+
+/// `1 - e^(-1/3)` with 60 fractional bits.
+const EARLY: i128 = 326817147922197888;
+/// `1 - e^(-1)` with 60 fractional bits.
+const LATE: i128 = 728785385777541632;
+
+/// First-order lag with dead time fitted to a step response,
+/// `G(s) = gain e^(-dead_time s) / (tau s + 1)`.
+///
+/// Uses the two-point method of Smith: such a response covers
+/// `1 - e^(-1/3)` (28.3%) of its final change at `dead_time + tau / 3` and
+/// `1 - e^(-1)` (63.2%) at `dead_time + tau`. Crossing times are
+/// interpolated linearly between samples, and all results are rounded to
+/// nearest. The fitted values match [`FirstOrderPlant`](crate::sim::FirstOrderPlant)
+/// and [`SmithPredictor`](crate::control::SmithPredictor).
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct FirstOrderFit {
+    gain: DyadicFraction,
+    tau: DyadicFraction,
+    dead_time: DyadicFraction,
+}
+
+impl FirstOrderFit {
+    /// Fits `(time, output)` samples recorded after an input change of
+    /// `step` applied at the time of the first sample. The first output is
+    /// taken as the initial value and the last as settled.
+    ///
+    /// Returns `None` with fewer than three samples, a zero step or no
+    /// change in the output. A dead time that comes out negative because
+    /// of noise is clamped to zero.
+    pub fn fit(samples: &[(DyadicFraction, DyadicFraction)], step: DyadicFraction) -> Option<Self> {
+        let (&(start, initial), &(_, settled)) = (samples.first()?, samples.last()?);
+        if samples.len() < 3 || step.num == 0 {
+            return None;
+        }
+        let mut change = Wide::from_df(settled);
+        change.sub(Wide::from_df(initial));
+        if change.is_zero() {
+            return None;
+        }
+        let rising = !change.is_negative();
+        let gain = change.div(Wide::from_df(step), i8::MAX);
+        let change = change.round(Rounding::Nearest);
+
+        let (early, from) = crossing(samples, 0, level(initial, EARLY, change), rising);
+        let (late, _) = crossing(samples, from, level(initial, LATE, change), rising);
+        let mut span = late;
+        span.sub(early);
+        let tau = span
+            .mul_df(DyadicFraction::new(3, 1))
+            .round(Rounding::Nearest);
+        let mut dead_time = late;
+        dead_time.sub(Wide::from_df(tau));
+        dead_time.sub(Wide::from_df(start));
+        let dead_time = if dead_time.is_negative() {
+            DyadicFraction::zero()
+        } else {
+            dead_time.round(Rounding::Nearest)
+        };
+        Some(Self {
+            gain,
+            tau,
+            dead_time,
+        })
+    }
+
+    /// Steady-state output change per unit of input change.
+    pub const fn gain(&self) -> DyadicFraction {
+        self.gain
+    }
+
+    pub const fn tau(&self) -> DyadicFraction {
+        self.tau
+    }
+
+    pub const fn dead_time(&self) -> DyadicFraction {
+        self.dead_time
+    }
+}
+
+/// `initial + fraction * change` with `fraction` in 60 fractional bits.
+fn level(initial: DyadicFraction, fraction: i128, change: DyadicFraction) -> Wide {
+    let mut level = Wide::from_df(initial);
+    level.add(Wide::new(fraction, 60).mul_df(change));
+    level
+}
+
+/// Interpolated time at which the samples first reach `level`, searching
+/// from index `from`, together with the index of the first sample at or
+/// past it. Falls back to the last sample when the level is never reached.
+fn crossing(
+    samples: &[(DyadicFraction, DyadicFraction)],
+    from: usize,
+    level: Wide,
+    rising: bool,
+) -> (Wide, usize) {
+    let reached = |val: DyadicFraction| {
+        let mut diff = Wide::from_df(val);
+        diff.sub(level);
+        diff.is_zero() || diff.is_negative() != rising
+    };
+    let Some(idx) = (from..samples.len()).find(|&idx| reached(samples[idx].1)) else {
+        let last = samples.len() - 1;
+        return (Wide::from_df(samples[last].0), last);
+    };
+    let (time, val) = samples[idx];
+    if idx == 0 {
+        return (Wide::from_df(time), idx);
+    }
+    let (prev_time, prev_val) = samples[idx - 1];
+    let mut rise = level;
+    rise.sub(Wide::from_df(prev_val));
+    let mut run = Wide::from_df(val);
+    run.sub(Wide::from_df(prev_val));
+    let fraction = rise.div(run, i8::MAX);
+    let mut interval = Wide::from_df(time);
+    interval.sub(Wide::from_df(prev_time));
+    let mut res = Wide::from_df(prev_time);
+    res.add(interval.mul_df(fraction));
+    (res, idx)
+}
@@ -0,0 +1,235 @@
+//! Table lookup with interpolation between breakpoints.
+
+use crate::wide::Wide;
+use crate::DyadicFraction;
+
+/// A one-dimensional lookup table with clamped linear interpolation.
+///
+/// Breakpoints are either listed explicitly and searched by bisection, or
+/// spaced uniformly so the segment follows from a single division.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Lut1d<const N: usize> {
+    x: Breakpoints<N>,
+    y: [DyadicFraction; N],
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Breakpoints<const N: usize> {
+    Sorted([DyadicFraction; N]),
+    Uniform {
+        start: DyadicFraction,
+        step: DyadicFraction,
+    },
+}
+
+impl<const N: usize> Lut1d<N> {
+    /// Creates a table with values `y[i]` at breakpoints `x[i]`, or `None`
+    /// unless the table is non-empty and `x` is strictly increasing.
+    pub fn new(x: [DyadicFraction; N], y: [DyadicFraction; N]) -> Option<Self> {
+        if !is_axis(&x) {
+            return None;
+        }
+        Some(Self {
+            x: Breakpoints::Sorted(x),
+            y,
+        })
+    }
+
+    /// Creates a table with values `y[i]` at `start + i * step`, or `None`
+    /// unless the table is non-empty and `step` is positive.
+    pub const fn uniform(
+        start: DyadicFraction,
+        step: DyadicFraction,
+        y: [DyadicFraction; N],
+    ) -> Option<Self> {
+        if N == 0 || !step.is_positive() {
+            return None;
+        }
+        Some(Self {
+            x: Breakpoints::Uniform { start, step },
+            y,
+        })
+    }
+
+    /// Value at `x`, interpolated linearly between the neighbouring
+    /// breakpoints and clamped to the first and last value outside of them.
+    ///
+    /// The position within the segment is rounded to nearest with at least
+    /// 31 significant bits; the interpolation itself is computed exactly and
+    /// narrowed rounding towards negative infinity.
+    pub fn lookup(&self, x: DyadicFraction) -> DyadicFraction {
+        let (lo, hi, t) = match self.x {
+            Breakpoints::Sorted(xs) => segment(&xs, x, Edge::Clamp),
+            Breakpoints::Uniform { start, step } => {
+                let mut offset = Wide::from_df(x);
+                offset.sub(Wide::from_df(start));
+                let pos = offset.div(Wide::from_df(step), i8::MAX);
+                let (index, t) = pos.to_int_and_frac();
+                if index < 0 {
+                    (0, 0, DyadicFraction::zero())
+                } else if index >= N as i64 - 1 {
+                    (N - 1, N - 1, DyadicFraction::zero())
+                } else {
+                    (index as usize, index as usize + 1, t)
+                }
+            }
+        };
+        lerp(self.y[lo], self.y[hi], t)
+    }
+}
+
+/// How a [`Lut2d`] treats inputs outside of its breakpoints.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Edge {
+    /// Holds the value at the nearest breakpoint.
+    #[default]
+    Clamp,
+    /// Continues the slope of the outermost segment.
+    Extrapolate,
+}
+
+/// A two-dimensional lookup table with bilinear interpolation, for maps
+/// such as `duty = f(rpm, load)`.
+///
+/// `values[row][col]` is the value at `(x[col], y[row])`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Lut2d<const ROWS: usize, const COLS: usize> {
+    x: [DyadicFraction; COLS],
+    y: [DyadicFraction; ROWS],
+    values: [[DyadicFraction; COLS]; ROWS],
+    edge: Edge,
+}
+
+impl<const ROWS: usize, const COLS: usize> Lut2d<ROWS, COLS> {
+    /// Creates a table, or `None` unless both axes are non-empty and
+    /// strictly increasing.
+    pub fn new(
+        x: [DyadicFraction; COLS],
+        y: [DyadicFraction; ROWS],
+        values: [[DyadicFraction; COLS]; ROWS],
+        edge: Edge,
+    ) -> Option<Self> {
+        if !is_axis(&x) || !is_axis(&y) {
+            return None;
+        }
+        Some(Self { x, y, values, edge })
+    }
+
+    /// Value at `(x, y)`, interpolated between the four surrounding grid
+    /// points and treated at the edges according to [`Lut2d::edge`].
+    ///
+    /// Positions within the cell are rounded to nearest with at least 31
+    /// significant bits; the interpolation is accumulated in a wide
+    /// accumulator and narrowed once, rounding towards negative infinity.
+    pub fn lookup(&self, x: DyadicFraction, y: DyadicFraction) -> DyadicFraction {
+        let (left, right, tx) = segment(&self.x, x, self.edge);
+        let (top, bottom, ty) = segment(&self.y, y, self.edge);
+        let upper = lerp_wide(self.values[top][left], self.values[top][right], tx);
+        let mut lower = lerp_wide(self.values[bottom][left], self.values[bottom][right], tx);
+        lower.sub(upper);
+        let mut acc = upper;
+        acc.add(lower.mul_df(ty));
+        acc.to_df()
+    }
+
+    pub const fn edge(&self) -> Edge {
+        self.edge
+    }
+
+    pub fn set_edge(&mut self, edge: Edge) {
+        self.edge = edge;
+    }
+}
+
+fn is_axis(bps: &[DyadicFraction]) -> bool {
+    !bps.is_empty() && bps.windows(2).all(|pair| pair[0] < pair[1])
+}
+
+/// Neighbouring breakpoints of `x` and its position between them. Outside of
+/// the breakpoints the position is either clamped or extrapolated from the
+/// outermost segment.
+fn segment(
+    bps: &[DyadicFraction],
+    x: DyadicFraction,
+    edge: Edge,
+) -> (usize, usize, DyadicFraction) {
+    let last = bps.len() - 1;
+    let index = bps.partition_point(|&bp| bp <= x);
+    let lo = index.clamp(1, last.max(1)) - 1;
+    let hi = (lo + 1).min(last);
+    if lo == hi {
+        return (lo, hi, DyadicFraction::zero());
+    }
+    if edge == Edge::Clamp {
+        if index == 0 {
+            return (lo, hi, DyadicFraction::zero());
+        }
+        if index > last {
+            return (hi, hi, DyadicFraction::zero());
+        }
+    }
+    (lo, hi, ratio(x, bps[lo], bps[hi]))
+}
+
+/// Position of `x` between `lo` and `hi` as a fraction of the distance.
+fn ratio(x: DyadicFraction, lo: DyadicFraction, hi: DyadicFraction) -> DyadicFraction {
+    let mut num = Wide::from_df(x);
+    num.sub(Wide::from_df(lo));
+    let mut den = Wide::from_df(hi);
+    den.sub(Wide::from_df(lo));
+    num.div(den, i8::MAX)
+}
+
+/// `a + (b - a) * t` computed exactly and narrowed rounding towards negative
+/// infinity.
+fn lerp(a: DyadicFraction, b: DyadicFraction, t: DyadicFraction) -> DyadicFraction {
+    lerp_wide(a, b, t).to_df()
+}
+
+fn lerp_wide(a: DyadicFraction, b: DyadicFraction, t: DyadicFraction) -> Wide {
+    let mut acc = Wide::from_df(a);
+    acc.add_product(b, t);
+    acc.sub_product(a, t);
+    acc
+}
+
+/// Catmull–Rom spline through uniformly spaced `samples`, evaluated at the
+/// fractional sample index `pos`.
+///
+/// The curve passes through every sample with a continuous slope. `pos` is
+/// clamped to the samples, and the first and last sample are repeated to
+/// supply the outer neighbours. The cubic is accumulated in a wide
+/// accumulator and narrowed once, rounding towards negative infinity; an
+/// empty slice yields zero.
+pub fn cubic(samples: &[DyadicFraction], pos: DyadicFraction) -> DyadicFraction {
+    let Some(last) = samples.len().checked_sub(1) else {
+        return DyadicFraction::zero();
+    };
+    let (index, t) = pos.to_int_and_frac();
+    if index < 0 {
+        return samples[0];
+    }
+    if index >= last as i64 {
+        return samples[last];
+    }
+    let index = index as usize;
+    let p = [
+        index.saturating_sub(1),
+        index,
+        index + 1,
+        (index + 2).min(last),
+    ]
+    .map(|i| samples[i]);
+
+    // 2 * p(t) = 2 p1 + (p2 - p0) t + (2 p0 - 5 p1 + 4 p2 - p3) t^2
+    //          + (3 p1 - 3 p2 + p3 - p0) t^3
+    let coeffs = [[0, 2, 0, 0], [-1, 0, 1, 0], [2, -5, 4, -1], [-1, 3, -3, 1]];
+    let mut acc = Wide::default();
+    for row in coeffs.iter().rev() {
+        acc = acc.mul_df(t);
+        for (&k, &val) in row.iter().zip(&p) {
+            acc.add_product(val, DyadicFraction::from(k));
+        }
+    }
+    acc.mul_df(DyadicFraction::new(1, 1)).to_df()
+}
@@ -0,0 +1,171 @@
+use crate::{DyadicFraction, Rounding};
+use core::fmt;
+use core::ops::*;
+
+/// A closed interval `[lo, hi]` of dyadic fractions for propagating
+/// worst-case error bounds.
+///
+/// Arithmetic rounds outward: whenever a bound does not fit into a dyadic
+/// fraction, `lo` is rounded down and `hi` up, so the exact result is always
+/// contained.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct DFInterval {
+    pub lo: DyadicFraction,
+    pub hi: DyadicFraction,
+}
+
+impl DFInterval {
+    /// Interval between two bounds given in any order.
+    pub fn new(a: DyadicFraction, b: DyadicFraction) -> Self {
+        Self {
+            lo: DyadicFraction::min(a, b),
+            hi: DyadicFraction::max(a, b),
+        }
+    }
+
+    pub const fn point(val: DyadicFraction) -> Self {
+        Self { lo: val, hi: val }
+    }
+
+    /// Interval `[val - tolerance, val + tolerance]`.
+    pub fn around(val: DyadicFraction, tolerance: DyadicFraction) -> Self {
+        let tolerance = tolerance.abs();
+        Self::new(
+            val.add_rounded(tolerance.neg_rounded(Rounding::Ceil), Rounding::Floor),
+            val.add_rounded(tolerance, Rounding::Ceil),
+        )
+    }
+
+    /// Width of the interval, rounded up.
+    pub fn width(self) -> DyadicFraction {
+        self.hi
+            .add_rounded(self.lo.neg_rounded(Rounding::Ceil), Rounding::Ceil)
+    }
+
+    /// Rounds `lo` down and `hi` up to a denominator of at most
+    /// `2^denominator_power`.
+    pub fn round_outward(self, denominator_power: i8) -> Self {
+        let power = denominator_power as i32;
+        Self {
+            lo: outward(self.lo, power, Rounding::Floor),
+            hi: outward(self.hi, power, Rounding::Ceil),
+        }
+    }
+
+    pub fn contains(self, val: DyadicFraction) -> bool {
+        self.lo <= val && val <= self.hi
+    }
+
+    pub fn contains_interval(self, other: Self) -> bool {
+        self.lo <= other.lo && other.hi <= self.hi
+    }
+
+    /// Common part of both intervals, if they overlap.
+    pub fn intersection(self, other: Self) -> Option<Self> {
+        let lo = DyadicFraction::max(self.lo, other.lo);
+        let hi = DyadicFraction::min(self.hi, other.hi);
+        if lo <= hi {
+            Some(Self { lo, hi })
+        } else {
+            None
+        }
+    }
+
+    /// Smallest interval containing both intervals.
+    pub fn hull(self, other: Self) -> Self {
+        Self {
+            lo: DyadicFraction::min(self.lo, other.lo),
+            hi: DyadicFraction::max(self.hi, other.hi),
+        }
+    }
+}
+
+fn outward(val: DyadicFraction, power: i32, rounding: Rounding) -> DyadicFraction {
+    if val.denominator_power() as i32 <= power {
+        return val;
+    }
+    DyadicFraction::narrow(val.scaled(power, rounding), power, rounding)
+}
+
+impl From<DyadicFraction> for DFInterval {
+    fn from(val: DyadicFraction) -> Self {
+        Self::point(val)
+    }
+}
+
+impl Add for DFInterval {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            lo: self.lo.add_rounded(other.lo, Rounding::Floor),
+            hi: self.hi.add_rounded(other.hi, Rounding::Ceil),
+        }
+    }
+}
+
+impl AddAssign for DFInterval {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for DFInterval {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self + -other
+    }
+}
+
+impl SubAssign for DFInterval {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul for DFInterval {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        let corners = [
+            (self.lo, other.lo),
+            (self.lo, other.hi),
+            (self.hi, other.lo),
+            (self.hi, other.hi),
+        ];
+        let mut res = Self::point(self.lo.mul_rounded(other.lo, Rounding::Floor));
+        for (a, b) in corners {
+            res.lo = DyadicFraction::min(res.lo, a.mul_rounded(b, Rounding::Floor));
+            res.hi = DyadicFraction::max(res.hi, a.mul_rounded(b, Rounding::Ceil));
+        }
+        res
+    }
+}
+
+impl MulAssign for DFInterval {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl Neg for DFInterval {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self {
+            lo: self.hi.neg_rounded(Rounding::Floor),
+            hi: self.lo.neg_rounded(Rounding::Ceil),
+        }
+    }
+}
+
+impl fmt::Display for DFInterval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("[")?;
+        fmt::Display::fmt(&self.lo, f)?;
+        f.write_str(", ")?;
+        fmt::Display::fmt(&self.hi, f)?;
+        f.write_str("]")
+    }
+}
@@ -1,12 +1,54 @@
-#![no_std]
+#![cfg_attr(not(feature = "std"), no_std)]
 
 use core::cmp::*;
 use core::fmt;
 use core::ops::*;
+use core::str::FromStr;
+use render::{Body, Cursor, Decimal};
+use wide::Wide;
+
+#[cfg(target_has_atomic = "64")]
+mod atomic;
+mod complex;
+mod exp;
+mod fixed;
+mod interval;
+mod linalg;
+mod macros;
+mod ratio;
+mod render;
+mod ring;
+mod unit_ratio;
+mod wide;
+
+pub mod cal;
+pub mod control;
+pub mod dsp;
+pub mod filter;
+pub mod ident;
+pub mod interp;
+pub mod monitor;
+pub mod motion;
+pub mod poly;
+pub mod pwm;
+pub mod sim;
+pub mod stats;
+pub mod table;
+pub mod time;
+pub mod trig;
+
+#[cfg(target_has_atomic = "64")]
+pub use atomic::AtomicDF;
+pub use complex::Complex;
+pub use fixed::DyadicFixed;
+pub use interval::DFInterval;
+pub use linalg::{Mat2, Mat3, Vec2, Vec3};
+pub use ratio::Ratio;
+pub use unit_ratio::UnitRatio;
 
 pub type DF = DyadicFraction;
 
-#[derive(Copy, Clone, Debug, Default)]
+#[derive(Copy, Clone, Default)]
 pub struct DyadicFraction {
     num: i32,
     power: i8,
@@ -48,6 +90,23 @@ impl DyadicFraction {
         self.num.is_negative()
     }
 
+    /// Floor of `log2(|self|)`, or `None` for zero.
+    pub const fn ilog2(self) -> Option<i16> {
+        if self.num == 0 {
+            return None;
+        }
+        Some(self.magnitude_bits() - 1)
+    }
+
+    /// Smallest `n` with `|self| < 2^n`, so `x.magnitude_bits() <= 8` checks
+    /// `|x| < 256`. Zero passes every such check and returns `i16::MIN`.
+    pub const fn magnitude_bits(self) -> i16 {
+        if self.num == 0 {
+            return i16::MIN;
+        }
+        32 - self.num.unsigned_abs().leading_zeros() as i16 - self.power as i16
+    }
+
     pub const fn canonical(&self) -> Self {
         let mut res = *self;
         if res.num == 0 {
@@ -79,6 +138,24 @@ impl DyadicFraction {
         res
     }
 
+    /// Rounds to nearest, ties away from zero, at a denominator of at most
+    /// `2^power`, and returns the result with the exact remainder
+    /// `self - rounded`, for carrying quantization error into the next step.
+    pub const fn round_with_remainder(self, power: i8) -> (Self, Self) {
+        let val = self.canonical();
+        let shift = val.power as i32 - power as i32;
+        if shift <= 0 {
+            return (val, Self::zero());
+        }
+        let num = val.num as i128;
+        let rounded = shr_round(num, shift, Rounding::Nearest);
+        let rem = if shift > 32 { num } else { num - (rounded << shift) };
+        (
+            Self::narrow(rounded, power as i32, Rounding::Nearest),
+            Self::narrow(rem, val.power as i32, Rounding::Nearest),
+        )
+    }
+
     pub fn floor(&self) -> i32 {
         let val = self.canonical();
         let shift = val.power.abs();
@@ -89,18 +166,107 @@ impl DyadicFraction {
         }
     }
 
+    /// Halves exactly, except for an odd numerator at power 127 where the
+    /// power saturates and the value is left unchanged.
+    #[track_caller]
     pub fn div_by_two(&self) -> Self {
         let mut res = *self;
-        res.power = res.power.saturating_add(1);
+        if res.power < i8::MAX {
+            res.power += 1;
+        } else if res.num & 1 == 0 {
+            res.num >>= 1;
+        } else {
+            return strict((res, Flags::POWER), "divide by two");
+        }
         res
     }
 
+    /// Sum together with the saturation it raised, if any.
+    pub fn add_with_flags(self, other: Self) -> (Self, Flags) {
+        let (fst, snd, power, mut flags) = self.saturating_cross(other);
+        if fst.checked_add(snd).is_none() {
+            flags |= Flags::NUMERATOR;
+        }
+        (Self::new(fst.saturating_add(snd), power), flags)
+    }
+
+    /// Difference together with the saturation it raised, if any.
+    pub fn sub_with_flags(self, other: Self) -> (Self, Flags) {
+        let (fst, snd, power, mut flags) = self.saturating_cross(other);
+        if fst.checked_sub(snd).is_none() {
+            flags |= Flags::NUMERATOR;
+        }
+        (Self::new(fst.saturating_sub(snd), power), flags)
+    }
+
+    /// Product together with the saturation it raised, if any.
+    pub fn mul_with_flags(self, other: Self) -> (Self, Flags) {
+        let mut flags = Flags::NONE;
+        if self.num.checked_mul(other.num).is_none() {
+            flags |= Flags::NUMERATOR;
+        }
+        if self.num != 0 && other.num != 0 && self.power.checked_add(other.power).is_none() {
+            flags |= Flags::POWER;
+        }
+        let res = Self::new(
+            self.num.saturating_mul(other.num),
+            self.power.saturating_add(other.power),
+        );
+        (res, flags)
+    }
+
+    /// Sums `vals` in a wide accumulator and narrows once, rounding towards
+    /// negative infinity, so intermediate sums never saturate.
+    pub fn sum_exact(vals: &[Self]) -> Self {
+        let mut acc = Wide::default();
+        for &val in vals {
+            acc.add(Wide::from_df(val));
+        }
+        acc.to_df()
+    }
+
+    /// Distance `|self - other|` computed exactly and narrowed rounding
+    /// towards positive infinity, so it never understates the difference.
+    pub fn abs_diff(self, other: Self) -> Self {
+        let mut acc = Wide::from_df(self);
+        acc.sub(Wide::from_df(other));
+        if acc.is_negative() {
+            acc = acc.neg();
+        }
+        acc.round(Rounding::Ceil)
+    }
+
+    /// Dot product of `a` and `b` with every product and partial sum kept
+    /// exact, narrowed once rounding towards negative infinity. Extra
+    /// elements of the longer slice are ignored.
+    pub fn dot(a: &[Self], b: &[Self]) -> Self {
+        let mut acc = Wide::default();
+        acc.add_dot(a, b);
+        acc.to_df()
+    }
+
     pub fn mul_add(self, a: impl Into<Self>, b: impl Into<Self>) -> Self {
         self * a.into() + b.into()
     }
 
+    /// `floor(self * a)` computed exactly, saturating at the range of `i32`.
     pub fn scale(self, a: impl Into<Self>) -> i32 {
-        self.canonical().mul(a.into()).floor()
+        self.scale_i64(a).clamp(i32::MIN as i64, i32::MAX as i64) as i32
+    }
+
+    /// `floor(self * a)` computed exactly, saturating at the range of `i64`.
+    pub fn scale_i64(self, a: impl Into<Self>) -> i64 {
+        let a = a.into();
+        let num = self.num as i128 * a.num as i128;
+        let power = self.power as i32 + a.power as i32;
+        let int = if power >= 0 {
+            shr_round(num, power, Rounding::Floor)
+        } else if -power < 64 {
+            num << -power
+        } else {
+            num.signum() << 64
+        };
+        int.clamp(i64::MIN as i128, i64::MAX as i128) as i64
     }
 
     pub fn pow(self, n: u8) -> Self {
@@ -138,23 +304,602 @@ impl DyadicFraction {
         self.power
     }
 
-    fn saturating_cross(self, other: Self) -> (i32, i32, i8) {
+    /// Parses `"3/8"`, `"7/2^5"`, `"-1.25"` or a plain integer, rounding to
+    /// the nearest multiple of `2^-max_power`.
+    pub const fn parse(src: &str, max_power: i8) -> Result<Self, ParseError> {
+        match Self::parse_parts(src) {
+            Ok((num, den, power, _)) => Ok(Self::round_ratio(num, den, power, max_power)),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Parses like [`parse`](Self::parse), but only values that are exactly
+    /// representable: fails with [`ParseError::NotDyadic`] for decimals such
+    /// as `"0.1"` and with [`ParseError::OutOfRange`] for values needing a
+    /// wider numerator or power.
+    pub const fn parse_exact(src: &str) -> Result<Self, ParseError> {
+        let (mut num, mut den, mut power) = match Self::parse_parts(src) {
+            Ok((num, den, power, true)) => (num, den, power),
+            Ok(_) => return Err(ParseError::OutOfRange),
+            Err(err) => return Err(err),
+        };
+        // The denominator is a power of ten, so its odd part must divide the
+        // numerator.
+        while den > 1 {
+            if num % 5 != 0 {
+                return Err(ParseError::NotDyadic);
+            }
+            num /= 5;
+            den /= 10;
+            power += 1;
+        }
+        if num == 0 {
+            return Ok(Self::zero());
+        }
+        let zeros = num.trailing_zeros() as i32;
+        num >>= zeros;
+        power -= zeros;
+        if power < i8::MIN as i32 {
+            let shift = i8::MIN as i32 - power;
+            if shift >= 32 {
+                return Err(ParseError::OutOfRange);
+            }
+            num <<= shift;
+            power = i8::MIN as i32;
+        }
+        if power > i8::MAX as i32 || num < i32::MIN as i128 || num > i32::MAX as i128 {
+            return Err(ParseError::OutOfRange);
+        }
+        Ok(Self::new(num as i32, power as i8))
+    }
+
+    /// Splits `src` into `num / den / 2^power`, where `den` is a power of
+    /// ten, and whether every digit was kept.
+    const fn parse_parts(src: &str) -> Result<(i128, i128, i32, bool), ParseError> {
+        let bytes = src.as_bytes();
+        if bytes.is_empty() {
+            return Err(ParseError::Empty);
+        }
+        let (negative, idx) = match bytes[0] {
+            b'-' => (true, 1),
+            b'+' => (false, 1),
+            _ => (false, 0),
+        };
+        let (mut num, mut idx) = match parse_digits(bytes, idx) {
+            Ok(res) => res,
+            Err(err) => return Err(err),
+        };
+
+        let mut den = 1;
+        let mut power = 0;
+        let mut exact = true;
+        if idx < bytes.len() && bytes[idx] == b'.' {
+            idx += 1;
+            if idx == bytes.len() {
+                return Err(ParseError::InvalidDigit);
+            }
+            while idx < bytes.len() {
+                if !bytes[idx].is_ascii_digit() {
+                    return Err(ParseError::InvalidDigit);
+                }
+                if num <= MAX_PARSED && den <= MAX_PARSED {
+                    num = num * 10 + (bytes[idx] - b'0') as i128;
+                    den *= 10;
+                } else if bytes[idx] != b'0' {
+                    exact = false;
+                }
+                idx += 1;
+            }
+        } else if idx < bytes.len() && bytes[idx] == b'/' {
+            idx += 1;
+            if idx + 1 < bytes.len() && bytes[idx] == b'2' && bytes[idx + 1] == b'^' {
+                let (exp, end) = match parse_digits(bytes, idx + 2) {
+                    Ok(res) => res,
+                    Err(err) => return Err(err),
+                };
+                if exp > 1024 {
+                    return Err(ParseError::OutOfRange);
+                }
+                power = exp as i32;
+                idx = end;
+            } else {
+                let (denominator, end) = match parse_digits(bytes, idx) {
+                    Ok(res) => res,
+                    Err(err) => return Err(err),
+                };
+                if denominator == 0 || denominator & (denominator - 1) != 0 {
+                    return Err(ParseError::NotDyadic);
+                }
+                power = denominator.trailing_zeros() as i32;
+                idx = end;
+            }
+        }
+        if idx != bytes.len() {
+            return Err(ParseError::InvalidDigit);
+        }
+
+        if negative {
+            num = -num;
+        }
+        Ok((num, den, power, exact))
+    }
+
+    /// Renders the `num/den` form into `buf` without going through
+    /// `core::fmt`.
+    pub fn write_to<'a>(&self, buf: &'a mut [u8]) -> Result<&'a str, BufferTooSmall> {
+        let mut cursor = Cursor::new(buf);
+        render::write_fraction(*self, &mut cursor)?;
+        Ok(cursor.finish())
+    }
+
+    /// Renders the decimal form into `buf` without going through `core::fmt`,
+    /// either exact or rounded half away from zero to `precision` digits.
+    pub fn write_decimal_to<'a>(
+        &self,
+        buf: &'a mut [u8],
+        precision: Option<usize>,
+    ) -> Result<&'a str, BufferTooSmall> {
+        let mut dec = Decimal::new(*self);
+        if let Some(precision) = precision {
+            dec.round(precision);
+        }
+        let mut cursor = Cursor::new(buf);
+        dec.write(&mut cursor, precision)?;
+        Ok(cursor.finish())
+    }
+
+    /// Splits into the integer part (rounded towards negative infinity) and
+    /// the remaining fraction in `[0, 1)`.
+    ///
+    /// The integer part saturates beyond the `i64` range. Negative values
+    /// finer than `2^-31` keep only the top 31 bits of their fraction.
+    pub fn to_int_and_frac(self) -> (i64, Self) {
+        let val = self.canonical();
+        let int = i64::from(val);
+        if val.power <= 0 {
+            return (int, Self::zero());
+        }
+        let mask = i128::MAX >> (127 - val.power);
+        (int, Self::from_wide(val.num as i128 & mask, val.power as i32))
+    }
+
+    /// Packs the canonical numerator and power into the low 40 bits:
+    /// the numerator in bits 8..40 and the power in bits 0..8.
+    pub const fn to_bits(self) -> u64 {
+        let val = self.canonical();
+        ((val.num as u32 as u64) << 8) | val.power as u8 as u64
+    }
+
+    /// Unpacks a value produced by [`DyadicFraction::to_bits`], ignoring the
+    /// upper 24 bits.
+    pub const fn from_bits(bits: u64) -> Self {
+        Self::new((bits >> 8) as u32 as i32, bits as u8 as i8)
+    }
+
+    /// Little-endian bytes of [`DyadicFraction::to_bits`]: the power
+    /// followed by the numerator.
+    pub const fn to_le_bytes(self) -> [u8; 5] {
+        let bytes = self.to_bits().to_le_bytes();
+        [bytes[0], bytes[1], bytes[2], bytes[3], bytes[4]]
+    }
+
+    /// Big-endian bytes of [`DyadicFraction::to_bits`]: the numerator
+    /// followed by the power.
+    pub const fn to_be_bytes(self) -> [u8; 5] {
+        let bytes = self.to_bits().to_be_bytes();
+        [bytes[3], bytes[4], bytes[5], bytes[6], bytes[7]]
+    }
+
+    /// Decodes [`DyadicFraction::to_le_bytes`], rejecting non-canonical
+    /// encodings.
+    pub const fn from_le_bytes(bytes: [u8; 5]) -> Option<Self> {
+        let [b0, b1, b2, b3, b4] = bytes;
+        Self::from_canonical_bits(u64::from_le_bytes([b0, b1, b2, b3, b4, 0, 0, 0]))
+    }
+
+    /// Decodes [`DyadicFraction::to_be_bytes`], rejecting non-canonical
+    /// encodings.
+    pub const fn from_be_bytes(bytes: [u8; 5]) -> Option<Self> {
+        let [b0, b1, b2, b3, b4] = bytes;
+        Self::from_canonical_bits(u64::from_be_bytes([0, 0, 0, b0, b1, b2, b3, b4]))
+    }
+
+    const fn from_canonical_bits(bits: u64) -> Option<Self> {
+        let val = Self::from_bits(bits);
+        if val.to_bits() == bits {
+            Some(val)
+        } else {
+            None
+        }
+    }
+
+    /// Square root rounded down to a denominator of at most `2^max_power`.
+    ///
+    /// Negative values yield zero.
+    pub const fn sqrt(self, max_power: i8) -> Self {
+        if self.num <= 0 {
+            return Self::zero();
+        }
+        Self::sqrt_wide(self.num as u128, self.power as i32, max_power)
+    }
+
+    /// Square root of `num / 2^power`, rounded down to a denominator of at
+    /// most `2^max_power`.
+    pub(crate) const fn sqrt_wide(num: u128, power: i32, max_power: i8) -> Self {
+        if num == 0 {
+            return Self::zero();
+        }
+        let limit = (62 - bit_len(num) + power).div_euclid(2);
+        let target = if (max_power as i32) < limit {
+            max_power as i32
+        } else {
+            limit
+        };
+        let shift = 2 * target - power;
+        let radicand = if shift >= 0 {
+            num << shift
+        } else if shift > -128 {
+            num >> -shift
+        } else {
+            0
+        };
+        Self::from_wide(radicand.isqrt() as i128, target)
+    }
+
+    /// Converts to Q15, rounding to nearest and saturating to `[-1, 1)`.
+    pub fn to_q15(self) -> i16 {
+        self.to_q(0, 15, Rounding::Nearest) as i16
+    }
+
+    pub const fn from_q15(raw: i16) -> Self {
+        Self::new(raw as i32, 15)
+    }
+
+    /// Converts to Q31, rounding to nearest and saturating to `[-1, 1)`.
+    pub fn to_q31(self) -> i32 {
+        self.to_q(0, 31, Rounding::Nearest)
+    }
+
+    pub const fn from_q31(raw: i32) -> Self {
+        Self::new(raw, 31)
+    }
+
+    /// Converts to Qm.n with `int_bits` integer and `frac_bits` fractional
+    /// bits plus a sign bit, saturating to the representable range.
+    ///
+    /// The total width is capped at 32 bits by reducing the integer bits.
+    pub fn to_q(self, int_bits: u8, frac_bits: u8, rounding: Rounding) -> i32 {
+        let frac_bits = frac_bits.min(31);
+        let bits = (int_bits as u32 + frac_bits as u32).min(31);
+        let limit = 1i128 << bits;
+        self.scaled(frac_bits as i32, rounding).clamp(-limit, limit - 1) as i32
+    }
+
+    /// Interprets `raw` as a Qm.n value with `frac_bits` fractional bits.
+    pub const fn from_q(raw: i32, frac_bits: u8) -> Self {
+        Self::new(raw, frac_bits as i8)
+    }
+
+    /// Timer compare value for a duty ratio clamped to `[0, 1]`, rounded to
+    /// nearest. See [`pwm::Duty`] for other rounding modes.
+    pub fn to_duty(self, top: u16) -> u16 {
+        pwm::Duty::new(top, Rounding::Nearest).compare(self)
+    }
+
+    /// Rounds to an integer in the given direction and clamps it to the
+    /// range of `u8`.
+    pub fn to_u8_clamped(self, rounding: Rounding) -> u8 {
+        self.to_unsigned(rounding, u8::MAX as u32) as u8
+    }
+
+    /// Rounds to an integer in the given direction and clamps it to the
+    /// range of `u16`.
+    pub fn to_u16_clamped(self, rounding: Rounding) -> u16 {
+        self.to_unsigned(rounding, u16::MAX as u32) as u16
+    }
+
+    /// Rounds to an integer in the given direction and clamps it to the
+    /// range of `u32`.
+    pub fn to_u32_clamped(self, rounding: Rounding) -> u32 {
+        self.to_unsigned(rounding, u32::MAX)
+    }
+
+    fn to_unsigned(self, rounding: Rounding, max: u32) -> u32 {
+        self.scaled(0, rounding).clamp(0, max as i128) as u32
+    }
+
+    /// Rounds `self * 2^frac_bits` to an integer, saturating beyond `2^126`.
+    const fn scaled(self, frac_bits: i32, rounding: Rounding) -> i128 {
+        let num = self.num as i128;
+        let shift = frac_bits - self.power as i32;
+        if num == 0 {
+            return 0;
+        }
+        if shift >= 0 {
+            return if shift <= 95 {
+                num << shift
+            } else if num > 0 {
+                i128::MAX >> 1
+            } else {
+                i128::MIN >> 1
+            };
+        }
+
+        shr_round(num, -shift, rounding)
+    }
+
+    /// Closest dyadic approximation of `num / den` with a denominator of at
+    /// most `2^max_power`, rounding half away from zero.
+    ///
+    /// The power is lowered further when the numerator would not fit into
+    /// `i32`. Division by zero saturates towards the sign of `num`.
+    pub const fn from_ratio(num: i32, den: i32, max_power: i8) -> Self {
+        Self::round_ratio(num as i128, den as i128, 0, max_power)
+    }
+
+    /// Closest dyadic approximation of `percent / 100` with at least 30
+    /// significant bits, rounding half away from zero.
+    pub const fn from_percent(percent: i32) -> Self {
+        Self::from_ratio(percent, 100, i8::MAX)
+    }
+
+    /// Closest dyadic approximation of `permille / 1000`, see
+    /// [`DyadicFraction::from_percent`].
+    pub const fn from_permille(permille: i32) -> Self {
+        Self::from_ratio(permille, 1000, i8::MAX)
+    }
+
+    /// Closest dyadic approximation of `ppm / 1_000_000`, see
+    /// [`DyadicFraction::from_percent`].
+    pub const fn from_ppm(ppm: i32) -> Self {
+        Self::from_ratio(ppm, 1_000_000, i8::MAX)
+    }
+
+    /// Value in percent, rounded to nearest at the finest power that fits.
+    pub const fn to_percent(self) -> Self {
+        self.scaled_by(100)
+    }
+
+    /// Value in permille, rounded to nearest at the finest power that fits.
+    pub const fn to_permille(self) -> Self {
+        self.scaled_by(1000)
+    }
+
+    /// Value in parts per million, rounded to nearest at the finest power
+    /// that fits.
+    pub const fn to_ppm(self) -> Self {
+        self.scaled_by(1_000_000)
+    }
+
+    const fn scaled_by(self, factor: i128) -> Self {
+        Self::narrow(self.num as i128 * factor, self.power as i32, Rounding::Nearest)
+    }
+
+    /// Exact quotient `self / other` in lowest terms, or `None` when `other`
+    /// is zero or the quotient does not fit into an `i32` ratio.
+    pub const fn div_exact(self, other: Self) -> Option<Ratio> {
+        if self.num == 0 {
+            return Ratio::from_wide(0, other.num as i128);
+        }
+        let shift = other.power as i32 - self.power as i32;
+        let (num, den) = (self.num as i128, other.num as i128);
+        if shift > 64 || shift < -64 {
+            None
+        } else if shift >= 0 {
+            Ratio::from_wide(num << shift, den)
+        } else {
+            Ratio::from_wide(num, den << -shift)
+        }
+    }
+
+    /// Approximates `num / den / 2^power` at a denominator power of at most
+    /// `max_power`, rounding to nearest.
+    const fn round_ratio(num: i128, den: i128, power: i32, max_power: i8) -> Self {
+        let (mut num, mut den, mut power) = if den < 0 {
+            (-num, -den, power)
+        } else {
+            (num, den, power)
+        };
+        if num == 0 {
+            return Self::zero();
+        }
+        if den == 0 {
+            return Self::saturated(num < 0);
+        }
+
+        let mut num_bits = bit_len(num.unsigned_abs());
+        if num_bits > 62 {
+            num >>= num_bits - 62;
+            power -= num_bits - 62;
+            num_bits = 62;
+        }
+        let mut den_bits = bit_len(den as u128);
+        if den_bits > 62 {
+            den >>= den_bits - 62;
+            power += den_bits - 62;
+            den_bits = 62;
+        }
+
+        let mut target = max_power as i32;
+        let estimate = num_bits - den_bits + target - power + 1;
+        if estimate > 31 {
+            target -= estimate - 31;
+        }
+        let shift = target - power;
+        let quotient = if shift >= 0 {
+            div_round(num << shift, den)
+        } else if shift > -64 {
+            div_round(num, den << -shift)
+        } else {
+            0
+        };
+        Self::from_wide(quotient, target)
+    }
+
+    /// Narrows a wide `num / 2^power` value, dropping low bits (rounding
+    /// towards negative infinity) when the numerator does not fit into `i32`.
+    const fn from_wide(num: i128, power: i32) -> Self {
+        Self::narrow(num, power, Rounding::Floor)
+    }
+
+    /// Narrows a wide `num / 2^power` value, rounding the dropped low bits
+    /// in the given direction.
+    const fn narrow(num: i128, power: i32, rounding: Rounding) -> Self {
+        let mut num = num;
+        let mut power = power;
+        if num == 0 {
+            return Self::zero();
+        }
+
+        if power < 0 {
+            let headroom = num.unsigned_abs().leading_zeros() as i32 - 2;
+            let shift = if headroom < -power { headroom } else { -power };
+            let shift = if shift > 0 { shift } else { 0 };
+            num <<= shift;
+            power += shift;
+        }
+        if power > 0 {
+            let zeros = num.trailing_zeros() as i32;
+            let shift = if zeros < power { zeros } else { power };
+            num >>= shift;
+            power -= shift;
+        }
+
+        let bits = bit_len(if num < 0 { !num } else { num } as u128);
+        if bits > 31 {
+            num = shr_round(num, bits - 31, rounding);
+            power -= bits - 31;
+            if num > i32::MAX as i128 {
+                num >>= 1;
+                power -= 1;
+            }
+        }
+        if power > i8::MAX as i32 {
+            num = shr_round(num, power - i8::MAX as i32, rounding);
+            power = i8::MAX as i32;
+        }
+        if power < i8::MIN as i32 {
+            return Self::saturated(num < 0);
+        }
+
+        Self::new(num as i32, power as i8)
+    }
+
+    /// Sum rounded in the given direction instead of saturating.
+    pub(crate) const fn add_rounded(self, other: Self, rounding: Rounding) -> Self {
+        let (lhs, rhs) = (self.power as i32, other.power as i32);
+        let (fine, coarse) = if lhs > rhs { (lhs, rhs) } else { (rhs, lhs) };
+        let power = if fine > coarse + 96 { coarse + 96 } else { fine };
+        let sum = align(self, power, rounding) + align(other, power, rounding);
+        Self::narrow(sum, power, rounding)
+    }
+
+    /// Product rounded in the given direction instead of saturating.
+    pub(crate) const fn mul_rounded(self, other: Self, rounding: Rounding) -> Self {
+        let product = self.num as i128 * other.num as i128;
+        Self::narrow(product, self.power as i32 + other.power as i32, rounding)
+    }
+
+    /// Negation that cannot overflow, rounding the dropped bit of `-i32::MIN`.
+    pub(crate) const fn neg_rounded(self, rounding: Rounding) -> Self {
+        Self::narrow(-(self.num as i128), self.power as i32, rounding)
+    }
+
+    const fn saturated(negative: bool) -> Self {
+        Self {
+            num: if negative { i32::MIN } else { i32::MAX },
+            power: i8::MIN,
+        }
+    }
+
+    fn saturating_cross(self, other: Self) -> (i32, i32, i8, Flags) {
         let (min_power, max_power) = if self.power > other.power {
             (other.power, self.power)
         } else {
             (self.power, other.power)
         };
-        (
-            saturating_shl(self.num, other.power - min_power),
-            saturating_shl(other.num, self.power - min_power),
-            max_power,
-        )
+        let fst = saturating_shl(self.num, other.power.saturating_sub(min_power));
+        let snd = saturating_shl(other.num, self.power.saturating_sub(min_power));
+        let mut flags = Flags::NONE;
+        if shl_overflows(self.num, other.power.saturating_sub(min_power))
+            || shl_overflows(other.num, self.power.saturating_sub(min_power))
+        {
+            flags |= Flags::NUMERATOR;
+        }
+        (fst, snd, max_power, flags)
+    }
+}
+
+const MAX_PARSED: i128 = 10i128.pow(36);
+
+const fn parse_digits(bytes: &[u8], start: usize) -> Result<(i128, usize), ParseError> {
+    let mut idx = start;
+    let mut val: i128 = 0;
+    while idx < bytes.len() && bytes[idx].is_ascii_digit() {
+        if val > MAX_PARSED {
+            return Err(ParseError::OutOfRange);
+        }
+        val = val * 10 + (bytes[idx] - b'0') as i128;
+        idx += 1;
+    }
+    if idx == start {
+        Err(ParseError::InvalidDigit)
+    } else {
+        Ok((val, idx))
+    }
+}
+
+const fn bit_len(num: u128) -> i32 {
+    128 - num.leading_zeros() as i32
+}
+
+/// Numerator of `val` rescaled to the denominator `2^power`.
+const fn align(val: DyadicFraction, power: i32, rounding: Rounding) -> i128 {
+    let shift = power - val.power as i32;
+    if shift >= 0 {
+        (val.num as i128) << shift
+    } else {
+        shr_round(val.num as i128, -shift, rounding)
+    }
+}
+
+/// Shifts `num` right by `shift` bits, rounding the dropped bits.
+const fn shr_round(num: i128, shift: i32, rounding: Rounding) -> i128 {
+    if shift <= 0 {
+        return num;
+    }
+    if shift > 125 {
+        return match rounding {
+            Rounding::Floor if num < 0 => -1,
+            Rounding::Ceil if num > 0 => 1,
+            _ => 0,
+        };
+    }
+    let floor = num >> shift;
+    let rem = num - (floor << shift);
+    let half = 1 << (shift - 1);
+    let round_up = match rounding {
+        Rounding::Floor => false,
+        Rounding::Ceil => rem != 0,
+        Rounding::TowardZero => num < 0 && rem != 0,
+        Rounding::Nearest => rem > half || (rem == half && num > 0),
+    };
+    floor + round_up as i128
+}
+
+const fn div_round(num: i128, den: i128) -> i128 {
+    if num < 0 {
+        -((-num + den / 2) / den)
+    } else {
+        (num + den / 2) / den
     }
 }
 
 fn saturating_shl(num: i32, rhs: i8) -> i32 {
-    if rhs < 32 {
-        num.shl(rhs)
+    if num == 0 {
+        0
+    } else if rhs < 32 {
+        ((num as i64) << rhs).clamp(i32::MIN as i64, i32::MAX as i64) as i32
     } else if num.is_positive() {
         i32::MAX
     } else {
@@ -162,6 +907,20 @@ fn saturating_shl(num: i32, rhs: i8) -> i32 {
     }
 }
 
+/// Unwraps an operation result, panicking on saturation when the
+/// `strict-overflow` feature is enabled in builds with debug assertions.
+#[track_caller]
+fn strict((val, flags): (DyadicFraction, Flags), op: &str) -> DyadicFraction {
+    if cfg!(all(feature = "strict-overflow", debug_assertions)) && !flags.is_empty() {
+        panic!("attempt to {} with overflow", op);
+    }
+    val
+}
+
+fn shl_overflows(num: i32, rhs: i8) -> bool {
+    num != 0 && (rhs >= 32 || saturating_shl(num, rhs) >> rhs != num)
+}
+
 fn saturating_shr(num: i32, rhs: i8) -> i32 {
     if rhs < 32 {
         num.shr(rhs)
@@ -170,6 +929,258 @@ fn saturating_shr(num: i32, rhs: i8) -> i32 {
     }
 }
 
+/// Rounding mode for conversions into coarser representations.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum Rounding {
+    /// Towards negative infinity.
+    Floor,
+    /// Towards positive infinity.
+    Ceil,
+    /// Towards zero.
+    TowardZero,
+    /// To nearest, ties away from zero.
+    #[default]
+    Nearest,
+}
+
+/// Saturation raised by an arithmetic operation.
+///
+/// Flags are sticky under `|`, so a whole computation can be checked once at
+/// the end.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Flags(u8);
+
+impl Flags {
+    pub const NONE: Self = Self(0);
+    /// The numerator was clamped to the `i32` range.
+    pub const NUMERATOR: Self = Self(1);
+    /// The denominator power was clamped to the `i8` range.
+    pub const POWER: Self = Self(2);
+
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl BitOr for Flags {
+    type Output = Self;
+
+    fn bitor(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+}
+
+impl BitOrAssign for Flags {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+/// Error returned when a value does not fit into a dyadic fraction.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct OutOfRange;
+
+impl fmt::Display for OutOfRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("value out of dyadic fraction range")
+    }
+}
+
+impl core::error::Error for OutOfRange {}
+
+/// Error returned when a rendered value does not fit into the output buffer.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct BufferTooSmall;
+
+impl fmt::Display for BufferTooSmall {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("output buffer too small")
+    }
+}
+
+impl core::error::Error for BufferTooSmall {}
+
+/// Error returned when parsing a dyadic fraction from a string fails.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ParseError {
+    /// The input is empty.
+    Empty,
+    /// The input contains an unexpected character or a missing digit.
+    InvalidDigit,
+    /// The denominator is not a power of two.
+    NotDyadic,
+    /// The value has too many digits to be represented.
+    OutOfRange,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ParseError::Empty => "cannot parse dyadic fraction from empty string",
+            ParseError::InvalidDigit => "invalid digit found in string",
+            ParseError::NotDyadic => "denominator is not a power of two",
+            ParseError::OutOfRange => "value out of dyadic fraction range",
+        })
+    }
+}
+
+impl core::error::Error for ParseError {}
+
+/// Parses at the finest precision the numerator allows, see
+/// [`DyadicFraction::parse`].
+impl FromStr for DyadicFraction {
+    type Err = ParseError;
+
+    fn from_str(src: &str) -> Result<Self, Self::Err> {
+        Self::parse(src, i8::MAX)
+    }
+}
+
+/// Exact for every finite value down to `2^-127`, below which low bits are
+/// dropped (rounding towards negative infinity). NaN converts to zero and
+/// infinities saturate.
+impl From<f32> for DyadicFraction {
+    fn from(val: f32) -> Self {
+        if val.is_nan() {
+            return Self::zero();
+        }
+        if val.is_infinite() {
+            return Self::saturated(val.is_sign_negative());
+        }
+        let bits = val.to_bits();
+        let exp = ((bits >> 23) & 0xff) as i32;
+        let mut mantissa = (bits & 0x7f_ffff) as i128;
+        let power = if exp == 0 {
+            149
+        } else {
+            mantissa |= 1 << 23;
+            150 - exp
+        };
+        if val.is_sign_negative() {
+            mantissa = -mantissa;
+        }
+        Self::from_wide(mantissa, power)
+    }
+}
+
+/// Exact for finite values with at most 31 significant bits, otherwise the
+/// low bits are dropped (rounding towards negative infinity). NaN, infinities
+/// and magnitudes beyond the dyadic fraction range are rejected.
+impl TryFrom<f64> for DyadicFraction {
+    type Error = OutOfRange;
+
+    fn try_from(val: f64) -> Result<Self, Self::Error> {
+        if !val.is_finite() {
+            return Err(OutOfRange);
+        }
+        let bits = val.to_bits();
+        let exp = ((bits >> 52) & 0x7ff) as i32;
+        let mut mantissa = (bits & 0xf_ffff_ffff_ffff) as i128;
+        let power = if exp == 0 {
+            1074
+        } else {
+            mantissa |= 1 << 52;
+            1075 - exp
+        };
+        if bit_len(mantissa as u128) - power > 31 - i8::MIN as i32 {
+            return Err(OutOfRange);
+        }
+        if val.is_sign_negative() {
+            mantissa = -mantissa;
+        }
+        Ok(Self::from_wide(mantissa, power))
+    }
+}
+
+/// Exact, as every dyadic fraction is representable in `f64`.
+#[cfg(feature = "float")]
+impl From<DyadicFraction> for f64 {
+    fn from(val: DyadicFraction) -> Self {
+        let scale = f64::from_bits(((1023 - val.power as i32) as u64) << 52);
+        val.num as f64 * scale
+    }
+}
+
+/// Rounds to the nearest `f32`; magnitudes beyond `f32::MAX` become infinite
+/// and values below the subnormal range flush to zero.
+#[cfg(feature = "float")]
+impl From<DyadicFraction> for f32 {
+    fn from(val: DyadicFraction) -> Self {
+        f64::from(val) as f32
+    }
+}
+
+macro_rules! impl_try_from_int {
+    ($($int:ty),*) => {
+        $(
+            /// Succeeds when the value is exactly representable, i.e. it has at
+            /// most 31 significant bits once trailing zeros are dropped.
+            impl TryFrom<$int> for DyadicFraction {
+                type Error = OutOfRange;
+
+                fn try_from(num: $int) -> Result<Self, Self::Error> {
+                    let num = num as i128;
+                    let significant = num >> num.trailing_zeros().min(127);
+                    if significant < i32::MIN as i128 || significant > i32::MAX as i128 {
+                        return Err(OutOfRange);
+                    }
+                    Ok(Self::from_wide(num, 0))
+                }
+            }
+        )*
+    };
+}
+
+impl_try_from_int!(u32, i64, u64, usize);
+
+/// Rounds towards negative infinity, saturating beyond the `i128` range.
+impl From<DyadicFraction> for i128 {
+    fn from(val: DyadicFraction) -> Self {
+        let val = val.canonical();
+        let num = val.num as i128;
+        if val.power > 0 {
+            num >> val.power
+        } else if bit_len(val.num.unsigned_abs() as u128) - (val.power as i32) < 128 {
+            num << -val.power
+        } else if num > 0 {
+            i128::MAX
+        } else if num < 0 {
+            i128::MIN
+        } else {
+            0
+        }
+    }
+}
+
+/// Rounds towards negative infinity, saturating beyond the `i64` range.
+impl From<DyadicFraction> for i64 {
+    fn from(val: DyadicFraction) -> Self {
+        i128::from(val).clamp(i64::MIN as i128, i64::MAX as i128) as i64
+    }
+}
+
+macro_rules! impl_try_into_unsigned {
+    ($($int:ty),*) => {
+        $(
+            /// Rounds towards negative infinity, failing outside the range of
+            /// the target type.
+            impl TryFrom<DyadicFraction> for $int {
+                type Error = OutOfRange;
+
+                fn try_from(val: DyadicFraction) -> Result<Self, Self::Error> {
+                    <$int>::try_from(val.scaled(0, Rounding::Floor)).map_err(|_| OutOfRange)
+                }
+            }
+        )*
+    };
+}
+
+impl_try_into_unsigned!(u8, u16, u32);
+
 impl From<i32> for DyadicFraction {
     fn from(num: i32) -> Self {
         Self::new(num, 0)
@@ -206,16 +1217,78 @@ impl From<i8> for DyadicFraction {
     }
 }
 
+/// Human-readable formats get the `"3/8"` string form, switching to
+/// `"num/2^power"` beyond 64-bit denominators. Compact formats, and whole
+/// values too large for the parser to read back, get a `(numerator, power)`
+/// tuple of the canonical form.
+#[cfg(feature = "serde")]
+impl serde::Serialize for DyadicFraction {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let val = self.canonical();
+        let bits = bit_len(val.num.unsigned_abs() as u128) - val.power as i32;
+        if !serializer.is_human_readable() || bits >= 120 {
+            return (val.num, val.power).serialize(serializer);
+        }
+
+        let mut buf = [0; 48];
+        let mut cursor = Cursor::new(&mut buf);
+        render::write_exact(val, &mut cursor).map_err(serde::ser::Error::custom)?;
+        serializer.serialize_str(cursor.finish())
+    }
+}
+
+/// Accepts either form regardless of the format, canonicalizing tuples and
+/// parsing strings as [`FromStr`] does.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for DyadicFraction {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(DyadicVisitor)
+        } else {
+            deserializer.deserialize_tuple(2, DyadicVisitor)
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+struct DyadicVisitor;
+
+#[cfg(feature = "serde")]
+impl<'de> serde::de::Visitor<'de> for DyadicVisitor {
+    type Value = DyadicFraction;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a dyadic fraction string or a (numerator, power) tuple")
+    }
+
+    fn visit_str<E: serde::de::Error>(self, src: &str) -> Result<Self::Value, E> {
+        src.parse().map_err(E::custom)
+    }
+
+    fn visit_seq<A: serde::de::SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        use serde::de::Error;
+
+        let num = seq
+            .next_element()?
+            .ok_or_else(|| A::Error::invalid_length(0, &self))?;
+        let power = seq
+            .next_element()?
+            .ok_or_else(|| A::Error::invalid_length(1, &self))?;
+        Ok(DyadicFraction::new(num, power))
+    }
+}
+
 impl Add for DyadicFraction {
     type Output = Self;
 
+    #[track_caller]
     fn add(self, other: Self) -> Self {
-        let (fst, snd, power) = self.saturating_cross(other);
-        Self::new(fst.saturating_add(snd), power)
+        strict(self.add_with_flags(other), "add")
     }
 }
 
 impl AddAssign for DyadicFraction {
+    #[track_caller]
     fn add_assign(&mut self, rhs: Self) {
         *self = *self + rhs;
     }
@@ -224,13 +1297,14 @@ impl AddAssign for DyadicFraction {
 impl Sub for DyadicFraction {
     type Output = Self;
 
+    #[track_caller]
     fn sub(self, other: Self) -> Self {
-        let (fst, snd, power) = self.saturating_cross(other);
-        Self::new(fst.saturating_sub(snd), power)
+        strict(self.sub_with_flags(other), "subtract")
     }
 }
 
 impl SubAssign for DyadicFraction {
+    #[track_caller]
     fn sub_assign(&mut self, rhs: Self) {
         *self = *self - rhs;
     }
@@ -239,21 +1313,122 @@ impl SubAssign for DyadicFraction {
 impl Mul for DyadicFraction {
     type Output = Self;
 
+    #[track_caller]
     fn mul(self, other: Self) -> Self {
-        Self::new(
-            self.num.saturating_mul(other.num),
-            self.power.saturating_add(other.power),
-        )
-        .canonical()
+        strict(self.mul_with_flags(other), "multiply")
     }
 }
 
 impl MulAssign for DyadicFraction {
+    #[track_caller]
     fn mul_assign(&mut self, rhs: Self) {
         *self = *self * rhs;
     }
 }
 
+macro_rules! impl_ref_ops {
+    ($($op:ident, $fn:ident, $op_assign:ident, $fn_assign:ident);*) => {
+        $(
+            impl $op<&DyadicFraction> for DyadicFraction {
+                type Output = Self;
+
+                #[track_caller]
+                fn $fn(self, other: &Self) -> Self {
+                    $op::$fn(self, *other)
+                }
+            }
+
+            impl $op<DyadicFraction> for &DyadicFraction {
+                type Output = DyadicFraction;
+
+                #[track_caller]
+                fn $fn(self, other: DyadicFraction) -> DyadicFraction {
+                    $op::$fn(*self, other)
+                }
+            }
+
+            impl $op<&DyadicFraction> for &DyadicFraction {
+                type Output = DyadicFraction;
+
+                #[track_caller]
+                fn $fn(self, other: &DyadicFraction) -> DyadicFraction {
+                    $op::$fn(*self, *other)
+                }
+            }
+
+            impl $op_assign<&DyadicFraction> for DyadicFraction {
+                #[track_caller]
+                fn $fn_assign(&mut self, rhs: &Self) {
+                    $op_assign::$fn_assign(self, *rhs);
+                }
+            }
+        )*
+    };
+}
+
+impl_ref_ops!(
+    Add, add, AddAssign, add_assign;
+    Sub, sub, SubAssign, sub_assign;
+    Mul, mul, MulAssign, mul_assign
+);
+
+mod sealed {
+    /// Integer types usable as operands next to a dyadic fraction.
+    pub trait Int: Into<super::DyadicFraction> {}
+
+    impl Int for i32 {}
+    impl Int for i16 {}
+    impl Int for i8 {}
+}
+
+macro_rules! impl_int_ops {
+    ($($op:ident, $fn:ident, $op_assign:ident, $fn_assign:ident);*) => {
+        $(
+            /// Converts the integer first, with the same saturation behavior.
+            ///
+            /// A single generic impl keeps the output type known while an
+            /// integer literal is still untyped, so `(val * 3).floor()`
+            /// compiles.
+            impl<T: sealed::Int> $op<T> for DyadicFraction {
+                type Output = Self;
+
+                #[track_caller]
+                fn $fn(self, other: T) -> Self {
+                    $op::$fn(self, other.into())
+                }
+            }
+
+            impl<T: sealed::Int> $op_assign<T> for DyadicFraction {
+                #[track_caller]
+                fn $fn_assign(&mut self, rhs: T) {
+                    *self = $op::$fn(*self, rhs);
+                }
+            }
+
+            impl_int_ops!(@rev $op, $fn, i32, i16, i8);
+        )*
+    };
+    (@rev $op:ident, $fn:ident, $($int:ty),*) => {
+        $(
+            /// Converts the integer first, with the same saturation behavior.
+            impl $op<DyadicFraction> for $int {
+                type Output = DyadicFraction;
+
+                #[track_caller]
+                fn $fn(self, other: DyadicFraction) -> DyadicFraction {
+                    $op::$fn(DyadicFraction::from(self), other)
+                }
+            }
+        )*
+    };
+}
+
+impl_int_ops!(
+    Add, add, AddAssign, add_assign;
+    Sub, sub, SubAssign, sub_assign;
+    Mul, mul, MulAssign, mul_assign
+);
+
 impl Neg for DyadicFraction {
     type Output = Self;
 
@@ -262,11 +1437,17 @@ impl Neg for DyadicFraction {
     }
 }
 
+impl Neg for &DyadicFraction {
+    type Output = DyadicFraction;
+
+    fn neg(self) -> DyadicFraction {
+        -*self
+    }
+}
+
 impl PartialEq for DyadicFraction {
     fn eq(&self, other: &Self) -> bool {
-        let lhs = self.canonical();
-        let rhs = other.canonical();
-        lhs.power == rhs.power && lhs.num == rhs.num
+        self.cmp(other) == Ordering::Equal
     }
 }
 
@@ -278,28 +1459,101 @@ impl PartialOrd for DyadicFraction {
     }
 }
 
+/// Compares exact values, independent of representation and saturation.
 impl Ord for DyadicFraction {
     fn cmp(&self, other: &Self) -> Ordering {
-        let delta = (*self - *other).numerator().signum();
-        if delta == 0 {
-            Ordering::Equal
-        } else if delta.is_positive() {
-            Ordering::Greater
+        let (lhs, rhs) = (self.power as i32, other.power as i32);
+        if (lhs - rhs).abs() <= 64 {
+            let power = lhs.max(rhs);
+            let lhs = (self.num as i128) << (power - lhs);
+            let rhs = (other.num as i128) << (power - rhs);
+            return lhs.cmp(&rhs);
+        }
+        let sign = self.num.signum().cmp(&other.num.signum());
+        if sign != Ordering::Equal || self.num == 0 {
+            return sign;
+        }
+        // Powers this far apart put the magnitudes more than 32 bits apart.
+        let lhs = bit_len(self.num.unsigned_abs() as u128) - lhs;
+        let rhs = bit_len(other.num.unsigned_abs() as u128) - rhs;
+        if self.num < 0 {
+            rhs.cmp(&lhs)
         } else {
-            Ordering::Less
+            lhs.cmp(&rhs)
         }
     }
 }
 
-impl fmt::Display for DyadicFraction {
+/// Shows the canonical `num/2^power` value alongside the raw fields.
+impl fmt::Debug for DyadicFraction {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let val = self.canonical();
-        let shift = val.power.abs();
-        if val.power <= 0 {
-            write!(f, "{}", saturating_shl(val.num, shift))
-        } else {
-            write!(f, "{}/{}", val.num, saturating_shl(1, shift))
+        f.debug_struct("DyadicFraction")
+            .field("value", &format_args!("{}/2^{}", val.num, val.power))
+            .field("num", &self.num)
+            .field("power", &self.power)
+            .finish()
+    }
+}
+
+/// Prints `num/den` by default. The alternate flag (`{:#}`) switches to the
+/// exact decimal expansion and a precision (`{:.3}`) to a decimal rounded half
+/// away from zero. Width, fill, alignment and the `+` and `0` flags apply to
+/// every form as they do for integers.
+impl fmt::Display for DyadicFraction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() || f.precision().is_some() {
+            let mut dec = Decimal::new(*self);
+            if let Some(precision) = f.precision() {
+                dec.round(precision);
+            }
+            return render::pad(f, dec.negative, Body::Decimal(&dec, f.precision()));
         }
+        render::pad(f, self.is_negative(), Body::Fraction(*self))
+    }
+}
+
+macro_rules! impl_radix_fmt {
+    ($trait:ident, $plain:literal, $alternate:literal) => {
+        /// Shows the canonical numerator in sign-magnitude form together with
+        /// the power of two it is scaled by, e.g. `-101 * 2^-3`.
+        impl fmt::$trait for DyadicFraction {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                let val = self.canonical();
+                let mag = val.num.unsigned_abs();
+                if val.num < 0 {
+                    f.write_str("-")?;
+                }
+                if f.alternate() {
+                    write!(f, $alternate, mag)?;
+                } else {
+                    write!(f, $plain, mag)?;
+                }
+                write!(f, " * 2^{}", -(val.power as i16))
+            }
+        }
+    };
+}
+
+impl_radix_fmt!(Binary, "{:b}", "{:#b}");
+impl_radix_fmt!(LowerHex, "{:x}", "{:#x}");
+impl_radix_fmt!(UpperHex, "{:X}", "{:#X}");
+
+/// Decimal scientific notation, e.g. `3.75e-1`, honoring the precision and
+/// padding flags.
+impl fmt::LowerExp for DyadicFraction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let dec = Decimal::new(*self);
+        render::pad(f, dec.negative, Body::Exp(&dec, f.precision(), b'e'))
+    }
+}
+
+/// Decimal scientific notation, e.g. `3.75E-1`, honoring the precision and
+/// padding flags.
+impl fmt::UpperExp for DyadicFraction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let dec = Decimal::new(*self);
+        render::pad(f, dec.negative, Body::Exp(&dec, f.precision(), b'E'))
     }
 }
 
@@ -323,6 +1577,12 @@ pub mod consts {
     /// π/3
     pub const FRAC_PI_3: DyadicFraction = DyadicFraction::new(68629, 16);
 
+    /// π/6
+    pub const FRAC_PI_6: DyadicFraction = DyadicFraction::new(34315, 16);
+
+    /// π/8
+    pub const FRAC_PI_8: DyadicFraction = DyadicFraction::new(3217, 13);
+
     /// 1/π
     pub const FRAC_1_PI: DyadicFraction = DyadicFraction::new(20861, 16);
 
@@ -332,15 +1592,30 @@ pub mod consts {
     /// 2/sqrt(π)
     pub const FRAC_2_SQRT_PI: DyadicFraction = DyadicFraction::new(73949, 16);
 
+    /// 1/sqrt(π)
+    pub const FRAC_1_SQRT_PI: DyadicFraction = DyadicFraction::new(36975, 16);
+
     /// sqrt(2)
     pub const SQRT_2: DyadicFraction = DyadicFraction::new(46341, 15);
 
     /// 1/sqrt(2)
     pub const FRAC_1_SQRT_2: DyadicFraction = DyadicFraction::new(46341, 16);
 
+    /// sqrt(3)
+    pub const SQRT_3: DyadicFraction = DyadicFraction::new(14189, 13);
+
+    /// 1/sqrt(3)
+    pub const FRAC_1_SQRT_3: DyadicFraction = DyadicFraction::new(37837, 16);
+
+    /// Cube root of 2
+    pub const CBRT_2: DyadicFraction = DyadicFraction::new(41285, 15);
+
     /// Euler's number (e)
     pub const E: DyadicFraction = DyadicFraction::new(178145, 16);
 
+    /// The Euler–Mascheroni constant (γ)
+    pub const EGAMMA: DyadicFraction = DyadicFraction::new(9457, 14);
+
     /// log<sub>2</sub>(10)
     pub const LOG2_10: DyadicFraction = DyadicFraction::new(108853, 15);
 
@@ -364,4 +1639,65 @@ pub mod consts {
 
     /// Supergolden ratio
     pub const PSI: DyadicFraction = DyadicFraction::new(6003, 12);
+
+    /// 1/3, rounded to nearest at a denominator of 2<sup>16</sup>
+    pub const FRAC_1_3: DyadicFraction = DyadicFraction::new(21845, 16);
+
+    /// 1/5, rounded to nearest at a denominator of 2<sup>16</sup>
+    pub const FRAC_1_5: DyadicFraction = DyadicFraction::new(13107, 16);
+
+    /// 1/10, rounded to nearest at a denominator of 2<sup>16</sup>
+    pub const FRAC_1_10: DyadicFraction = DyadicFraction::new(3277, 15);
+
+    /// π rounded to nearest at a denominator of at most
+    /// 2<sup>`max_power`</sup>, or the finest that fits.
+    pub const fn pi(max_power: i8) -> DyadicFraction {
+        from_q60(3622009729038561421, max_power)
+    }
+
+    /// τ = 2π rounded to nearest at a denominator of at most
+    /// 2<sup>`max_power`</sup>, or the finest that fits.
+    pub const fn tau(max_power: i8) -> DyadicFraction {
+        from_q60(7244019458077122842, max_power)
+    }
+
+    /// Euler's number (e) rounded to nearest at a denominator of at most
+    /// 2<sup>`max_power`</sup>, or the finest that fits.
+    pub const fn e(max_power: i8) -> DyadicFraction {
+        from_q60(3133965575612453543, max_power)
+    }
+
+    /// sqrt(2) rounded to nearest at a denominator of at most
+    /// 2<sup>`max_power`</sup>, or the finest that fits.
+    pub const fn sqrt_2(max_power: i8) -> DyadicFraction {
+        from_q60(1630477228166597777, max_power)
+    }
+
+    /// ln(2) rounded to nearest at a denominator of at most
+    /// 2<sup>`max_power`</sup>, or the finest that fits.
+    pub const fn ln_2(max_power: i8) -> DyadicFraction {
+        from_q60(799144290325165979, max_power)
+    }
+
+    /// ln(10) rounded to nearest at a denominator of at most
+    /// 2<sup>`max_power`</sup>, or the finest that fits.
+    pub const fn ln_10(max_power: i8) -> DyadicFraction {
+        from_q60(2654699869899991814, max_power)
+    }
+
+    /// log<sub>2</sub>(e) rounded to nearest at a denominator of at most
+    /// 2<sup>`max_power`</sup>, or the finest that fits.
+    pub const fn log2_e(max_power: i8) -> DyadicFraction {
+        from_q60(1663314137230540311, max_power)
+    }
+
+    /// log<sub>2</sub>(10) rounded to nearest at a denominator of at most
+    /// 2<sup>`max_power`</sup>, or the finest that fits.
+    pub const fn log2_10(max_power: i8) -> DyadicFraction {
+        from_q60(3829922337353294528, max_power)
+    }
+
+    const fn from_q60(val: i128, max_power: i8) -> DyadicFraction {
+        DyadicFraction::round_ratio(val, 1, 60, max_power)
+    }
 }
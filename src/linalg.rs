@@ -0,0 +1,354 @@
+use crate::wide::Wide;
+use crate::DyadicFraction;
+use core::ops::*;
+
+/// A two-dimensional vector with dyadic fraction components.
+///
+/// Dot products, cross products and norms are accumulated exactly and
+/// narrowed once, rounding towards negative infinity when the result does
+/// not fit.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Vec2 {
+    pub x: DyadicFraction,
+    pub y: DyadicFraction,
+}
+
+/// A three-dimensional vector with dyadic fraction components.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Vec3 {
+    pub x: DyadicFraction,
+    pub y: DyadicFraction,
+    pub z: DyadicFraction,
+}
+
+impl Vec2 {
+    pub const fn new(x: DyadicFraction, y: DyadicFraction) -> Self {
+        Self { x, y }
+    }
+
+    /// 2D cross product, the z component of the 3D cross product.
+    pub fn cross(self, other: Self) -> DyadicFraction {
+        minor(self.x, other.y, self.y, other.x).to_df()
+    }
+}
+
+impl Vec3 {
+    pub const fn new(x: DyadicFraction, y: DyadicFraction, z: DyadicFraction) -> Self {
+        Self { x, y, z }
+    }
+
+    pub fn cross(self, other: Self) -> Self {
+        Self::new(
+            minor(self.y, other.z, self.z, other.y).to_df(),
+            minor(self.z, other.x, self.x, other.z).to_df(),
+            minor(self.x, other.y, self.y, other.x).to_df(),
+        )
+    }
+}
+
+macro_rules! impl_vec {
+    ($vec:ident { $($field:ident),+ }) => {
+        impl $vec {
+            pub const fn zero() -> Self {
+                Self { $($field: DyadicFraction::zero()),+ }
+            }
+
+            pub fn dot(self, other: Self) -> DyadicFraction {
+                let mut acc = Wide::default();
+                $(acc.add_product(self.$field, other.$field);)+
+                acc.to_df()
+            }
+
+            pub fn norm_sqr(self) -> DyadicFraction {
+                self.dot(self)
+            }
+
+            /// Euclidean length rounded down to a denominator of at most
+            /// `2^max_power`.
+            pub fn norm(self, max_power: i8) -> DyadicFraction {
+                let mut acc = Wide::default();
+                $(acc.add_product(self.$field, self.$field);)+
+                acc.sqrt(max_power)
+            }
+
+            pub fn scale(self, factor: DyadicFraction) -> Self {
+                Self { $($field: self.$field * factor),+ }
+            }
+
+            /// Linear interpolation `a + (b - a) * t`, computed exactly per
+            /// component before narrowing.
+            pub fn lerp(a: Self, b: Self, t: DyadicFraction) -> Self {
+                Self {
+                    $($field: {
+                        let mut acc = Wide::from_df(a.$field);
+                        acc.add_product(b.$field, t);
+                        acc.sub_product(a.$field, t);
+                        acc.to_df()
+                    }),+
+                }
+            }
+        }
+
+        impl Add for $vec {
+            type Output = Self;
+
+            fn add(self, other: Self) -> Self {
+                Self { $($field: self.$field + other.$field),+ }
+            }
+        }
+
+        impl AddAssign for $vec {
+            fn add_assign(&mut self, rhs: Self) {
+                *self = *self + rhs;
+            }
+        }
+
+        impl Sub for $vec {
+            type Output = Self;
+
+            fn sub(self, other: Self) -> Self {
+                Self { $($field: self.$field - other.$field),+ }
+            }
+        }
+
+        impl SubAssign for $vec {
+            fn sub_assign(&mut self, rhs: Self) {
+                *self = *self - rhs;
+            }
+        }
+
+        impl Mul<DyadicFraction> for $vec {
+            type Output = Self;
+
+            fn mul(self, factor: DyadicFraction) -> Self {
+                self.scale(factor)
+            }
+        }
+
+        impl MulAssign<DyadicFraction> for $vec {
+            fn mul_assign(&mut self, factor: DyadicFraction) {
+                *self = self.scale(factor);
+            }
+        }
+
+        impl Neg for $vec {
+            type Output = Self;
+
+            fn neg(self) -> Self::Output {
+                Self { $($field: -self.$field),+ }
+            }
+        }
+    };
+}
+
+impl_vec!(Vec2 { x, y });
+impl_vec!(Vec3 { x, y, z });
+
+/// A 2x2 matrix stored as row vectors.
+///
+/// Products and determinants are accumulated exactly and narrowed once,
+/// rounding towards negative infinity when the result does not fit.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Mat2 {
+    pub rows: [Vec2; 2],
+}
+
+/// A 3x3 matrix stored as row vectors.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Mat3 {
+    pub rows: [Vec3; 3],
+}
+
+impl Mat2 {
+    pub const fn from_rows(x: Vec2, y: Vec2) -> Self {
+        Self { rows: [x, y] }
+    }
+
+    pub const fn zero() -> Self {
+        Self::from_rows(Vec2::zero(), Vec2::zero())
+    }
+
+    pub const fn identity() -> Self {
+        let (zero, one) = (DyadicFraction::zero(), DyadicFraction::new(1, 0));
+        Self::from_rows(Vec2::new(one, zero), Vec2::new(zero, one))
+    }
+
+    pub fn transpose(self) -> Self {
+        let [x, y] = self.rows;
+        Self::from_rows(Vec2::new(x.x, y.x), Vec2::new(x.y, y.y))
+    }
+
+    pub fn determinant(self) -> DyadicFraction {
+        self.wide_determinant().to_df()
+    }
+
+    /// Inverse rounded to nearest at a denominator of at most `2^max_power`,
+    /// or `None` for a singular matrix.
+    pub fn inverse(self, max_power: i8) -> Option<Self> {
+        let det = self.wide_determinant();
+        if det.is_zero() {
+            return None;
+        }
+        let [x, y] = self.rows;
+        let entry = |val: Wide| val.div(det, max_power);
+        Some(Self::from_rows(
+            Vec2::new(entry(Wide::from_df(y.y)), entry(Wide::from_df(x.y).neg())),
+            Vec2::new(entry(Wide::from_df(y.x).neg()), entry(Wide::from_df(x.x))),
+        ))
+    }
+
+    /// Solves `self * v = rhs` by Cramer's rule, rounding each component to
+    /// nearest at a denominator of at most `2^max_power`, or returns `None`
+    /// for a singular matrix.
+    pub fn solve(self, rhs: Vec2, max_power: i8) -> Option<Vec2> {
+        let det = self.wide_determinant();
+        if det.is_zero() {
+            return None;
+        }
+        let [x, y] = self.rows;
+        Some(Vec2::new(
+            minor(rhs.x, y.y, x.y, rhs.y).div(det, max_power),
+            minor(x.x, rhs.y, y.x, rhs.x).div(det, max_power),
+        ))
+    }
+
+    fn wide_determinant(self) -> Wide {
+        let [x, y] = self.rows;
+        minor(x.x, y.y, x.y, y.x)
+    }
+}
+
+impl Mat3 {
+    pub const fn from_rows(x: Vec3, y: Vec3, z: Vec3) -> Self {
+        Self { rows: [x, y, z] }
+    }
+
+    pub const fn zero() -> Self {
+        Self::from_rows(Vec3::zero(), Vec3::zero(), Vec3::zero())
+    }
+
+    pub const fn identity() -> Self {
+        let (zero, one) = (DyadicFraction::zero(), DyadicFraction::new(1, 0));
+        Self::from_rows(
+            Vec3::new(one, zero, zero),
+            Vec3::new(zero, one, zero),
+            Vec3::new(zero, zero, one),
+        )
+    }
+
+    pub fn transpose(self) -> Self {
+        let [x, y, z] = self.rows;
+        Self::from_rows(
+            Vec3::new(x.x, y.x, z.x),
+            Vec3::new(x.y, y.y, z.y),
+            Vec3::new(x.z, y.z, z.z),
+        )
+    }
+
+    pub fn determinant(self) -> DyadicFraction {
+        self.wide_determinant(&self.cofactors()).to_df()
+    }
+
+    /// Inverse rounded to nearest at a denominator of at most `2^max_power`,
+    /// or `None` for a singular matrix.
+    pub fn inverse(self, max_power: i8) -> Option<Self> {
+        let cof = self.cofactors();
+        let det = self.wide_determinant(&cof);
+        if det.is_zero() {
+            return None;
+        }
+        let entry = |row: usize, col: usize| cof[col][row].div(det, max_power);
+        Some(Self::from_rows(
+            Vec3::new(entry(0, 0), entry(0, 1), entry(0, 2)),
+            Vec3::new(entry(1, 0), entry(1, 1), entry(1, 2)),
+            Vec3::new(entry(2, 0), entry(2, 1), entry(2, 2)),
+        ))
+    }
+
+    /// Solves `self * v = rhs` by Cramer's rule, rounding each component to
+    /// nearest at a denominator of at most `2^max_power`, or returns `None`
+    /// for a singular matrix.
+    pub fn solve(self, rhs: Vec3, max_power: i8) -> Option<Vec3> {
+        let cof = self.cofactors();
+        let det = self.wide_determinant(&cof);
+        if det.is_zero() {
+            return None;
+        }
+        let component = |col: usize| {
+            let mut acc = cof[0][col].mul_df(rhs.x);
+            acc.add(cof[1][col].mul_df(rhs.y));
+            acc.add(cof[2][col].mul_df(rhs.z));
+            acc.div(det, max_power)
+        };
+        Some(Vec3::new(component(0), component(1), component(2)))
+    }
+
+    fn entries(self) -> [[DyadicFraction; 3]; 3] {
+        self.rows.map(|row| [row.x, row.y, row.z])
+    }
+
+    /// Signed cofactors, exact.
+    fn cofactors(self) -> [[Wide; 3]; 3] {
+        let m = self.entries();
+        let mut cof = [[Wide::default(); 3]; 3];
+        for (i, row) in cof.iter_mut().enumerate() {
+            let (i1, i2) = ((i + 1) % 3, (i + 2) % 3);
+            for (j, val) in row.iter_mut().enumerate() {
+                let (j1, j2) = ((j + 1) % 3, (j + 2) % 3);
+                *val = minor(m[i1][j1], m[i2][j2], m[i1][j2], m[i2][j1]);
+            }
+        }
+        cof
+    }
+
+    fn wide_determinant(self, cof: &[[Wide; 3]; 3]) -> Wide {
+        let [x, _, _] = self.rows;
+        let mut acc = cof[0][0].mul_df(x.x);
+        acc.add(cof[0][1].mul_df(x.y));
+        acc.add(cof[0][2].mul_df(x.z));
+        acc
+    }
+}
+
+macro_rules! impl_mat {
+    ($mat:ident, $vec:ident { $($field:ident),+ }) => {
+        impl Mul for $mat {
+            type Output = Self;
+
+            fn mul(self, other: Self) -> Self {
+                let cols = other.transpose().rows;
+                Self {
+                    rows: self.rows.map(|row| {
+                        let [$($field),+] = cols;
+                        $vec { $($field: row.dot($field)),+ }
+                    }),
+                }
+            }
+        }
+
+        impl MulAssign for $mat {
+            fn mul_assign(&mut self, rhs: Self) {
+                *self = *self * rhs;
+            }
+        }
+
+        impl Mul<$vec> for $mat {
+            type Output = $vec;
+
+            fn mul(self, vec: $vec) -> $vec {
+                let [$($field),+] = self.rows;
+                $vec { $($field: $field.dot(vec)),+ }
+            }
+        }
+    };
+}
+
+impl_mat!(Mat2, Vec2 { x, y });
+impl_mat!(Mat3, Vec3 { x, y, z });
+
+/// Computes `a * b - c * d` exactly.
+fn minor(a: DyadicFraction, b: DyadicFraction, c: DyadicFraction, d: DyadicFraction) -> Wide {
+    let mut acc = Wide::product(a, b);
+    acc.sub_product(c, d);
+    acc
+}
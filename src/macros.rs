@@ -0,0 +1,110 @@
+/// Asserts that two dyadic fractions are equal, printing both as exact
+/// decimals and as fractions on failure.
+#[macro_export]
+macro_rules! assert_df_eq {
+    ($left:expr, $right:expr $(,)?) => {{
+        let left: $crate::DyadicFraction = $left;
+        let right: $crate::DyadicFraction = $right;
+        if left != right {
+            panic!(
+                "assertion `left == right` failed\n  left: {:#} ({})\n right: {:#} ({})",
+                left, left, right, right,
+            );
+        }
+    }};
+    ($left:expr, $right:expr, $($arg:tt)+) => {{
+        let left: $crate::DyadicFraction = $left;
+        let right: $crate::DyadicFraction = $right;
+        if left != right {
+            panic!(
+                "assertion `left == right` failed: {}\n  left: {:#} ({})\n right: {:#} ({})",
+                format_args!($($arg)+), left, left, right, right,
+            );
+        }
+    }};
+}
+
+/// Asserts that two dyadic fractions differ by at most `tol`, printing both
+/// as exact decimals and as fractions on failure.
+///
+/// The difference is computed exactly, see
+/// [`DyadicFraction::abs_diff`](crate::DyadicFraction::abs_diff).
+#[macro_export]
+macro_rules! assert_df_approx_eq {
+    ($left:expr, $right:expr, $tol:expr $(,)?) => {{
+        let left: $crate::DyadicFraction = $left;
+        let right: $crate::DyadicFraction = $right;
+        let tol: $crate::DyadicFraction = $tol;
+        let diff = left.abs_diff(right);
+        if diff > tol {
+            panic!(
+                "assertion `left ≈ right` failed\n  left: {:#} ({})\n right: {:#} ({})\n  diff: {:#} > {:#}",
+                left, left, right, right, diff, tol,
+            );
+        }
+    }};
+    ($left:expr, $right:expr, $tol:expr, $($arg:tt)+) => {{
+        let left: $crate::DyadicFraction = $left;
+        let right: $crate::DyadicFraction = $right;
+        let tol: $crate::DyadicFraction = $tol;
+        let diff = left.abs_diff(right);
+        if diff > tol {
+            panic!(
+                "assertion `left ≈ right` failed: {}\n  left: {:#} ({})\n right: {:#} ({})\n  diff: {:#} > {:#}",
+                format_args!($($arg)+), left, left, right, right, diff, tol,
+            );
+        }
+    }};
+}
+
+/// Dyadic fraction from a literal, evaluated at compile time.
+///
+/// Accepts the forms of [`DyadicFraction::parse`](crate::DyadicFraction::parse)
+/// as a string, as well as plain numbers and fractions: `df!("7/2^5")`,
+/// `df!(1.375)`, `df!(-3/8)`. Literals that are not exactly representable,
+/// such as `0.1` or `1/3`, fail to compile unless a maximum denominator
+/// power is given to round them to nearest: `df!(0.1, 16)`. The result can
+/// initialize constants.
+#[macro_export]
+macro_rules! df {
+    (- $num:literal / $den:literal, $max_power:expr $(,)?) => {{
+        const VALUE: $crate::DyadicFraction =
+            $crate::DyadicFraction::from_ratio(-$num, $den, $max_power);
+        VALUE
+    }};
+    ($num:literal / $den:literal, $max_power:expr $(,)?) => {{
+        const VALUE: $crate::DyadicFraction =
+            $crate::DyadicFraction::from_ratio($num, $den, $max_power);
+        VALUE
+    }};
+    (- $num:literal / $den:literal $(,)?) => {
+        $crate::df!(@parse concat!("-", $num, "/", $den))
+    };
+    ($num:literal / $den:literal $(,)?) => {
+        $crate::df!(@parse concat!($num, "/", $den))
+    };
+    (- $val:literal $(, $max_power:expr)? $(,)?) => {
+        $crate::df!(@parse concat!("-", $val) $(, $max_power)?)
+    };
+    ($val:literal $(, $max_power:expr)? $(,)?) => {
+        $crate::df!(@parse concat!($val) $(, $max_power)?)
+    };
+    (@parse $src:expr) => {{
+        const VALUE: $crate::DyadicFraction = match $crate::DyadicFraction::parse_exact($src) {
+            Ok(val) => val,
+            Err($crate::ParseError::NotDyadic) => {
+                panic!("literal is not a dyadic fraction, give a precision to round it")
+            }
+            Err($crate::ParseError::OutOfRange) => panic!("literal out of dyadic fraction range"),
+            Err(_) => panic!("invalid dyadic fraction literal"),
+        };
+        VALUE
+    }};
+    (@parse $src:expr, $max_power:expr) => {{
+        const VALUE: $crate::DyadicFraction = match $crate::DyadicFraction::parse($src, $max_power) {
+            Ok(val) => val,
+            Err(_) => panic!("invalid dyadic fraction literal"),
+        };
+        VALUE
+    }};
+}
@@ -0,0 +1,381 @@
+//! Supervision of measured signals, for detecting sensor faults and unsafe
+//! conditions alongside a controller.
+
+use crate::wide::Wide;
+use crate::{DyadicFraction, Rounding};
+use core::ops::{BitOr, BitOrAssign};
+
+/// Rate of change of a signal sampled every `dt`, measured across the last
+/// `N` samples and checked against limits.
+///
+/// The rate is the difference between the newest and the oldest sample in
+/// the window over the time between them, rounded to nearest, so a longer
+/// window rejects more noise at the cost of a slower response. Until the
+/// window fills up, it spans the samples seen so far. Typical uses are a
+/// thermocouple that jumps when it breaks, a reading that stops moving
+/// while the actuator drives, or a temperature running away.
+#[derive(Copy, Clone, Debug)]
+pub struct RateOfChange<const N: usize> {
+    dt: DyadicFraction,
+    min: DyadicFraction,
+    max: DyadicFraction,
+    window: [DyadicFraction; N],
+    head: usize,
+    len: usize,
+    rate: DyadicFraction,
+}
+
+impl<const N: usize> RateOfChange<N> {
+    /// Creates a monitor allowing rates in `[min, max]` per unit of time,
+    /// with limits given in any order.
+    pub fn new(dt: DyadicFraction, min: DyadicFraction, max: DyadicFraction) -> Self {
+        let mut monitor = Self {
+            dt,
+            min,
+            max,
+            window: [DyadicFraction::zero(); N],
+            head: 0,
+            len: 0,
+            rate: DyadicFraction::zero(),
+        };
+        monitor.set_limits(min, max);
+        monitor
+    }
+
+    /// Feeds `sample` and returns the new rate.
+    pub fn update(&mut self, sample: DyadicFraction) -> DyadicFraction {
+        if N == 0 {
+            return self.rate;
+        }
+        self.window[self.head] = sample;
+        self.head = (self.head + 1) % N;
+        self.len = (self.len + 1).min(N);
+        if self.len > 1 {
+            let oldest = self.window[if self.len == N { self.head } else { 0 }];
+            let mut diff = Wide::from_df(sample);
+            diff.sub(Wide::from_df(oldest));
+            let span = Wide::new(self.len as i128 - 1, 0).mul_df(self.dt);
+            self.rate = diff.div(span, i8::MAX);
+        }
+        self.rate
+    }
+
+    /// Rate per unit of time, zero until two samples are seen.
+    pub const fn rate(&self) -> DyadicFraction {
+        self.rate
+    }
+
+    /// Whether the last rate is outside the limits.
+    pub fn is_exceeded(&self) -> bool {
+        self.rate < self.min || self.rate > self.max
+    }
+
+    /// Clears the window and the rate.
+    pub fn reset(&mut self) {
+        *self = Self::new(self.dt, self.min, self.max);
+    }
+
+    pub const fn dt(&self) -> DyadicFraction {
+        self.dt
+    }
+
+    /// Limits `(min, max)`.
+    pub const fn limits(&self) -> (DyadicFraction, DyadicFraction) {
+        (self.min, self.max)
+    }
+
+    /// Sets the limits, given in any order.
+    pub fn set_limits(&mut self, min: DyadicFraction, max: DyadicFraction) {
+        self.min = DyadicFraction::min(min, max);
+        self.max = DyadicFraction::max(min, max);
+    }
+}
+
+/// Set of active alarms raised by [`Limits`], one bit each, so the status
+/// fits a register or telemetry byte.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Alarms(u8);
+
+impl Alarms {
+    pub const NONE: Self = Self(0);
+    pub const LOW_WARNING: Self = Self(1);
+    pub const HIGH_WARNING: Self = Self(2);
+    pub const LOW_TRIP: Self = Self(4);
+    pub const HIGH_TRIP: Self = Self(8);
+
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Whether either trip alarm is active.
+    pub const fn is_tripped(self) -> bool {
+        self.0 & (Self::LOW_TRIP.0 | Self::HIGH_TRIP.0) != 0
+    }
+
+    pub const fn bits(self) -> u8 {
+        self.0
+    }
+
+    /// Alarms from [`bits`](Self::bits), ignoring unknown bits.
+    pub const fn from_bits(bits: u8) -> Self {
+        Self(bits & 0xF)
+    }
+}
+
+impl BitOr for Alarms {
+    type Output = Self;
+
+    fn bitor(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+}
+
+impl BitOrAssign for Alarms {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+/// Alarm threshold for [`Limits`].
+///
+/// A high alarm is raised once the signal reaches `level` and released once
+/// it drops to `level - hysteresis`; a low alarm mirrors this. A latching
+/// alarm stays raised until acknowledged.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Threshold {
+    pub level: DyadicFraction,
+    pub hysteresis: DyadicFraction,
+    pub latching: bool,
+}
+
+impl Threshold {
+    /// Creates a threshold, taking the magnitude of `hysteresis`.
+    pub const fn new(level: DyadicFraction, hysteresis: DyadicFraction, latching: bool) -> Self {
+        Self {
+            level,
+            hysteresis: hysteresis.abs(),
+            latching,
+        }
+    }
+}
+
+/// Low and high warning and trip alarms on a signal, the protection
+/// wrapper around a measurement feeding a heater or motor controller.
+///
+/// Each of the four thresholds is optional and has its own hysteresis and
+/// latching. Comparisons are exact.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Limits {
+    /// Thresholds in the bit order of [`Alarms`].
+    thresholds: [Option<Threshold>; 4],
+    status: Alarms,
+    value: DyadicFraction,
+}
+
+impl Limits {
+    /// Creates a monitor with every threshold disabled.
+    pub const fn new() -> Self {
+        Self {
+            thresholds: [None; 4],
+            status: Alarms::NONE,
+            value: DyadicFraction::zero(),
+        }
+    }
+
+    /// Checks `value` against the thresholds and returns the active alarms.
+    pub fn update(&mut self, value: DyadicFraction) -> Alarms {
+        self.value = value;
+        for (idx, threshold) in self.thresholds.iter().enumerate() {
+            let Some(threshold) = threshold else {
+                continue;
+            };
+            let bit = Alarms(1 << idx);
+            if self.status.contains(bit) {
+                if !threshold.latching && released(threshold, idx, value) {
+                    self.status.0 &= !bit.0;
+                }
+            } else if raised(threshold, idx, value) {
+                self.status |= bit;
+            }
+        }
+        self.status
+    }
+
+    pub const fn status(&self) -> Alarms {
+        self.status
+    }
+
+    /// Releases latched alarms whose signal has returned past the release
+    /// level, judged by the last value; the others stay latched.
+    pub fn acknowledge(&mut self) {
+        for (idx, threshold) in self.thresholds.iter().enumerate() {
+            if let Some(threshold) = threshold {
+                if released(threshold, idx, self.value) {
+                    self.status.0 &= !(1 << idx);
+                }
+            }
+        }
+    }
+
+    /// Clears every alarm, latched or not.
+    pub fn reset(&mut self) {
+        self.status = Alarms::NONE;
+    }
+
+    pub const fn low_warning(&self) -> Option<Threshold> {
+        self.thresholds[0]
+    }
+
+    pub const fn high_warning(&self) -> Option<Threshold> {
+        self.thresholds[1]
+    }
+
+    pub const fn low_trip(&self) -> Option<Threshold> {
+        self.thresholds[2]
+    }
+
+    pub const fn high_trip(&self) -> Option<Threshold> {
+        self.thresholds[3]
+    }
+
+    /// Changes or disables the low warning, clearing its alarm.
+    pub fn set_low_warning(&mut self, threshold: Option<Threshold>) {
+        self.set(0, threshold);
+    }
+
+    /// Changes or disables the high warning, clearing its alarm.
+    pub fn set_high_warning(&mut self, threshold: Option<Threshold>) {
+        self.set(1, threshold);
+    }
+
+    /// Changes or disables the low trip, clearing its alarm.
+    pub fn set_low_trip(&mut self, threshold: Option<Threshold>) {
+        self.set(2, threshold);
+    }
+
+    /// Changes or disables the high trip, clearing its alarm.
+    pub fn set_high_trip(&mut self, threshold: Option<Threshold>) {
+        self.set(3, threshold);
+    }
+
+    fn set(&mut self, idx: usize, threshold: Option<Threshold>) {
+        self.thresholds[idx] = threshold;
+        self.status.0 &= !(1 << idx);
+    }
+}
+
+/// How far `value` is past the level of a threshold, in the direction that
+/// raises its alarm; odd indices are high alarms.
+fn excess(threshold: &Threshold, idx: usize, value: DyadicFraction) -> Wide {
+    let mut excess = Wide::from_df(value);
+    excess.sub(Wide::from_df(threshold.level));
+    if idx % 2 == 1 {
+        excess
+    } else {
+        excess.neg()
+    }
+}
+
+fn raised(threshold: &Threshold, idx: usize, value: DyadicFraction) -> bool {
+    !excess(threshold, idx, value).is_negative()
+}
+
+fn released(threshold: &Threshold, idx: usize, value: DyadicFraction) -> bool {
+    let mut excess = excess(threshold, idx, value);
+    excess.add(Wide::from_df(threshold.hysteresis));
+    excess.is_negative() || excess.is_zero()
+}
+
+/// Debouncer for a boolean condition, such as an error above a threshold:
+/// the output follows only once the condition has held for a number of
+/// consecutive updates, counted separately for asserting and deasserting.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Debounce {
+    assert_ticks: u32,
+    deassert_ticks: u32,
+    count: u32,
+    state: bool,
+}
+
+impl Debounce {
+    /// Creates a deasserted debouncer that asserts after `assert_ticks`
+    /// consecutive updates with the condition set and deasserts after
+    /// `deassert_ticks` with it clear. Zero follows the condition at once.
+    pub const fn new(assert_ticks: u32, deassert_ticks: u32) -> Self {
+        Self {
+            assert_ticks,
+            deassert_ticks,
+            count: 0,
+            state: false,
+        }
+    }
+
+    /// Creates a debouncer updated every `dt` from hold times in the same
+    /// unit, rounded up to whole updates.
+    pub fn from_durations(
+        assert_time: DyadicFraction,
+        deassert_time: DyadicFraction,
+        dt: DyadicFraction,
+    ) -> Self {
+        Self::new(ticks(assert_time, dt), ticks(deassert_time, dt))
+    }
+
+    /// Feeds the current `condition` and returns the debounced state.
+    pub fn update(&mut self, condition: bool) -> bool {
+        if condition == self.state {
+            self.count = 0;
+            return self.state;
+        }
+        self.count = self.count.saturating_add(1);
+        let required = if condition {
+            self.assert_ticks
+        } else {
+            self.deassert_ticks
+        };
+        if self.count >= required {
+            self.state = condition;
+            self.count = 0;
+        }
+        self.state
+    }
+
+    pub const fn state(&self) -> bool {
+        self.state
+    }
+
+    /// Forces the output to `state` and restarts the count.
+    pub fn reset(&mut self, state: bool) {
+        self.state = state;
+        self.count = 0;
+    }
+
+    /// Hold times `(assert, deassert)` in updates.
+    pub const fn ticks(&self) -> (u32, u32) {
+        (self.assert_ticks, self.deassert_ticks)
+    }
+
+    /// Changes the hold times, keeping the state and the current count.
+    pub fn set_ticks(&mut self, assert_ticks: u32, deassert_ticks: u32) {
+        self.assert_ticks = assert_ticks;
+        self.deassert_ticks = deassert_ticks;
+    }
+}
+
+/// Whole updates of `dt` covering `time`, rounded up; zero for a
+/// non-positive `dt`.
+///
+/// The ratio is first rounded to nearest at a denominator of `2^16`, so a
+/// `dt` such as 0.1 that is only approximated still divides whole seconds
+/// evenly.
+fn ticks(time: DyadicFraction, dt: DyadicFraction) -> u32 {
+    if !dt.is_positive() {
+        return 0;
+    }
+    Wide::from_df(time)
+        .div(Wide::from_df(dt), 16)
+        .to_u32_clamped(Rounding::Ceil)
+}
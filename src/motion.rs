@@ -0,0 +1,386 @@
+//! Motion profiles producing per-tick position and velocity setpoints.
+//!
+//! Velocities are in position units per tick and accelerations in position
+//! units per tick squared.
+
+use crate::wide::Wide;
+use crate::{DyadicFraction, Rounding};
+
+/// Trapezoidal velocity profile limited by a maximum velocity and
+/// acceleration.
+///
+/// Each tick the velocity is steered towards the fastest speed that still
+/// allows stopping at the target, `sqrt(2 * acceleration * distance)`, so
+/// the target may be changed at any time, including mid-move.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct TrapezoidProfile {
+    max_velocity: DyadicFraction,
+    max_acceleration: DyadicFraction,
+    target: DyadicFraction,
+    position: DyadicFraction,
+    velocity: DyadicFraction,
+}
+
+impl TrapezoidProfile {
+    /// Creates a profile at rest at zero. Both limits are taken by magnitude.
+    pub const fn new(max_velocity: DyadicFraction, max_acceleration: DyadicFraction) -> Self {
+        Self {
+            max_velocity: max_velocity.abs(),
+            max_acceleration: max_acceleration.abs(),
+            target: DyadicFraction::zero(),
+            position: DyadicFraction::zero(),
+            velocity: DyadicFraction::zero(),
+        }
+    }
+
+    /// Advances one tick and returns the new `(position, velocity)`.
+    pub fn update(&mut self) -> (DyadicFraction, DyadicFraction) {
+        let accel = self.max_acceleration;
+        let distance = sub(self.target, self.position);
+        let reachable = Wide::product(accel, distance.abs())
+            .mul_df(DyadicFraction::from(2))
+            .sqrt(i8::MAX);
+        let cruise = DyadicFraction::min(self.max_velocity, reachable).copysign(distance.num);
+        let dv = DyadicFraction::max(
+            DyadicFraction::min(sub(cruise, self.velocity), accel),
+            accel.neg_rounded(Rounding::Nearest),
+        );
+        self.velocity = self.velocity.add_rounded(dv, Rounding::Nearest);
+
+        let stopping = self.velocity.abs() <= accel.add_rounded(accel, Rounding::Nearest);
+        let overshoots = self.velocity.abs() >= distance.abs()
+            && self.velocity.is_positive() == distance.is_positive();
+        if stopping && (distance.num == 0 || overshoots) {
+            self.position = self.target;
+            self.velocity = DyadicFraction::zero();
+        } else {
+            self.position = self.position.add_rounded(self.velocity, Rounding::Nearest);
+        }
+        (self.position, self.velocity)
+    }
+
+    pub const fn position(&self) -> DyadicFraction {
+        self.position
+    }
+
+    pub const fn velocity(&self) -> DyadicFraction {
+        self.velocity
+    }
+
+    pub const fn target(&self) -> DyadicFraction {
+        self.target
+    }
+
+    /// Sets a new target, approached from the current position and velocity.
+    pub fn set_target(&mut self, target: DyadicFraction) {
+        self.target = target;
+    }
+
+    /// Whether the profile rests at the target.
+    pub fn is_done(&self) -> bool {
+        self.position == self.target && self.velocity.num == 0
+    }
+
+    /// Stops at `position` and makes it the target.
+    pub fn reset(&mut self, position: DyadicFraction) {
+        self.position = position;
+        self.target = position;
+        self.velocity = DyadicFraction::zero();
+    }
+
+    pub fn set_limits(&mut self, max_velocity: DyadicFraction, max_acceleration: DyadicFraction) {
+        self.max_velocity = max_velocity.abs();
+        self.max_acceleration = max_acceleration.abs();
+    }
+}
+
+/// `a - b` rounded to nearest rather than saturating.
+fn sub(a: DyadicFraction, b: DyadicFraction) -> DyadicFraction {
+    a.add_rounded(b.neg_rounded(Rounding::Nearest), Rounding::Nearest)
+}
+
+/// Jerk-limited S-curve profile, a smoother alternative to
+/// [`TrapezoidProfile`] for resonance-prone mechanics.
+///
+/// Each move is planned from rest to rest as seven segments of whole ticks:
+/// jerk up, constant acceleration, jerk down, cruise and the mirror image.
+/// The jerk is then scaled down so the move ends exactly on the target, and
+/// setpoints are evaluated from exact tick counts, so rounding never
+/// accumulates. A target changed mid-move is approached once the current
+/// move has come to rest. Moves that would cruise for more than `2^30`
+/// ticks are not planned and the profile stays where it is.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct SCurveProfile {
+    max_velocity: DyadicFraction,
+    max_acceleration: DyadicFraction,
+    max_jerk: DyadicFraction,
+    target: DyadicFraction,
+    position: DyadicFraction,
+    velocity: DyadicFraction,
+    acceleration: DyadicFraction,
+    plan: Option<Plan>,
+}
+
+/// A rest-to-rest move in units of the planned jerk per tick cubed.
+#[derive(Copy, Clone, Debug)]
+struct Plan {
+    start: DyadicFraction,
+    distance: DyadicFraction,
+    /// Ticks of each jerk, constant acceleration and cruise segment.
+    jerk_ticks: u32,
+    accel_ticks: u32,
+    cruise_ticks: u32,
+    /// Travel of the whole move in units, `distance / jerk`.
+    scale: i128,
+    tick: u32,
+    accel: i128,
+    velocity: i128,
+    position: i128,
+}
+
+impl SCurveProfile {
+    /// Creates a profile at rest at zero. All limits are taken by magnitude
+    /// and must be non-zero for the profile to move.
+    pub const fn new(
+        max_velocity: DyadicFraction,
+        max_acceleration: DyadicFraction,
+        max_jerk: DyadicFraction,
+    ) -> Self {
+        Self {
+            max_velocity: max_velocity.abs(),
+            max_acceleration: max_acceleration.abs(),
+            max_jerk: max_jerk.abs(),
+            target: DyadicFraction::zero(),
+            position: DyadicFraction::zero(),
+            velocity: DyadicFraction::zero(),
+            acceleration: DyadicFraction::zero(),
+            plan: None,
+        }
+    }
+
+    /// Advances one tick and returns the new `(position, velocity)`.
+    pub fn update(&mut self) -> (DyadicFraction, DyadicFraction) {
+        if self.plan.is_none() && self.position != self.target {
+            self.plan = self.plan_move();
+        }
+        let Some(plan) = &mut self.plan else {
+            return (self.position, self.velocity);
+        };
+
+        let (jerk, accel, total) = (plan.jerk_ticks, plan.accel_ticks, plan.total());
+        let tick = plan.tick;
+        let cruise_start = 2 * jerk + accel;
+        let cruise_end = cruise_start + plan.cruise_ticks;
+        plan.accel += if tick < jerk || tick >= total - jerk {
+            1
+        } else if (jerk + accel..cruise_start).contains(&tick)
+            || (cruise_end..cruise_end + jerk).contains(&tick)
+        {
+            -1
+        } else {
+            0
+        };
+        plan.velocity += plan.accel;
+        plan.position += plan.velocity;
+        plan.tick += 1;
+
+        let plan = *plan;
+        self.position = plan.at(plan.start, plan.position);
+        self.velocity = plan.at(DyadicFraction::zero(), plan.velocity);
+        self.acceleration = plan.at(DyadicFraction::zero(), plan.accel);
+        if plan.tick == total {
+            self.plan = None;
+        }
+        (self.position, self.velocity)
+    }
+
+    pub const fn position(&self) -> DyadicFraction {
+        self.position
+    }
+
+    pub const fn velocity(&self) -> DyadicFraction {
+        self.velocity
+    }
+
+    pub const fn acceleration(&self) -> DyadicFraction {
+        self.acceleration
+    }
+
+    pub const fn target(&self) -> DyadicFraction {
+        self.target
+    }
+
+    /// Sets a new target, planned immediately when at rest and otherwise
+    /// once the current move has finished.
+    pub fn set_target(&mut self, target: DyadicFraction) {
+        self.target = target;
+    }
+
+    /// Whether the profile rests at the target.
+    pub fn is_done(&self) -> bool {
+        self.plan.is_none() && self.position == self.target
+    }
+
+    /// Stops at `position` and makes it the target.
+    pub fn reset(&mut self, position: DyadicFraction) {
+        *self = Self {
+            position,
+            target: position,
+            ..Self::new(self.max_velocity, self.max_acceleration, self.max_jerk)
+        };
+    }
+
+    /// Changes the limits, taking effect with the next move.
+    pub fn set_limits(
+        &mut self,
+        max_velocity: DyadicFraction,
+        max_acceleration: DyadicFraction,
+        max_jerk: DyadicFraction,
+    ) {
+        self.max_velocity = max_velocity.abs();
+        self.max_acceleration = max_acceleration.abs();
+        self.max_jerk = max_jerk.abs();
+    }
+
+    /// Plans the move from the current position to the target.
+    fn plan_move(&self) -> Option<Plan> {
+        let (vel, acc, jerk) = (self.max_velocity, self.max_acceleration, self.max_jerk);
+        if vel.num == 0 || acc.num == 0 || jerk.num == 0 {
+            return None;
+        }
+        let distance = sub(self.target, self.position);
+        let dist = distance.abs();
+
+        // Continuous segment durations for a profile whose travel is
+        // `jerk * tj * (tj + ta) * (2 tj + ta + tv)`, matching the discrete
+        // sums, limited by acceleration, velocity and distance in turn.
+        let two = DyadicFraction::from(2);
+        let cube = div(
+            div(dist, jerk.mul_rounded(two, Rounding::Nearest)).log2(40),
+            DyadicFraction::from(3),
+        );
+        let tj = DyadicFraction::min(
+            DyadicFraction::min(div(acc, jerk), div(vel, jerk).sqrt(i8::MAX)),
+            cube.exp2(i8::MAX),
+        );
+        let peak_acc = jerk.mul_rounded(tj, Rounding::Nearest);
+        let by_velocity = sub(div(vel, peak_acc), tj);
+        let radicand = tj.mul_rounded(tj, Rounding::Nearest).add_rounded(
+            div(
+                dist.mul_rounded(DyadicFraction::from(4), Rounding::Nearest),
+                peak_acc,
+            ),
+            Rounding::Nearest,
+        );
+        let by_distance = sub(
+            radicand.sqrt(i8::MAX),
+            tj.mul_rounded(DyadicFraction::from(3), Rounding::Nearest),
+        )
+        .div_by_two();
+        let ta = DyadicFraction::max(
+            DyadicFraction::min(by_velocity, by_distance),
+            DyadicFraction::zero(),
+        );
+        let reach = peak_acc.mul_rounded(tj.add_rounded(ta, Rounding::Nearest), Rounding::Nearest);
+        let tv = sub(
+            div(dist, reach),
+            tj.mul_rounded(two, Rounding::Nearest)
+                .add_rounded(ta, Rounding::Nearest),
+        );
+
+        let jerk_ticks = ceil_ticks(tj).max(1);
+        let accel_ticks = ceil_ticks(ta);
+        let mut plan = Plan {
+            start: self.position,
+            distance,
+            jerk_ticks,
+            accel_ticks,
+            cruise_ticks: ceil_ticks(tv),
+            scale: 0,
+            tick: 0,
+            accel: 0,
+            velocity: 0,
+            position: 0,
+        };
+        // Rounding of the estimates may leave the limits exceeded; the
+        // required jerk falls as the cruise grows.
+        let mut step = 1;
+        while !plan.fits(vel, acc, jerk) {
+            plan.cruise_ticks += step;
+            if plan.cruise_ticks > MAX_CRUISE_TICKS {
+                return None;
+            }
+            step *= 2;
+        }
+        while step > 1 {
+            step /= 2;
+            plan.cruise_ticks -= step;
+            if !plan.fits(vel, acc, jerk) {
+                plan.cruise_ticks += step;
+            }
+        }
+        plan.scale = plan.units();
+        Some(plan)
+    }
+}
+
+impl Plan {
+    fn total(&self) -> u32 {
+        4 * self.jerk_ticks + 2 * self.accel_ticks + self.cruise_ticks
+    }
+
+    /// Travel of the move in units of the jerk.
+    fn units(&self) -> i128 {
+        let (tj, ta, tv) = (
+            self.jerk_ticks as i128,
+            self.accel_ticks as i128,
+            self.cruise_ticks as i128,
+        );
+        tj * (tj + ta) * (2 * tj + ta + tv)
+    }
+
+    /// Whether the jerk, peak acceleration and peak velocity implied by the
+    /// segment durations stay within the limits.
+    fn fits(&self, vel: DyadicFraction, acc: DyadicFraction, jerk: DyadicFraction) -> bool {
+        let units = self.units();
+        let tj = self.jerk_ticks as i128;
+        let peak = tj * (tj + self.accel_ticks as i128);
+        [(jerk, 1), (acc, tj), (vel, peak)]
+            .iter()
+            .all(|&(limit, factor)| {
+                let mut margin = Wide::new(limit.num as i128 * units, limit.power as i32);
+                margin.sub(Wide::new(
+                    self.distance.abs().num as i128 * factor,
+                    self.distance.power as i32,
+                ));
+                !margin.is_negative()
+            })
+    }
+
+    /// `base + distance * units / scale`, rounded to nearest.
+    fn at(&self, base: DyadicFraction, units: i128) -> DyadicFraction {
+        let mut acc = Wide::new(base.num as i128 * self.scale, base.power as i32);
+        acc.add(Wide::new(
+            self.distance.num as i128 * units,
+            self.distance.power as i32,
+        ));
+        acc.div(Wide::new(self.scale, 0), i8::MAX)
+    }
+}
+
+/// `a / b` rounded to nearest at the finest power that fits.
+fn div(a: DyadicFraction, b: DyadicFraction) -> DyadicFraction {
+    Wide::from_df(a).div(Wide::from_df(b), i8::MAX)
+}
+
+/// Longest cruise a move may plan, keeping the tick counts within `u32` and
+/// the setpoint products within `i128`.
+const MAX_CRUISE_TICKS: u32 = 1 << 30;
+
+/// Smallest whole number of ticks covering `val`, zero when negative and
+/// capped at `2^20` to keep the travel in units within 64 bits.
+fn ceil_ticks(val: DyadicFraction) -> u32 {
+    let (int, frac) = val.to_int_and_frac();
+    let ticks = int + (frac.num != 0) as i64;
+    ticks.clamp(0, 1 << 20) as u32
+}
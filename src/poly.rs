@@ -0,0 +1,42 @@
+//! Polynomial evaluation for calibration curves and function approximation.
+
+use crate::wide::Wide;
+use crate::DyadicFraction;
+
+/// Evaluates `coeffs[0] + coeffs[1] * x + coeffs[2] * x^2 + ...` by Horner's
+/// scheme, returning zero for an empty slice.
+///
+/// Every step runs in a wide accumulator, so intermediate terms never
+/// saturate; the accumulator keeps at least 94 significant bits, and the
+/// result is narrowed once, rounding towards negative infinity.
+pub fn eval(coeffs: &[DyadicFraction], x: DyadicFraction) -> DyadicFraction {
+    let mut acc = Wide::default();
+    for &coeff in coeffs.iter().rev() {
+        acc = acc.mul_df(x);
+        acc.add(Wide::from_df(coeff));
+    }
+    acc.to_df()
+}
+
+/// A polynomial of degree below `N`, with coefficients in ascending order.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Polynomial<const N: usize> {
+    pub coeffs: [DyadicFraction; N],
+}
+
+impl<const N: usize> Polynomial<N> {
+    pub const fn new(coeffs: [DyadicFraction; N]) -> Self {
+        Self { coeffs }
+    }
+
+    /// Value at `x`, see [`eval`].
+    pub fn eval(&self, x: DyadicFraction) -> DyadicFraction {
+        eval(&self.coeffs, x)
+    }
+}
+
+impl<const N: usize> Default for Polynomial<N> {
+    fn default() -> Self {
+        Self::new([DyadicFraction::zero(); N])
+    }
+}
@@ -0,0 +1,86 @@
+//! Conversion between duty ratios and timer compare values.
+
+use crate::{shr_round, DyadicFraction, Rounding};
+
+/// Maps duty ratios in `[0, 1]` onto compare values of a timer counting up
+/// to `top`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Duty {
+    top: u16,
+    rounding: Rounding,
+}
+
+impl Duty {
+    pub const fn new(top: u16, rounding: Rounding) -> Self {
+        Self { top, rounding }
+    }
+
+    pub const fn top(&self) -> u16 {
+        self.top
+    }
+
+    pub const fn rounding(&self) -> Rounding {
+        self.rounding
+    }
+
+    /// Compare value for `ratio * top`, with the ratio clamped to `[0, 1]`.
+    pub fn compare(&self, ratio: DyadicFraction) -> u16 {
+        let ratio = ratio.canonical();
+        if !ratio.is_positive() {
+            return 0;
+        }
+        if ratio.power <= 0 {
+            return self.top;
+        }
+        let val = shr_round(
+            ratio.num as i128 * self.top as i128,
+            ratio.power as i32,
+            self.rounding,
+        );
+        val.min(self.top as i128) as u16
+    }
+
+    /// Duty ratio of a compare value, rounded to nearest at a denominator of
+    /// at most `2^max_power` and clamped to `[0, 1]`. A zero `top` yields
+    /// zero.
+    pub fn ratio(&self, compare: u16, max_power: i8) -> DyadicFraction {
+        if self.top == 0 {
+            return DyadicFraction::zero();
+        }
+        DyadicFraction::from_ratio(compare.min(self.top) as i32, self.top as i32, max_power)
+    }
+}
+
+/// Drives an `embedded-hal` PWM channel from duty ratios, such as a
+/// controller output, scaled to the channel's maximum duty cycle.
+#[cfg(feature = "embedded-hal")]
+#[derive(Debug)]
+pub struct DutyOutput<P> {
+    pwm: P,
+    rounding: Rounding,
+}
+
+#[cfg(feature = "embedded-hal")]
+impl<P: embedded_hal::pwm::SetDutyCycle> DutyOutput<P> {
+    pub const fn new(pwm: P, rounding: Rounding) -> Self {
+        Self { pwm, rounding }
+    }
+
+    /// Sets the duty cycle to `ratio` clamped to `[0, 1]`.
+    pub fn set(&mut self, ratio: DyadicFraction) -> Result<(), P::Error> {
+        let duty = Duty::new(self.pwm.max_duty_cycle(), self.rounding).compare(ratio);
+        self.pwm.set_duty_cycle(duty)
+    }
+
+    pub fn inner(&self) -> &P {
+        &self.pwm
+    }
+
+    pub fn inner_mut(&mut self) -> &mut P {
+        &mut self.pwm
+    }
+
+    pub fn release(self) -> P {
+        self.pwm
+    }
+}
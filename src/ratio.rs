@@ -0,0 +1,84 @@
+use crate::DyadicFraction;
+use core::fmt;
+
+/// An exact ratio of two integers, for intermediate results that are not
+/// dyadic, such as a 1/3 gear ratio.
+///
+/// The fraction is stored as given and equality is structural; use
+/// [`Ratio::reduced`] to compare values. Round to a dyadic fraction once at
+/// the end with [`Ratio::to_df`] or [`Ratio::mul_df`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Ratio {
+    pub num: i32,
+    pub den: i32,
+}
+
+impl Ratio {
+    pub const fn new(num: i32, den: i32) -> Self {
+        Self { num, den }
+    }
+
+    /// Lowest terms with a positive denominator, or `self` unchanged when
+    /// that form does not fit into `i32` or the denominator is zero.
+    pub const fn reduced(self) -> Self {
+        match Self::from_wide(self.num as i128, self.den as i128) {
+            Some(val) => val,
+            None => self,
+        }
+    }
+
+    /// Closest dyadic approximation with a denominator of at most
+    /// `2^max_power`, rounding half away from zero.
+    pub const fn to_df(self, max_power: i8) -> DyadicFraction {
+        DyadicFraction::from_ratio(self.num, self.den, max_power)
+    }
+
+    /// Computes `val * num / den` with a single rounding to nearest at a
+    /// denominator of at most `2^max_power`.
+    pub const fn mul_df(self, val: DyadicFraction, max_power: i8) -> DyadicFraction {
+        DyadicFraction::round_ratio(
+            val.num as i128 * self.num as i128,
+            self.den as i128,
+            val.power as i32,
+            max_power,
+        )
+    }
+
+    /// Reduces `num / den` to lowest terms with a positive denominator.
+    pub(crate) const fn from_wide(num: i128, den: i128) -> Option<Self> {
+        if den == 0 {
+            return None;
+        }
+        let div = gcd(num.unsigned_abs(), den.unsigned_abs()) as i128;
+        let (num, den) = if den < 0 {
+            (-num / div, -den / div)
+        } else {
+            (num / div, den / div)
+        };
+        if num < i32::MIN as i128 || num > i32::MAX as i128 || den > i32::MAX as i128 {
+            return None;
+        }
+        Some(Self::new(num as i32, den as i32))
+    }
+}
+
+impl From<i32> for Ratio {
+    fn from(num: i32) -> Self {
+        Self::new(num, 1)
+    }
+}
+
+impl fmt::Display for Ratio {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.num, self.den)
+    }
+}
+
+const fn gcd(mut a: u128, mut b: u128) -> u128 {
+    while b != 0 {
+        let rem = a % b;
+        a = b;
+        b = rem;
+    }
+    a
+}
@@ -0,0 +1,405 @@
+use crate::{BufferTooSmall, DyadicFraction};
+use core::fmt;
+
+/// Byte-oriented output shared by the `core::fmt` impls and the
+/// allocation-free buffer writers.
+pub(crate) trait Sink {
+    type Error;
+
+    fn put(&mut self, byte: u8) -> Result<(), Self::Error>;
+
+    fn put_str(&mut self, bytes: &[u8]) -> Result<(), Self::Error> {
+        for &byte in bytes {
+            self.put(byte)?;
+        }
+        Ok(())
+    }
+
+    fn put_uint(&mut self, mut val: u128) -> Result<(), Self::Error> {
+        let mut digits = [0u8; 39];
+        let mut len = 0;
+        loop {
+            digits[len] = b'0' + (val % 10) as u8;
+            val /= 10;
+            len += 1;
+            if val == 0 {
+                break;
+            }
+        }
+        digits[..len].reverse();
+        self.put_str(&digits[..len])
+    }
+
+    fn put_int(&mut self, val: i128) -> Result<(), Self::Error> {
+        if val < 0 {
+            self.put(b'-')?;
+        }
+        self.put_uint(val.unsigned_abs())
+    }
+}
+
+impl Sink for fmt::Formatter<'_> {
+    type Error = fmt::Error;
+
+    fn put(&mut self, byte: u8) -> fmt::Result {
+        fmt::Write::write_char(self, byte as char)
+    }
+}
+
+/// Writes into a byte slice, failing once it is full.
+pub(crate) struct Cursor<'a> {
+    buf: &'a mut [u8],
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    pub fn new(buf: &'a mut [u8]) -> Self {
+        Self { buf, pos: 0 }
+    }
+
+    pub fn finish(self) -> &'a str {
+        core::str::from_utf8(&self.buf[..self.pos]).unwrap_or("")
+    }
+}
+
+impl Sink for Cursor<'_> {
+    type Error = BufferTooSmall;
+
+    fn put(&mut self, byte: u8) -> Result<(), BufferTooSmall> {
+        let slot = self.buf.get_mut(self.pos).ok_or(BufferTooSmall)?;
+        *slot = byte;
+        self.pos += 1;
+        Ok(())
+    }
+}
+
+/// Writes the `num/den` form, or a plain integer for whole values.
+pub(crate) fn write_fraction<S: Sink>(val: DyadicFraction, out: &mut S) -> Result<(), S::Error> {
+    if val.is_negative() {
+        out.put(b'-')?;
+    }
+    write_fraction_magnitude(val, out)
+}
+
+/// Writes the `num/den` form of `|val|`.
+fn write_fraction_magnitude<S: Sink>(val: DyadicFraction, out: &mut S) -> Result<(), S::Error> {
+    let val = val.canonical();
+    let mag = val.numerator().unsigned_abs();
+    let power = val.denominator_power() as i32;
+    if power <= 0 {
+        let mut digits = [0u8; INT_DIGITS];
+        let len = shifted_digits(mag, -power as u32, &mut digits);
+        if len == 0 {
+            return out.put(b'0');
+        }
+        for &digit in &digits[..len] {
+            out.put(b'0' + digit)?;
+        }
+        Ok(())
+    } else {
+        out.put_uint(mag as u128)?;
+        out.put(b'/')?;
+        out.put_uint(1 << power)
+    }
+}
+
+/// Writes a form that `FromStr` reads back exactly: `num/den`, `num/2^power`
+/// beyond 64-bit denominators, or a plain integer. Whole values have to stay
+/// below `2^120`, the largest the parser accepts.
+#[cfg(feature = "serde")]
+pub(crate) fn write_exact<S: Sink>(val: DyadicFraction, out: &mut S) -> Result<(), S::Error> {
+    let val = val.canonical();
+    let power = val.denominator_power();
+    if power <= 0 {
+        return out.put_int((val.numerator() as i128) << -power);
+    }
+    out.put_int(val.numerator() as i128)?;
+    if power > 64 {
+        out.put_str(b"/2^")?;
+        out.put_uint(power as u128)
+    } else {
+        out.put(b'/')?;
+        out.put_uint(1 << power)
+    }
+}
+
+/// Unsigned rendering of a value, measured before it is padded.
+pub(crate) enum Body<'a> {
+    Fraction(DyadicFraction),
+    Decimal(&'a Decimal, Option<usize>),
+    Exp(&'a Decimal, Option<usize>, u8),
+}
+
+impl Body<'_> {
+    fn write<S: Sink>(&self, out: &mut S) -> Result<(), S::Error> {
+        match *self {
+            Body::Fraction(val) => write_fraction_magnitude(val, out),
+            Body::Decimal(dec, precision) => dec.write_digits(out, precision),
+            Body::Exp(dec, precision, marker) => dec.write_exp(out, precision, marker),
+        }
+    }
+}
+
+/// Counts the bytes of a rendering.
+struct Counter(usize);
+
+impl Sink for Counter {
+    type Error = core::convert::Infallible;
+
+    fn put(&mut self, _: u8) -> Result<(), Self::Error> {
+        self.0 += 1;
+        Ok(())
+    }
+}
+
+/// Writes the sign and `body` honoring the width, fill, alignment, `+` and
+/// `0` flags of `f`, the way the integer impls of `core` do.
+pub(crate) fn pad(f: &mut fmt::Formatter<'_>, negative: bool, body: Body) -> fmt::Result {
+    let sign: &[u8] = if negative {
+        b"-"
+    } else if f.sign_plus() {
+        b"+"
+    } else {
+        b""
+    };
+    let mut counter = Counter(sign.len());
+    let Ok(()) = body.write(&mut counter);
+    let padding = f.width().unwrap_or(0).saturating_sub(counter.0);
+    if f.sign_aware_zero_pad() {
+        f.put_str(sign)?;
+        for _ in 0..padding {
+            f.put(b'0')?;
+        }
+        return body.write(f);
+    }
+    let (before, after) = match f.align() {
+        Some(fmt::Alignment::Left) => (0, padding),
+        Some(fmt::Alignment::Center) => (padding / 2, padding - padding / 2),
+        _ => (padding, 0),
+    };
+    let fill = f.fill();
+    for _ in 0..before {
+        fmt::Write::write_char(f, fill)?;
+    }
+    f.put_str(sign)?;
+    body.write(f)?;
+    for _ in 0..after {
+        fmt::Write::write_char(f, fill)?;
+    }
+    Ok(())
+}
+
+/// Decimal digits of the largest integer part, `2^31 * 2^128`.
+const INT_DIGITS: usize = 48;
+
+/// Decimal digits of `mag * 2^shift`, most significant first, returning
+/// their count; zero has no digits.
+fn shifted_digits(mag: u32, shift: u32, digits: &mut [u8; INT_DIGITS]) -> usize {
+    // Little-endian 32-bit limbs, wide enough for 31 + 128 bits.
+    let mut limbs = [0u32; 5];
+    let wide = (mag as u64) << (shift % 32);
+    let low = (shift / 32) as usize;
+    limbs[low] = wide as u32;
+    if low + 1 < limbs.len() {
+        limbs[low + 1] = (wide >> 32) as u32;
+    }
+
+    let mut len = 0;
+    while limbs.iter().any(|&limb| limb != 0) {
+        let mut rem = 0u64;
+        for limb in limbs.iter_mut().rev() {
+            let cur = (rem << 32) | *limb as u64;
+            *limb = (cur / 10) as u32;
+            rem = cur % 10;
+        }
+        digits[len] = rem as u8;
+        len += 1;
+    }
+    digits[..len].reverse();
+    len
+}
+
+/// Exact decimal expansion of a dyadic fraction.
+///
+/// A denominator of `2^power` never needs more than `power` fractional digits,
+/// so the expansion always fits into a fixed buffer.
+pub(crate) struct Decimal {
+    pub negative: bool,
+    pub int: [u8; INT_DIGITS],
+    pub int_len: usize,
+    pub frac: [u8; 128],
+    pub len: usize,
+}
+
+impl Decimal {
+    pub fn new(val: DyadicFraction) -> Self {
+        let val = val.canonical();
+        let mag = val.numerator().unsigned_abs();
+        let mut power = val.denominator_power() as i32;
+        let mut res = Self {
+            negative: val.is_negative(),
+            int: [0; INT_DIGITS],
+            int_len: 0,
+            frac: [0; 128],
+            len: 0,
+        };
+        if power <= 0 {
+            res.int_len = shifted_digits(mag, -power as u32, &mut res.int);
+        } else {
+            res.int_len = shifted_digits(mag >> power.min(31), 0, &mut res.int);
+            // Multiplying by 5 and halving the denominator yields a digit per
+            // step; the remainder is below both `2^power` and `mag * 5^k`, so
+            // the product never overflows.
+            let mut rem = mag as u128 & ((1 << power) - 1);
+            while rem != 0 {
+                rem *= 5;
+                power -= 1;
+                res.frac[res.len] = (rem >> power) as u8;
+                rem &= (1 << power) - 1;
+                res.len += 1;
+            }
+        }
+        res
+    }
+
+    /// Rounds half away from zero to `precision` fractional digits.
+    pub fn round(&mut self, precision: usize) {
+        if precision >= self.len {
+            return;
+        }
+        let round_up = self.frac[precision] >= 5;
+        self.len = precision;
+        if !round_up {
+            return;
+        }
+        let mut idx = precision;
+        loop {
+            if idx == 0 {
+                self.increment_int();
+                return;
+            }
+            idx -= 1;
+            if self.frac[idx] == 9 {
+                self.frac[idx] = 0;
+            } else {
+                self.frac[idx] += 1;
+                return;
+            }
+        }
+    }
+
+    fn increment_int(&mut self) {
+        for digit in self.int[..self.int_len].iter_mut().rev() {
+            if *digit == 9 {
+                *digit = 0;
+            } else {
+                *digit += 1;
+                return;
+            }
+        }
+        self.int.copy_within(..self.int_len, 1);
+        self.int[0] = 1;
+        self.int_len += 1;
+    }
+
+    /// Writes the expansion, zero-padded to `precision` fractional digits
+    /// when given.
+    pub fn write<S: Sink>(&self, out: &mut S, precision: Option<usize>) -> Result<(), S::Error> {
+        if self.negative {
+            out.put(b'-')?;
+        }
+        self.write_digits(out, precision)
+    }
+
+    /// Writes the expansion without its sign.
+    fn write_digits<S: Sink>(&self, out: &mut S, precision: Option<usize>) -> Result<(), S::Error> {
+        if self.int_len == 0 {
+            out.put(b'0')?;
+        }
+        for &digit in &self.int[..self.int_len] {
+            out.put(b'0' + digit)?;
+        }
+        let digits = precision.unwrap_or(self.len);
+        if digits > 0 {
+            out.put(b'.')?;
+        }
+        for idx in 0..digits {
+            let digit = if idx < self.len { self.frac[idx] } else { 0 };
+            out.put(b'0' + digit)?;
+        }
+        Ok(())
+    }
+
+    /// Writes the magnitude in decimal scientific notation with `marker`
+    /// separating the exponent, rounding half away from zero to `precision`
+    /// fractional mantissa digits when given.
+    pub fn write_exp<S: Sink>(
+        &self,
+        out: &mut S,
+        precision: Option<usize>,
+        marker: u8,
+    ) -> Result<(), S::Error> {
+        let mut digits = [0u8; INT_DIGITS + 128];
+        let int_len = self.int_len;
+        digits[..int_len].copy_from_slice(&self.int[..int_len]);
+        let mut len = int_len;
+        digits[len..len + self.len].copy_from_slice(&self.frac[..self.len]);
+        len += self.len;
+
+        let start = match digits[..len].iter().position(|&d| d != 0) {
+            Some(start) => start,
+            None => {
+                out.put(b'0')?;
+                if let Some(precision) = precision.filter(|&p| p > 0) {
+                    out.put(b'.')?;
+                    for _ in 0..precision {
+                        out.put(b'0')?;
+                    }
+                }
+                out.put(marker)?;
+                return out.put(b'0');
+            }
+        };
+        let mut exp = int_len as i32 - 1 - start as i32;
+        let mut end = len;
+        if let Some(precision) = precision {
+            if end - start > precision + 1 {
+                end = start + precision + 1;
+                if digits[end] >= 5 {
+                    let mut idx = end;
+                    loop {
+                        if idx == start {
+                            digits[start] = 1;
+                            exp += 1;
+                            break;
+                        }
+                        idx -= 1;
+                        if digits[idx] == 9 {
+                            digits[idx] = 0;
+                        } else {
+                            digits[idx] += 1;
+                            break;
+                        }
+                    }
+                }
+            }
+        } else {
+            while end > start + 1 && digits[end - 1] == 0 {
+                end -= 1;
+            }
+        }
+
+        out.put(b'0' + digits[start])?;
+        let fraction = precision.unwrap_or(end - start - 1);
+        if fraction > 0 {
+            out.put(b'.')?;
+        }
+        for idx in 0..fraction {
+            let pos = start + 1 + idx;
+            let digit = if pos < end { digits[pos] } else { 0 };
+            out.put(b'0' + digit)?;
+        }
+        out.put(marker)?;
+        out.put_int(exp as i128)
+    }
+}
@@ -0,0 +1,39 @@
+use crate::DyadicFraction;
+
+/// Fixed-capacity buffer of the last `N` samples.
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct Ring<const N: usize> {
+    buf: [DyadicFraction; N],
+    head: usize,
+    len: usize,
+}
+
+impl<const N: usize> Ring<N> {
+    pub const fn new() -> Self {
+        Self {
+            buf: [DyadicFraction::zero(); N],
+            head: 0,
+            len: 0,
+        }
+    }
+
+    /// Appends `sample`, returning the oldest sample once the buffer is full.
+    pub fn push(&mut self, sample: DyadicFraction) -> Option<DyadicFraction> {
+        if N == 0 {
+            return Some(sample);
+        }
+        let evicted = if self.len == N {
+            Some(self.buf[self.head])
+        } else {
+            self.len += 1;
+            None
+        };
+        self.buf[self.head] = sample;
+        self.head = (self.head + 1) % N;
+        evicted
+    }
+
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+}
@@ -0,0 +1,89 @@
+//! Plant models for closed-loop simulation of controllers on the host.
+
+use crate::wide::Wide;
+use crate::{DyadicFraction, Rounding};
+
+/// First-order lag with dead time, `G(s) = gain e^(-DELAY dt s) / (tau s + 1)`.
+///
+/// The lag is discretized exactly for inputs held over each time step, so
+/// the output moves by `1 - e^(-dt / tau)` of the remaining distance per
+/// update. The transport delay is a whole number of `DELAY` updates. The
+/// update is computed exactly and rounded to nearest once.
+#[derive(Copy, Clone, Debug)]
+pub struct FirstOrderPlant<const DELAY: usize = 0> {
+    gain: DyadicFraction,
+    tau: DyadicFraction,
+    dt: DyadicFraction,
+    alpha: DyadicFraction,
+    delay: [DyadicFraction; DELAY],
+    head: usize,
+    output: DyadicFraction,
+}
+
+impl<const DELAY: usize> FirstOrderPlant<DELAY> {
+    /// Creates a plant at rest with static `gain`, time constant `tau` and
+    /// time step `dt`, in the same unit. A zero `tau` gives a pure gain.
+    pub fn new(gain: DyadicFraction, tau: DyadicFraction, dt: DyadicFraction) -> Self {
+        Self {
+            gain,
+            tau,
+            dt,
+            alpha: step_fraction(tau, dt),
+            delay: [DyadicFraction::zero(); DELAY],
+            head: 0,
+            output: DyadicFraction::zero(),
+        }
+    }
+
+    /// Applies `input` for one time step and returns the new output.
+    pub fn update(&mut self, input: DyadicFraction) -> DyadicFraction {
+        let input = match self.delay.get_mut(self.head) {
+            Some(slot) => {
+                self.head = (self.head + 1) % DELAY;
+                core::mem::replace(slot, input)
+            }
+            None => input,
+        };
+        let mut acc = Wide::from_df(self.output);
+        acc.add(Wide::product(self.gain, input).mul_df(self.alpha));
+        acc.sub_product(self.alpha, self.output);
+        self.output = acc.round(Rounding::Nearest);
+        self.output
+    }
+
+    pub const fn value(&self) -> DyadicFraction {
+        self.output
+    }
+
+    /// Returns the plant to rest with a zero input history.
+    pub fn reset(&mut self) {
+        *self = Self::new(self.gain, self.tau, self.dt);
+    }
+
+    pub const fn gain(&self) -> DyadicFraction {
+        self.gain
+    }
+
+    pub const fn tau(&self) -> DyadicFraction {
+        self.tau
+    }
+
+    pub const fn dt(&self) -> DyadicFraction {
+        self.dt
+    }
+
+    /// Transport delay in time steps.
+    pub const fn delay(&self) -> usize {
+        DELAY
+    }
+}
+
+/// `1 - e^(-dt / tau)`, rounded to nearest at the finest power that fits.
+fn step_fraction(tau: DyadicFraction, dt: DyadicFraction) -> DyadicFraction {
+    if !tau.is_positive() {
+        return DyadicFraction::from(1);
+    }
+    let ratio = Wide::from_df(dt).div(Wide::from_df(tau), i8::MAX);
+    let decay = (-ratio).exp(i8::MAX);
+    DyadicFraction::from(1).add_rounded(decay.neg_rounded(Rounding::Nearest), Rounding::Nearest)
+}
@@ -0,0 +1,188 @@
+//! Streaming statistics over dyadic fraction samples.
+
+use crate::ring::Ring;
+use crate::wide::Wide;
+use crate::{DyadicFraction, Rounding};
+
+/// Running mean, variance and range using Welford's algorithm.
+///
+/// The sum and the sum of squared deviations are accumulated exactly; the
+/// mean is rounded to nearest at the finest power that fits into `i32`.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Running {
+    count: u32,
+    sum: Wide,
+    mean: DyadicFraction,
+    m2: Wide,
+    min: DyadicFraction,
+    max: DyadicFraction,
+}
+
+impl Running {
+    pub const fn new() -> Self {
+        Self {
+            count: 0,
+            sum: Wide::new(0, 0),
+            mean: DyadicFraction::zero(),
+            m2: Wide::new(0, 0),
+            min: DyadicFraction::zero(),
+            max: DyadicFraction::zero(),
+        }
+    }
+
+    pub fn push(&mut self, sample: DyadicFraction) {
+        if self.count == 0 {
+            self.min = sample;
+            self.max = sample;
+        } else {
+            self.min = DyadicFraction::min(self.min, sample);
+            self.max = DyadicFraction::max(self.max, sample);
+        }
+        self.count = self.count.saturating_add(1);
+
+        let prev = self.mean;
+        self.sum.add(Wide::from_df(sample));
+        self.mean = self.sum.average(self.count as usize);
+        self.m2
+            .add_product(deviation(sample, prev), deviation(sample, self.mean));
+    }
+
+    pub const fn count(&self) -> u32 {
+        self.count
+    }
+
+    /// Arithmetic mean, zero before the first sample.
+    pub const fn mean(&self) -> DyadicFraction {
+        self.mean
+    }
+
+    /// Population variance, zero before the first sample.
+    pub fn variance(&self) -> DyadicFraction {
+        self.m2.average(self.count as usize)
+    }
+
+    /// Unbiased sample variance, zero before the second sample.
+    pub fn sample_variance(&self) -> DyadicFraction {
+        self.m2.average(self.count.saturating_sub(1) as usize)
+    }
+
+    pub const fn min(&self) -> Option<DyadicFraction> {
+        if self.count == 0 {
+            None
+        } else {
+            Some(self.min)
+        }
+    }
+
+    pub const fn max(&self) -> Option<DyadicFraction> {
+        if self.count == 0 {
+            None
+        } else {
+            Some(self.max)
+        }
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+}
+
+/// `sample - mean` rounded to nearest instead of saturating.
+fn deviation(sample: DyadicFraction, mean: DyadicFraction) -> DyadicFraction {
+    sample.add_rounded(mean.neg_rounded(Rounding::Nearest), Rounding::Nearest)
+}
+
+/// Root mean square of all samples since the last reset.
+///
+/// Squares are accumulated exactly and rounded once when read.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Rms {
+    count: u32,
+    sum_sqr: Wide,
+}
+
+impl Rms {
+    pub const fn new() -> Self {
+        Self {
+            count: 0,
+            sum_sqr: Wide::new(0, 0),
+        }
+    }
+
+    pub fn push(&mut self, sample: DyadicFraction) {
+        self.count = self.count.saturating_add(1);
+        self.sum_sqr.add_product(sample, sample);
+    }
+
+    pub const fn count(&self) -> u32 {
+        self.count
+    }
+
+    /// Mean of the squared samples, zero before the first sample.
+    pub fn mean_square(&self) -> DyadicFraction {
+        self.sum_sqr.average(self.count as usize)
+    }
+
+    /// Root mean square rounded down to a denominator of at most
+    /// `2^max_power`.
+    pub fn rms(&self, max_power: i8) -> DyadicFraction {
+        self.mean_square().sqrt(max_power)
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+}
+
+/// Root mean square over the last `N` samples.
+#[derive(Copy, Clone, Debug)]
+pub struct SlidingRms<const N: usize> {
+    window: Ring<N>,
+    sum_sqr: Wide,
+}
+
+impl<const N: usize> SlidingRms<N> {
+    pub const fn new() -> Self {
+        Self {
+            window: Ring::new(),
+            sum_sqr: Wide::new(0, 0),
+        }
+    }
+
+    pub fn push(&mut self, sample: DyadicFraction) {
+        self.sum_sqr.add_product(sample, sample);
+        if let Some(oldest) = self.window.push(sample) {
+            self.sum_sqr.sub_product(oldest, oldest);
+        }
+    }
+
+    /// Number of samples in the window, at most `N`.
+    pub const fn len(&self) -> usize {
+        self.window.len()
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.window.len() == 0
+    }
+
+    /// Mean of the squared samples in the window, zero when empty.
+    pub fn mean_square(&self) -> DyadicFraction {
+        self.sum_sqr.average(self.window.len())
+    }
+
+    /// Root mean square rounded down to a denominator of at most
+    /// `2^max_power`.
+    pub fn rms(&self, max_power: i8) -> DyadicFraction {
+        self.mean_square().sqrt(max_power)
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+}
+
+impl<const N: usize> Default for SlidingRms<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,53 @@
+//! Lookup tables generated at compile time.
+//!
+//! Every generator is a `const fn`, so a table declared as a constant or
+//! static is computed by the compiler and stored in flash with no startup
+//! cost. Entries come from the same code as the runtime functions, and the
+//! tables fit [`Lut1d::uniform`](crate::interp::Lut1d::uniform), which is
+//! also `const`.
+
+use crate::trig::{self, TAU};
+use crate::wide::Wide;
+use crate::{shr_round, DyadicFraction, Rounding};
+
+/// Quarter sine wave, `sin(i * π / (2 (N - 1)))` for `i` in `0..N`, rising
+/// from zero to one. A table of one entry holds zero.
+///
+/// Entries are computed by the CORDIC rotation behind [`trig::sin`] and
+/// rounded to nearest at a denominator of at most `2^max_power`.
+pub const fn sine_quarter<const N: usize>(max_power: i8) -> [DyadicFraction; N] {
+    let mut table = [DyadicFraction::zero(); N];
+    let mut i = 1;
+    while i < N {
+        // Radians with 60 fractional bits, narrowed to the 32 of the rotation.
+        let angle = TAU * i as i128 / (4 * (N as i128 - 1));
+        table[i] = trig::rotate(shr_round(angle, 28, Rounding::Nearest) as i64, max_power).0;
+        i += 1;
+    }
+    table
+}
+
+/// Exponential segments, `e^(start + i * step)` for `i` in `0..N`, for a
+/// table built with `Lut1d::uniform(start, step, ..)`.
+///
+/// Each entry equals [`DyadicFraction::exp`] of its exact breakpoint.
+pub const fn exp_segments<const N: usize>(
+    start: DyadicFraction,
+    step: DyadicFraction,
+    max_power: i8,
+) -> [DyadicFraction; N] {
+    let mut table = [DyadicFraction::zero(); N];
+    let mut i = 0;
+    while i < N {
+        table[i] = point(start, step, i).exp(max_power);
+        i += 1;
+    }
+    table
+}
+
+/// Breakpoint `start + i * step`, rounded to nearest if it does not fit.
+const fn point(start: DyadicFraction, step: DyadicFraction, i: usize) -> DyadicFraction {
+    let mut acc = Wide::from_df(start);
+    acc.add(Wide::new(step.num as i128 * i as i128, step.power as i32));
+    acc.round(Rounding::Nearest)
+}
@@ -0,0 +1,106 @@
+//! Conversions between durations in seconds and timer ticks.
+//!
+//! Clock periods such as `1 / 72 MHz` have no exact dyadic representation,
+//! so converting through a precomputed period accumulates its error with
+//! every tick. These conversions multiply or divide by the clock frequency
+//! exactly and round once.
+
+use crate::wide::Wide;
+use crate::{shr_round, DyadicFraction, Rounding};
+
+/// Ticks of a `clock_hz` timer in `secs` seconds, rounded in the given
+/// direction. Negative durations give zero and results beyond `u64`
+/// saturate.
+pub const fn secs_to_ticks(secs: DyadicFraction, clock_hz: u32, rounding: Rounding) -> u64 {
+    let product = secs.num as i128 * clock_hz as i128;
+    to_ticks(product, secs.power as i32, rounding)
+}
+
+/// Duration of `ticks` of a `clock_hz` timer in seconds, rounded to nearest
+/// at a denominator of at most `2^max_power`. A zero clock saturates.
+pub const fn ticks_to_secs(ticks: u64, clock_hz: u32, max_power: i8) -> DyadicFraction {
+    DyadicFraction::round_ratio(ticks as i128, clock_hz as i128, 0, max_power)
+}
+
+/// A frequency in hertz.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Frequency(DyadicFraction);
+
+impl Frequency {
+    pub const fn new(hz: DyadicFraction) -> Self {
+        Self(hz)
+    }
+
+    pub const fn hz(self) -> DyadicFraction {
+        self.0
+    }
+
+    /// The period `1 / self`, rounded to nearest at a denominator of at most
+    /// `2^max_power`. Exact when the frequency is a power of two; a zero
+    /// frequency saturates.
+    pub const fn period(self, max_power: i8) -> Period {
+        Period(reciprocal(self.0, max_power))
+    }
+
+    /// Whole cycles in `secs` seconds, rounded in the given direction.
+    /// Negative counts give zero and results beyond `u64` saturate.
+    pub const fn cycles(self, secs: DyadicFraction, rounding: Rounding) -> u64 {
+        let product = secs.num as i128 * self.0.num as i128;
+        to_ticks(product, secs.power as i32 + self.0.power as i32, rounding)
+    }
+}
+
+/// A period in seconds.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Period(DyadicFraction);
+
+impl Period {
+    pub const fn new(secs: DyadicFraction) -> Self {
+        Self(secs)
+    }
+
+    pub const fn secs(self) -> DyadicFraction {
+        self.0
+    }
+
+    /// The frequency `1 / self`, rounded to nearest at a denominator of at
+    /// most `2^max_power`. Exact when the period is a power of two; a zero
+    /// period saturates.
+    pub const fn frequency(self, max_power: i8) -> Frequency {
+        Frequency(reciprocal(self.0, max_power))
+    }
+
+    /// Duration of `count` periods in seconds, rounded to nearest at the
+    /// finest power that fits.
+    pub const fn duration(self, count: u32) -> DyadicFraction {
+        DyadicFraction::narrow(
+            self.0.num as i128 * count as i128,
+            self.0.power as i32,
+            Rounding::Nearest,
+        )
+    }
+}
+
+const fn reciprocal(val: DyadicFraction, max_power: i8) -> DyadicFraction {
+    Wide::new(1, 0).div(Wide::from_df(val), max_power)
+}
+
+/// Rounds `num / 2^power` to a tick count, clamped to the range of `u64`.
+const fn to_ticks(num: i128, power: i32, rounding: Rounding) -> u64 {
+    let ticks = if power >= 0 {
+        shr_round(num, power, rounding)
+    } else if power >= -63 && num.unsigned_abs() < 1 << 64 {
+        num << -power
+    } else if num > 0 {
+        i128::MAX
+    } else {
+        0
+    };
+    if ticks < 0 {
+        0
+    } else if ticks > u64::MAX as i128 {
+        u64::MAX
+    } else {
+        ticks as u64
+    }
+}
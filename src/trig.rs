@@ -0,0 +1,300 @@
+//! Trigonometry by CORDIC.
+//!
+//! Precision is selected like elsewhere in the crate: results are rounded
+//! to nearest at a denominator of at most `2^max_power`, and the number of
+//! CORDIC iterations follows from it, capped at 32. The error stays within
+//! a few units of `2^-max_power` for `max_power` up to about 30.
+
+use crate::{shr_round, DyadicFraction, Rounding};
+use core::ops::*;
+
+/// `atan(2^-i)` in radians with 32 fractional bits.
+const ATAN: [i64; 32] = [
+    3373259426, 1991351318, 1052175346, 534100635, 268086748, 134174063, 67103403, 33553749,
+    16777131, 8388597, 4194303, 2097152, 1048576, 524288, 262144, 131072, 65536, 32768, 16384,
+    8192, 4096, 2048, 1024, 512, 256, 128, 64, 32, 16, 8, 4, 2,
+];
+
+/// CORDIC gain compensation, the product of `1 / sqrt(1 + 2^-2i)`, with 32
+/// fractional bits.
+const GAIN: i64 = 2608131496;
+
+/// `π` with 32 fractional bits.
+const PI: i64 = 13493037705;
+
+/// `τ` with 60 fractional bits.
+pub(crate) const TAU: i128 = 7244019458077122842;
+
+/// Sine of `angle` in radians.
+pub fn sin(angle: DyadicFraction, max_power: i8) -> DyadicFraction {
+    sin_cos(angle, max_power).0
+}
+
+/// Cosine of `angle` in radians.
+pub fn cos(angle: DyadicFraction, max_power: i8) -> DyadicFraction {
+    sin_cos(angle, max_power).1
+}
+
+/// Sine and cosine of `angle` in radians, computed together.
+///
+/// The angle is reduced modulo `τ` with 60 fractional bits, which stays
+/// accurate for magnitudes well beyond `2^20` and saturates at `2^66`.
+pub fn sin_cos(angle: DyadicFraction, max_power: i8) -> (DyadicFraction, DyadicFraction) {
+    let reduced = angle.scaled(60, Rounding::Nearest).rem_euclid(TAU);
+    let mut angle = shr_round(reduced, 28, Rounding::Nearest) as i64;
+    if angle >= PI {
+        angle -= 2 * PI;
+    }
+    rotate(angle, max_power)
+}
+
+/// Angle of the point `(x, y)` in radians, in `(-π, π]`, and zero at the
+/// origin.
+pub fn atan2(y: DyadicFraction, x: DyadicFraction, max_power: i8) -> DyadicFraction {
+    if x.num == 0 && y.num == 0 {
+        return DyadicFraction::zero();
+    }
+    let (mut x, mut y) = align(x, y);
+    // Vectoring converges on the right half-plane; rotate the left by π.
+    let offset = if x >= 0 {
+        0
+    } else if y >= 0 {
+        PI
+    } else {
+        -PI
+    };
+    if x < 0 {
+        (x, y) = (-x, -y);
+    }
+    let mut z = offset;
+    for (i, &atan) in ATAN.iter().enumerate().take(iterations(max_power)) {
+        let (dx, dy) = (y >> i, x >> i);
+        if y > 0 {
+            (x, y, z) = (x + dx, y - dy, z + atan);
+        } else {
+            (x, y, z) = (x - dx, y + dy, z - atan);
+        }
+    }
+    narrow(z, max_power)
+}
+
+/// Sine and cosine of `turns` full revolutions, rounded to nearest at a
+/// denominator of at most `2^max_power`.
+///
+/// Whole turns are dropped exactly before the angle is converted to
+/// radians, so the result does not depend on the precision of `τ`.
+pub(crate) fn sin_cos_turns(
+    turns: DyadicFraction,
+    max_power: i8,
+) -> (DyadicFraction, DyadicFraction) {
+    let phase = turn_fraction(turns) as i64;
+    let angle = ((phase as i128 * TAU) >> 92) as i64;
+    rotate(angle, max_power)
+}
+
+/// Fraction of a turn with 64 fractional bits, wrapping whole turns.
+pub(crate) fn turn_fraction(turns: DyadicFraction) -> u64 {
+    let shift = 64 - turns.power as i32;
+    if shift >= 64 {
+        0
+    } else if shift >= 0 {
+        (turns.num as i64 as u64) << shift
+    } else {
+        shr_round(turns.num as i128, -shift, Rounding::Nearest) as u64
+    }
+}
+
+/// CORDIC rotation of an angle in radians with 32 fractional bits in
+/// `[-π, π]`.
+pub(crate) const fn rotate(angle: i64, max_power: i8) -> (DyadicFraction, DyadicFraction) {
+    // Rotation converges on [-π/2, π/2]; mirror the rest through ±π.
+    let (angle, mirrored) = if angle > PI / 2 {
+        (PI - angle, true)
+    } else if angle < -PI / 2 {
+        (-PI - angle, true)
+    } else {
+        (angle, false)
+    };
+    let (mut x, mut y, mut z) = (GAIN, 0, angle);
+    let mut i = 0;
+    while i < iterations(max_power) {
+        let (dx, dy) = (y >> i, x >> i);
+        if z >= 0 {
+            (x, y, z) = (x - dx, y + dy, z - ATAN[i]);
+        } else {
+            (x, y, z) = (x + dx, y - dy, z + ATAN[i]);
+        }
+        i += 1;
+    }
+    let x = if mirrored { -x } else { x };
+    (narrow(y, max_power), narrow(x, max_power))
+}
+
+/// Scales `x` and `y` to a common power with at most 60 significant bits.
+/// A value too small to register keeps its sign as a single unit.
+fn align(x: DyadicFraction, y: DyadicFraction) -> (i64, i64) {
+    let (x_power, y_power) = (x.power as i32, y.power as i32);
+    let power = x_power.max(y_power).min(x_power.min(y_power) + 96);
+    let scale = |val: DyadicFraction| {
+        let shift = power - val.power as i32;
+        if shift >= 0 {
+            (val.num as i128) << shift
+        } else {
+            match (val.num as i128) >> -shift {
+                0 => val.num.signum() as i128,
+                num => num,
+            }
+        }
+    };
+    let (x, y) = (scale(x), scale(y));
+    let bits = 128 - (x.unsigned_abs() | y.unsigned_abs()).leading_zeros() as i32;
+    let narrow = |val: i128| {
+        if bits <= 60 {
+            return (val << (60 - bits)) as i64;
+        }
+        match val >> (bits - 60) {
+            0 => val.signum() as i64,
+            val => val as i64,
+        }
+    };
+    (narrow(x), narrow(y))
+}
+
+/// Each CORDIC step adds about one bit; a few more absorb the rounding.
+const fn iterations(max_power: i8) -> usize {
+    let iterations = max_power as i32 + 4;
+    if iterations < 1 {
+        1
+    } else if iterations > ATAN.len() as i32 {
+        ATAN.len()
+    } else {
+        iterations as usize
+    }
+}
+
+/// Rounds a value with 32 fractional bits to nearest at a denominator of at
+/// most `2^max_power`.
+const fn narrow(val: i64, max_power: i8) -> DyadicFraction {
+    let power = if max_power < 32 { max_power as i32 } else { 32 };
+    let num = shr_round(val as i128, 32 - power, Rounding::Nearest);
+    DyadicFraction::narrow(num, power, Rounding::Nearest)
+}
+
+/// An angle in radians, wrapped into `[-π, π)` by every operation.
+///
+/// Wrapping is carried out with 60 fractional bits before the result is
+/// rounded to nearest, so adding up many small steps does not drift out of
+/// range the way plain radians do.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Angle(DyadicFraction);
+
+impl Angle {
+    /// `π / 180` with 60 fractional bits.
+    const RADIANS_PER_DEGREE: i128 = 20122276272436452;
+    /// `180 / π` with 56 fractional bits.
+    const DEGREES_PER_RADIAN: i128 = 4128596020240314225;
+
+    pub const fn zero() -> Self {
+        Self(DyadicFraction::zero())
+    }
+
+    pub fn from_radians(radians: DyadicFraction) -> Self {
+        Self::from_fixed(radians.scaled(60, Rounding::Nearest))
+    }
+
+    pub fn from_degrees(degrees: DyadicFraction) -> Self {
+        let full = 360i128 << 32;
+        let mut degrees = degrees.scaled(32, Rounding::Nearest).rem_euclid(full);
+        if degrees >= full / 2 {
+            degrees -= full;
+        }
+        Self::from_fixed((degrees * Self::RADIANS_PER_DEGREE) >> 32)
+    }
+
+    /// Radians in `[-π, π)`.
+    pub const fn radians(self) -> DyadicFraction {
+        self.0
+    }
+
+    /// Radians in `[0, τ)`.
+    pub fn radians_positive(self) -> DyadicFraction {
+        let radians = self.fixed();
+        narrow_below(if radians < 0 { radians + TAU } else { radians }, TAU, 0)
+    }
+
+    /// Degrees in `[-180, 180)`.
+    pub fn to_degrees(self) -> DyadicFraction {
+        let degrees = self.fixed() * Self::DEGREES_PER_RADIAN;
+        let degrees = shr_round(degrees, 56, Rounding::Nearest);
+        narrow_below(degrees, 180 << 60, -180 << 60)
+    }
+
+    /// Shortest signed rotation from `other` to `self`, in `[-π, π)`.
+    pub fn diff(self, other: Self) -> Self {
+        self - other
+    }
+
+    pub fn sin_cos(self, max_power: i8) -> (DyadicFraction, DyadicFraction) {
+        sin_cos(self.0, max_power)
+    }
+
+    fn fixed(self) -> i128 {
+        self.0.scaled(60, Rounding::Nearest)
+    }
+
+    /// Wraps radians with 60 fractional bits into `[-π, π)`.
+    fn from_fixed(radians: i128) -> Self {
+        let mut radians = radians.rem_euclid(TAU);
+        if radians >= TAU / 2 {
+            radians -= TAU;
+        }
+        Self(narrow_below(radians, TAU / 2, -TAU / 2))
+    }
+}
+
+/// Rounds a value with 60 fractional bits to nearest, returning `low` instead
+/// when the result lands on or above the excluded upper bound `high`.
+fn narrow_below(val: i128, high: i128, low: i128) -> DyadicFraction {
+    let res = DyadicFraction::narrow(val, 60, Rounding::Nearest);
+    if res.scaled(60, Rounding::Nearest) >= high {
+        DyadicFraction::narrow(low, 60, Rounding::Nearest)
+    } else {
+        res
+    }
+}
+
+impl Add for Angle {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::from_fixed(self.fixed() + other.fixed())
+    }
+}
+
+impl AddAssign for Angle {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Angle {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::from_fixed(self.fixed() - other.fixed())
+    }
+}
+
+impl SubAssign for Angle {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl Neg for Angle {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::from_fixed(-self.fixed())
+    }
+}
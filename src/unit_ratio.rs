@@ -0,0 +1,117 @@
+use crate::pwm::Duty;
+use crate::{DyadicFraction, Rounding};
+use core::ops::*;
+
+/// A dyadic fraction clamped to `[0, 1]`, for duty cycles, DAC outputs and
+/// other commands that must not leave their range.
+///
+/// Constructors and arithmetic saturate at the bounds instead of failing.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct UnitRatio(DyadicFraction);
+
+impl UnitRatio {
+    pub const fn zero() -> Self {
+        Self(DyadicFraction::zero())
+    }
+
+    pub const fn one() -> Self {
+        Self(DyadicFraction::new(1, 0))
+    }
+
+    /// Clamps `val` to `[0, 1]`.
+    pub fn new(val: DyadicFraction) -> Self {
+        let val = DyadicFraction::max(val, DyadicFraction::zero());
+        Self(DyadicFraction::min(val, DyadicFraction::from(1)))
+    }
+
+    /// Clamps `percent / 100` to `[0, 1]`, rounded to nearest at the finest
+    /// power that fits.
+    pub fn from_percent(percent: DyadicFraction) -> Self {
+        Self::new(DyadicFraction::round_ratio(
+            percent.num as i128,
+            100,
+            percent.power as i32,
+            i8::MAX,
+        ))
+    }
+
+    pub const fn value(self) -> DyadicFraction {
+        self.0
+    }
+
+    /// Value in percent, rounded to nearest at the finest power that fits.
+    pub const fn as_percent(self) -> DyadicFraction {
+        self.0.to_percent()
+    }
+
+    /// `1 - self`.
+    pub const fn complement(self) -> Self {
+        let neg = self.0.neg_rounded(Rounding::Nearest);
+        Self(Self::one().0.add_rounded(neg, Rounding::Nearest))
+    }
+
+    /// Timer compare value for a timer counting up to `top`, rounded to
+    /// nearest.
+    pub fn to_duty(self, top: u16) -> u16 {
+        self.0.to_duty(top)
+    }
+
+    /// Output code of a DAC with `bits` of resolution, where all ones is full
+    /// scale. Resolutions above 16 bits are treated as 16, and zero bits
+    /// always give 0.
+    pub fn to_dac_code(self, bits: u8) -> u16 {
+        let full_scale = u16::MAX.checked_shr(16 - bits.min(16) as u32).unwrap_or(0);
+        Duty::new(full_scale, Rounding::Nearest).compare(self.0)
+    }
+}
+
+impl From<UnitRatio> for DyadicFraction {
+    fn from(val: UnitRatio) -> Self {
+        val.0
+    }
+}
+
+impl Add for UnitRatio {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(self.0.add_rounded(other.0, Rounding::Nearest))
+    }
+}
+
+impl AddAssign for UnitRatio {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for UnitRatio {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::new(
+            self.0
+                .add_rounded(other.0.neg_rounded(Rounding::Nearest), Rounding::Nearest),
+        )
+    }
+}
+
+impl SubAssign for UnitRatio {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul for UnitRatio {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        Self(self.0.mul_rounded(other.0, Rounding::Nearest))
+    }
+}
+
+impl MulAssign for UnitRatio {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
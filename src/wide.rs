@@ -0,0 +1,156 @@
+use crate::{DyadicFraction, Rounding};
+
+/// Wide accumulator for sums of dyadic fractions and their products.
+///
+/// Values are aligned to the finest power that keeps the numerator within
+/// 126 bits, so sums stay exact unless the operands span more than ~90 bits
+/// of dynamic range, in which case the finest bits are dropped.
+#[derive(Copy, Clone, Debug, Default)]
+pub(crate) struct Wide {
+    num: i128,
+    power: i32,
+}
+
+impl Wide {
+    pub const fn new(num: i128, power: i32) -> Self {
+        Self { num, power }
+    }
+
+    pub const fn from_df(val: DyadicFraction) -> Self {
+        Self::new(val.num as i128, val.power as i32)
+    }
+
+    pub const fn product(a: DyadicFraction, b: DyadicFraction) -> Self {
+        Self::new(
+            a.num as i128 * b.num as i128,
+            a.power as i32 + b.power as i32,
+        )
+    }
+
+    pub const fn neg(self) -> Self {
+        Self::new(-self.num, self.power)
+    }
+
+    pub const fn is_zero(self) -> bool {
+        self.num == 0
+    }
+
+    pub const fn is_negative(self) -> bool {
+        self.num < 0
+    }
+
+    pub const fn add(&mut self, other: Self) {
+        if other.num == 0 {
+            return;
+        }
+        if self.num == 0 {
+            *self = other;
+            return;
+        }
+        let target = min(
+            max(self.power, other.power),
+            min(
+                self.power + headroom(self.num),
+                other.power + headroom(other.num),
+            ),
+        );
+        self.num = shift(self.num, target - self.power) + shift(other.num, target - other.power);
+        self.power = target;
+    }
+
+    pub fn sub(&mut self, other: Self) {
+        self.add(other.neg());
+    }
+
+    pub fn add_product(&mut self, a: DyadicFraction, b: DyadicFraction) {
+        self.add(Self::product(a, b));
+    }
+
+    pub fn sub_product(&mut self, a: DyadicFraction, b: DyadicFraction) {
+        self.sub(Self::product(a, b));
+    }
+
+    /// Adds the dot product of `a` and `b`, ignoring extra elements of the
+    /// longer slice.
+    pub fn add_dot(&mut self, a: &[DyadicFraction], b: &[DyadicFraction]) {
+        for (&lhs, &rhs) in a.iter().zip(b) {
+            self.add_product(lhs, rhs);
+        }
+    }
+
+    /// Multiplies by `val`, first dropping low bits of the accumulator when
+    /// needed to keep the product within 126 bits.
+    pub fn mul_df(self, val: DyadicFraction) -> Self {
+        let excess = (128 - self.num.unsigned_abs().leading_zeros() as i32 - 94).max(0);
+        Self::new(
+            shift(self.num, -excess) * val.num as i128,
+            self.power - excess + val.power as i32,
+        )
+    }
+
+    /// Quotient `self / den` rounded to nearest at a denominator of at most
+    /// `2^max_power`, saturating when `den` is zero.
+    pub const fn div(self, den: Self, max_power: i8) -> DyadicFraction {
+        DyadicFraction::round_ratio(self.num, den.num, self.power - den.power, max_power)
+    }
+
+    /// `self / count` rounded to nearest at the finest power that fits, zero
+    /// for an empty set.
+    pub const fn average(self, count: usize) -> DyadicFraction {
+        if count == 0 {
+            return DyadicFraction::zero();
+        }
+        self.div(Self::new(count as i128, 0), i8::MAX)
+    }
+
+    /// Narrows to a dyadic fraction, rounding towards negative infinity.
+    pub const fn to_df(self) -> DyadicFraction {
+        DyadicFraction::from_wide(self.num, self.power)
+    }
+
+    /// Narrows to a dyadic fraction, rounding in the given direction.
+    pub const fn round(self, rounding: Rounding) -> DyadicFraction {
+        DyadicFraction::narrow(self.num, self.power, rounding)
+    }
+
+    /// Square root rounded down, zero for negative values.
+    pub const fn sqrt(self, max_power: i8) -> DyadicFraction {
+        if self.num <= 0 {
+            return DyadicFraction::zero();
+        }
+        DyadicFraction::sqrt_wide(self.num as u128, self.power, max_power)
+    }
+}
+
+/// Bits `num` can be shifted left while staying below `2^125`.
+const fn headroom(num: i128) -> i32 {
+    num.unsigned_abs().leading_zeros() as i32 - 3
+}
+
+const fn shift(num: i128, bits: i32) -> i128 {
+    if bits >= 0 {
+        num << bits
+    } else if bits > -128 {
+        num >> -bits
+    } else if num < 0 {
+        -1
+    } else {
+        0
+    }
+}
+
+const fn min(a: i32, b: i32) -> i32 {
+    if a < b {
+        a
+    } else {
+        b
+    }
+}
+
+const fn max(a: i32, b: i32) -> i32 {
+    if a > b {
+        a
+    } else {
+        b
+    }
+}
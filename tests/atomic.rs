@@ -0,0 +1,19 @@
+use core::sync::atomic::Ordering;
+use dyadic::{AtomicDF, DF};
+
+#[test]
+fn test_atomic() {
+    let val = AtomicDF::new(DF::new(3, 3));
+    assert_eq!(val.load(Ordering::Relaxed), DF::new(3, 3));
+    assert_eq!(val.swap(DF::from(-7), Ordering::Relaxed), DF::new(3, 3));
+    val.store(DF::new(5, 100), Ordering::Relaxed);
+    assert_eq!(val.load(Ordering::Relaxed), DF::new(5, 100));
+
+    let res = val.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |x| Some(x * DF::from(2)));
+    assert_eq!(res, Ok(DF::new(5, 100)));
+    assert_eq!(val.load(Ordering::Relaxed), DF::new(5, 99));
+    let res = val.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |_| None);
+    assert_eq!(res, Err(DF::new(5, 99)));
+
+    assert_eq!(AtomicDF::default().into_inner(), DF::zero());
+}
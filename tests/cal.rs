@@ -0,0 +1,150 @@
+use dyadic::cal::{AdcScaler, Linear, Ntc, Poly};
+use dyadic::DF;
+
+#[test]
+fn test_linear() {
+    // 12-bit ADC spanning -50..150 °C.
+    let cal =
+        Linear::from_points((DF::zero(), DF::from(-50)), (DF::from(4096), DF::from(150))).unwrap();
+    assert_eq!(cal, Linear::new(DF::new(25, 9), DF::from(-50)));
+    assert_eq!(cal.apply(DF::from(2048)), DF::from(50));
+    assert_eq!(cal.apply(DF::from(1)), DF::new(-25575, 9));
+    assert_eq!(cal.invert(DF::from(50)), DF::from(2048));
+    assert_eq!(cal.invert(DF::from(-50)), DF::zero());
+
+    // A gain of 1/3 is rounded once, the offset absorbs the first point.
+    let cal =
+        Linear::from_points((DF::from(1), DF::from(10)), (DF::from(4), DF::from(11))).unwrap();
+    assert!((cal.apply(DF::from(1)) - DF::from(10)).abs() <= DF::new(1, 26));
+    assert!((cal.apply(DF::from(4)) - DF::from(11)).abs() <= DF::new(1, 26));
+    assert!((cal.apply(DF::from(301)) - DF::from(110)).abs() <= DF::new(1, 22));
+    assert!((cal.invert(DF::from(110)) - DF::from(301)).abs() <= DF::new(1, 20));
+
+    assert!(Linear::from_points((DF::from(1), DF::zero()), (DF::from(1), DF::from(2))).is_none());
+    assert_eq!(
+        Linear::new(DF::zero(), DF::from(1)).invert(DF::from(2)),
+        DF::new(i32::MAX, -128)
+    );
+}
+
+#[test]
+fn test_poly() {
+    // Load cell with a slight quadratic bow.
+    let cal = Poly::new([DF::from(-12), DF::new(5, 3), DF::new(3, 20)]);
+    assert_eq!(cal.apply(DF::zero()), DF::from(-12));
+    assert_eq!(cal.apply(DF::from(1024)), DF::from(631));
+    assert_eq!(cal.apply(DF::from(-2048)), DF::from(-1280));
+    assert_eq!(Poly::<0>::new([]).apply(DF::from(3)), DF::zero());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_poly_fit() {
+    let near = |actual: DF, expected: DF| {
+        assert!(
+            (f64::from(actual) - f64::from(expected)).abs() < 1e-6,
+            "{}",
+            actual
+        );
+    };
+    let truth = Poly::new([DF::new(1, 1), DF::new(-1, 2), DF::new(1, 3)]);
+    let points: Vec<_> = (0..=10)
+        .map(|i| (DF::from(i * 100), truth.apply(DF::from(i * 100))))
+        .collect();
+    let fit = Poly::<3>::fit(&points).unwrap();
+    for (&coeff, &expected) in fit.coeffs.iter().zip(&truth.coeffs) {
+        near(coeff, expected);
+    }
+
+    // Noisy points around a line.
+    let points = [(0, 1), (1, 1), (3, 2), (4, 3)].map(|(x, y)| (DF::from(x), DF::from(y)));
+    let line = Poly::<2>::fit(&points).unwrap();
+    near(line.coeffs[0], DF::new(3, 2));
+    near(line.coeffs[1], DF::new(1, 1));
+
+    assert!(Poly::<3>::fit(&points[..2]).is_none());
+    let repeated = [(DF::from(2), DF::from(1)); 4];
+    assert!(Poly::<2>::fit(&repeated).is_none());
+}
+
+#[test]
+fn test_ntc_beta() {
+    let near = |actual: Option<DF>, num: i32| {
+        let actual = actual.unwrap();
+        assert!(
+            (actual - DF::new(num, 16)).abs() <= DF::new(1, 16),
+            "{}",
+            actual
+        );
+    };
+    // 10k NTC with B = 3950 behind a 10k series resistor.
+    let ntc = Ntc::beta(
+        DF::from(10000),
+        DF::from(25),
+        DF::from(3950),
+        DF::from(10000),
+    );
+    assert_eq!(ntc.resistance(DF::new(1, 1)), Some(DF::from(10000)));
+    assert_eq!(ntc.resistance(DF::new(3, 2)), Some(DF::from(30000)));
+    assert_eq!(ntc.temperature(DF::new(1, 1), 16), Some(DF::from(25)));
+    near(ntc.temperature(DF::new(1, 2), 16), 3405218);
+    near(ntc.temperature(DF::new(3, 2), 16), 142169);
+    near(ntc.temperature_from_resistance(DF::from(30000), 16), 142169);
+
+    assert_eq!(ntc.temperature(DF::zero(), 16), None);
+    assert_eq!(ntc.temperature(DF::from(1), 16), None);
+    assert_eq!(ntc.temperature(DF::new(-1, 2), 16), None);
+    assert_eq!(ntc.temperature_from_resistance(DF::zero(), 16), None);
+}
+
+#[test]
+fn test_ntc_steinhart_hart() {
+    let near = |actual: Option<DF>, num: i32| {
+        let actual = actual.unwrap();
+        assert!(
+            (actual - DF::new(num, 16)).abs() <= DF::new(1, 16),
+            "{}",
+            actual
+        );
+    };
+    let ntc = Ntc::steinhart_hart(
+        DF::try_from(1.009249522e-3).unwrap(),
+        DF::try_from(2.378405444e-4).unwrap(),
+        DF::try_from(2.019202697e-7).unwrap(),
+        DF::from(10000),
+    );
+    near(ntc.temperature(DF::new(1, 1), 16), 1617513);
+    near(ntc.temperature(DF::new(1, 2), 16), 3611991);
+}
+
+#[test]
+fn test_adc_scaler() {
+    // 12-bit ADC with a 3.3 V reference reading a 1:11 voltage divider.
+    let volts = AdcScaler::new(DF::from(4096), DF::from_ratio(33, 10, 28), DF::from(11));
+    assert_eq!(volts.convert(0), DF::zero());
+    assert!((volts.convert(4096) - DF::from_ratio(363, 10, 24)).abs() <= DF::new(1, 24));
+    assert!((volts.convert(2048) - DF::from_ratio(363, 20, 24)).abs() <= DF::new(1, 24));
+    assert!((volts.convert(u16::MAX) - DF::new(38062728, 16)).abs() <= DF::new(1, 16));
+
+    // Power-of-two references stay exact.
+    let exact = AdcScaler::new(DF::from(1024), DF::from(2), DF::from(3));
+    assert_eq!(exact.resolution(), DF::new(3, 9));
+    assert_eq!(exact.convert(100), DF::new(75, 7));
+
+    // A full scale of 1000 counts is rounded once.
+    let amps = AdcScaler::new(DF::from(1000), DF::from(1), DF::from(1));
+    assert!((amps.convert(500) - DF::new(1, 1)).abs() <= DF::new(1, 30));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde() {
+    use serde::de::value::{Error, SeqDeserializer};
+    use serde::Deserialize;
+
+    let raw = SeqDeserializer::<_, Error>::new(["25/512", "-50"].into_iter());
+    assert_eq!(
+        Linear::deserialize(raw),
+        Ok(Linear::new(DF::new(25, 9), DF::from(-50)))
+    );
+}
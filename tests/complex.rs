@@ -0,0 +1,38 @@
+use dyadic::{Complex, DF};
+
+#[test]
+fn test_complex_ops() {
+    let a = Complex::new(DF::from(3), DF::new(1, 1));
+    let b = Complex::new(DF::new(-1, 2), DF::from(2));
+    assert_eq!(a + b, Complex::new(DF::new(11, 2), DF::new(5, 1)));
+    assert_eq!(a - b, Complex::new(DF::new(13, 2), DF::new(-3, 1)));
+    assert_eq!(a * b, Complex::new(DF::new(-7, 2), DF::new(47, 3)));
+    assert_eq!(a * DF::from(2), Complex::new(DF::from(6), DF::from(1)));
+    assert_eq!(a.conj(), Complex::new(DF::from(3), DF::new(-1, 1)));
+    assert_eq!(-a, Complex::new(DF::from(-3), DF::new(-1, 1)));
+    assert_eq!(format!("{}", b), "-1/4 + 2i");
+    assert_eq!(format!("{:#}", a.conj()), "3 - 0.5i");
+}
+
+#[test]
+fn test_complex_norm() {
+    let a = Complex::new(DF::from(3), DF::from(-4));
+    assert_eq!(a.norm_sqr(), DF::from(25));
+    assert_eq!(a.norm(16), DF::from(5));
+
+    let b = Complex::new(DF::from(1), DF::from(1));
+    assert_eq!(b.norm(16), DF::new(92681, 16));
+
+    let big = Complex::new(DF::from(i32::MAX), DF::from(i32::MAX));
+    assert_eq!(big.norm_sqr(), DF::new(i32::MAX - 1, -32));
+    assert_eq!(big.norm(0), DF::new(1518500249, -1));
+}
+
+#[test]
+fn test_sqrt() {
+    assert_eq!(DF::from(2).sqrt(8), DF::new(362, 8));
+    assert_eq!(DF::new(9, 4).sqrt(8), DF::new(3, 2));
+    assert_eq!(DF::new(1, 100).sqrt(127), DF::new(1, 50));
+    assert_eq!(DF::from(-4).sqrt(8), DF::zero());
+    assert_eq!(DF::from(i32::MAX).sqrt(127), DF::new(1518500249, 15));
+}
@@ -0,0 +1,300 @@
+use dyadic::control::{
+    BangBang, Differentiator, Discrete, Hysteresis, Integration, Integrator, Ramp, SlewLimiter,
+    SmithPredictor, TakeBackHalf, Transition,
+};
+use dyadic::sim::FirstOrderPlant;
+use dyadic::DF;
+
+#[test]
+fn test_slew_limiter() {
+    let mut slew = SlewLimiter::new(DF::from(2), DF::new(-1, 1));
+    assert_eq!(slew.rise(), DF::from(2));
+    assert_eq!(slew.fall(), DF::new(1, 1));
+    assert_eq!(slew.update(DF::from(5)), DF::from(2));
+    assert_eq!(slew.update(DF::from(5)), DF::from(4));
+    assert_eq!(slew.update(DF::from(5)), DF::from(5));
+    assert_eq!(slew.update(DF::from(5)), DF::from(5));
+    assert_eq!(slew.update(DF::from(4)), DF::new(9, 1));
+    assert_eq!(slew.update(DF::new(17, 2)), DF::new(17, 2));
+    assert_eq!(slew.value(), DF::new(17, 2));
+
+    slew.reset(DF::from(-100));
+    assert_eq!(slew.value(), DF::from(-100));
+    slew.set_rates(DF::from(i32::MAX), DF::new(1, 3));
+    assert_eq!(slew.update(DF::from(i32::MAX)), DF::from(i32::MAX - 100));
+    assert_eq!(slew.update(DF::from(i32::MAX)), DF::from(i32::MAX));
+    // Steps finer than the output are rounded away instead of saturating.
+    assert_eq!(slew.update(DF::from(i32::MIN)), DF::from(i32::MAX));
+}
+
+#[test]
+fn test_ramp() {
+    let mut ramp = Ramp::new(DF::new(-3, 1));
+    assert_eq!(ramp.rate(), DF::new(3, 1));
+    assert!(ramp.is_done());
+    assert_eq!(ramp.update(), DF::zero());
+
+    ramp.set_target(DF::from(4));
+    assert_eq!(ramp.target(), DF::from(4));
+    assert!(!ramp.is_done());
+    assert_eq!(ramp.update(), DF::new(3, 1));
+    assert_eq!(ramp.update(), DF::from(3));
+    assert_eq!(ramp.update(), DF::from(4));
+    assert!(ramp.is_done());
+    assert_eq!(ramp.update(), DF::from(4));
+
+    ramp.set_target(DF::from(-1));
+    ramp.set_rate(DF::from(2));
+    assert_eq!(ramp.update(), DF::from(2));
+    assert_eq!(ramp.update(), DF::zero());
+    assert_eq!(ramp.update(), DF::from(-1));
+    assert!(ramp.is_done());
+
+    ramp.set_target(DF::from(100));
+    ramp.reset(DF::from(7));
+    assert_eq!(ramp.value(), DF::from(7));
+    assert!(ramp.is_done());
+}
+
+#[test]
+fn test_integrator() {
+    let dt = DF::new(1, 2);
+    let mut rect = Integrator::new(Integration::Rectangular, dt);
+    let mut trap = Integrator::new(Integration::Trapezoidal, dt);
+    assert_eq!(rect.dt(), dt);
+    for input in [4, 4, 8, 0] {
+        rect.update(DF::from(input));
+        trap.update(DF::from(input));
+    }
+    assert_eq!(rect.value(), DF::from(4));
+    // Trapezoids (0+4)/2, (4+4)/2, (4+8)/2, (8+0)/2 of width 1/4.
+    assert_eq!(trap.value(), DF::from(4));
+
+    // Ramp input: the trapezoid rule is exact, rectangles overshoot by dt/2
+    // per step.
+    rect.reset();
+    trap.reset();
+    for i in 1..=4 {
+        rect.update(DF::from(i));
+        trap.update(DF::from(i));
+    }
+    assert_eq!(rect.value(), DF::new(5, 1));
+    assert_eq!(trap.value(), DF::from(2));
+
+    // Clamping keeps the sum at the limit so it recovers immediately.
+    rect.set_limits(DF::from(3), DF::from(-1));
+    assert_eq!(rect.limits(), (DF::from(-1), DF::from(3)));
+    assert_eq!(rect.value(), DF::new(5, 1));
+    assert_eq!(rect.update(DF::from(100)), DF::from(3));
+    assert_eq!(rect.update(DF::from(-4)), DF::from(2));
+    assert_eq!(rect.update(DF::from(-100)), DF::from(-1));
+
+    rect.preload(DF::new(3, 1));
+    assert_eq!(rect.value(), DF::new(3, 1));
+    rect.preload(DF::from(9));
+    assert_eq!(rect.value(), DF::from(3));
+
+    rect.set_frozen(true);
+    assert!(rect.is_frozen());
+    assert_eq!(rect.update(DF::from(-8)), DF::from(3));
+    rect.set_frozen(false);
+    assert_eq!(rect.update(DF::from(-8)), DF::from(1));
+
+    // The previous input is tracked while frozen.
+    trap.reset();
+    trap.set_frozen(true);
+    trap.update(DF::from(8));
+    trap.set_frozen(false);
+    assert_eq!(trap.update(DF::from(8)), DF::from(2));
+}
+
+#[test]
+fn test_differentiator() {
+    let mut raw = Differentiator::new(DF::new(1, 2), DF::zero());
+    assert_eq!(raw.dt(), DF::new(1, 2));
+    assert_eq!(raw.tau(), DF::zero());
+    raw.reset(DF::from(10));
+    assert_eq!(raw.update(DF::from(11)), DF::from(4));
+    assert_eq!(raw.update(DF::from(13)), DF::from(8));
+    assert_eq!(raw.update(DF::from(13)), DF::zero());
+
+    // With tau = 3 dt a unit step decays by 3/4 per sample.
+    let mut diff = Differentiator::new(DF::from(1), DF::from(3));
+    assert_eq!(diff.update(DF::from(4)), DF::from(1));
+    assert_eq!(diff.update(DF::from(4)), DF::new(3, 2));
+    assert_eq!(diff.update(DF::from(4)), DF::new(9, 4));
+
+    // A ramp settles at its slope.
+    diff.reset(DF::zero());
+    let mut rate = DF::zero();
+    for i in 1..=100 {
+        rate = diff.update(DF::from(2 * i));
+    }
+    assert!(DF::sum_exact(&[rate, DF::from(-2)]).abs() < DF::new(1, 20));
+    assert_eq!(diff.value(), rate);
+
+    diff.set_params(DF::from(1), DF::zero());
+    assert_eq!(diff.update(DF::from(205)), DF::from(5));
+}
+
+#[test]
+fn test_discrete() {
+    // Tustin-discretized integrator with dt = 1/2: y[n] = y[n-1] + (x[n] + x[n-1]) / 4.
+    let mut integrator = Discrete::new([DF::new(1, 2); 2], [DF::from(-1)]);
+    assert_eq!(integrator.update(DF::from(4)), DF::from(1));
+    assert_eq!(integrator.update(DF::from(4)), DF::from(3));
+    assert_eq!(integrator.update(DF::zero()), DF::from(4));
+    assert_eq!(integrator.update(DF::zero()), DF::from(4));
+    assert_eq!(integrator.value(), DF::from(4));
+    assert_eq!(integrator.b(), &[DF::new(1, 2); 2]);
+    assert_eq!(integrator.a(), &[DF::from(-1)]);
+
+    // Second-order feedback y[n] = x[n] + y[n-1] - y[n-2]/2 from a unit impulse.
+    let mut resonator = Discrete::new([DF::from(1)], [DF::from(-1), DF::new(1, 1)]);
+    let response = [DF::from(1), DF::zero(), DF::zero()].map(|x| resonator.update(x));
+    assert_eq!(response, [DF::from(1), DF::from(1), DF::new(1, 1)]);
+
+    // Matches the equivalent FIR and biquad.
+    let taps = [3, -1, 2].map(|k| DF::new(k, 2));
+    let mut fir = Discrete::<3, 0>::new(taps, []);
+    let mut reference = dyadic::filter::Fir::new(taps);
+    let mut biquad = Discrete::new(taps, [DF::new(-1, 1), DF::new(1, 3)]);
+    let mut reference_biquad = dyadic::filter::Biquad::new(taps, [DF::new(-1, 1), DF::new(1, 3)]);
+    for i in 0..20 {
+        let x = DF::new((i * 37) % 11 - 5, 1);
+        assert_eq!(fir.update(x), reference.update(x));
+        assert_eq!(biquad.update(x), reference_biquad.update(x));
+    }
+
+    integrator.reset();
+    assert_eq!(integrator.update(DF::from(2)), DF::new(1, 1));
+    let mut gain = Discrete::<1, 0>::new([DF::from(3)], []);
+    assert_eq!(gain.update(DF::from(2)), DF::from(6));
+    let mut empty = Discrete::<0, 0>::new([], []);
+    assert_eq!(empty.update(DF::from(2)), DF::zero());
+}
+
+#[test]
+fn test_hysteresis() {
+    // Thermostat heating below 19 and stopping above 21 degrees.
+    let mut cold = Hysteresis::new(DF::from(21), DF::from(19));
+    assert_eq!(cold.thresholds(), (DF::from(19), DF::from(21)));
+    assert!(!cold.state());
+    let readings = [20, 21, 22, 20, 19, 18, 20, 21];
+    let events = readings.map(|t| cold.update(DF::from(t)));
+    assert_eq!(
+        events,
+        [
+            None,
+            Some(Transition::Rising),
+            None,
+            None,
+            Some(Transition::Falling),
+            None,
+            None,
+            Some(Transition::Rising),
+        ]
+    );
+    assert!(cold.state());
+
+    cold.reset(false);
+    assert_eq!(cold.update(DF::new(41, 1)), None);
+    assert_eq!(cold.update(DF::new(43, 1)), Some(Transition::Rising));
+    cold.set_thresholds(DF::from(30), DF::from(30));
+    assert_eq!(cold.update(DF::from(30)), Some(Transition::Falling));
+    assert_eq!(cold.update(DF::from(30)), Some(Transition::Rising));
+}
+
+#[test]
+fn test_bang_bang() {
+    let mut relay = BangBang::new(DF::from(-2), 3, 2);
+    assert_eq!(relay.dead_band(), DF::from(2));
+    assert_eq!((relay.min_on(), relay.min_off()), (3, 2));
+    assert!(!relay.update(DF::new(1, 1)));
+    assert!(relay.update(DF::from(1)));
+    // Held on for three ticks although the error reverses at once.
+    assert!(relay.update(DF::from(-5)));
+    assert!(relay.update(DF::from(-5)));
+    assert!(!relay.update(DF::from(-5)));
+    // Held off for two ticks.
+    assert!(!relay.update(DF::from(5)));
+    assert!(relay.update(DF::from(5)));
+    // Inside the dead band the output holds without a time limit.
+    for _ in 0..10 {
+        assert!(relay.update(DF::zero()));
+    }
+    assert!(!relay.update(DF::from(-1)));
+
+    relay.reset(true);
+    assert!(relay.state());
+    assert!(!relay.update(DF::from(-1)));
+    relay.set_min_times(0, 0);
+    relay.set_dead_band(DF::zero());
+    assert!(relay.update(DF::zero()));
+    assert!(!relay.update(DF::zero()));
+}
+
+#[test]
+fn test_take_back_half() {
+    let mut tbh = TakeBackHalf::new(DF::new(1, 2));
+    assert_eq!(tbh.gain(), DF::new(1, 2));
+    assert_eq!(tbh.update(DF::from(8)), DF::from(2));
+    assert_eq!(tbh.update(DF::from(8)), DF::from(4));
+    // First crossing halves back towards the initial take-back value of zero.
+    assert_eq!(tbh.update(DF::from(-4)), DF::new(3, 1));
+    assert_eq!(tbh.update(DF::from(-4)), DF::new(1, 1));
+    // Second crossing halves back towards 3/2.
+    assert_eq!(tbh.update(DF::from(2)), DF::new(5, 2));
+    assert_eq!(tbh.value(), DF::new(5, 2));
+
+    // Settles on a first-order plant, speed = output.
+    tbh.reset(DF::from(1));
+    let mut speed = DF::zero();
+    for _ in 0..200 {
+        let output = tbh.update(DF::sum_exact(&[DF::from(3), -speed]));
+        speed = output;
+    }
+    assert!(DF::sum_exact(&[speed, DF::from(-3)]).abs() < DF::new(1, 10));
+
+    tbh.set_limits(DF::from(2), DF::zero());
+    assert_eq!(tbh.limits(), (DF::zero(), DF::from(2)));
+    assert_eq!(tbh.value(), DF::from(2));
+    assert_eq!(tbh.update(DF::from(100)), DF::from(2));
+    tbh.reset(DF::from(-1));
+    assert_eq!(tbh.value(), DF::zero());
+    tbh.set_gain(DF::from(1));
+    assert_eq!(tbh.update(DF::from(1)), DF::from(1));
+}
+
+#[test]
+fn test_smith_predictor() {
+    let (gain, tau, dt) = (DF::from(2), DF::from(4), DF::from(1));
+    let mut plant = FirstOrderPlant::<6>::new(gain, tau, dt);
+    let mut smith = SmithPredictor::<6>::new(gain, tau, dt);
+    assert_eq!(smith.model().delay(), 0);
+    let mut integrator = Integrator::new(Integration::Rectangular, DF::new(1, 3));
+
+    // With an exact model the controller sees the undelayed response.
+    let mut measurement = DF::zero();
+    for _ in 0..300 {
+        let feedback = smith.feedback(measurement);
+        assert_eq!(feedback, smith.prediction());
+        let output = integrator.update(DF::sum_exact(&[DF::from(10), -feedback]));
+        smith.update(output);
+        measurement = plant.update(output);
+    }
+    assert!(DF::sum_exact(&[measurement, DF::from(-10)]).abs() < DF::new(1, 10));
+
+    // A disturbance on the measurement passes straight through.
+    assert_eq!(
+        smith.feedback(DF::sum_exact(&[measurement, DF::from(1)])),
+        DF::sum_exact(&[smith.prediction(), DF::from(1)])
+    );
+
+    smith.reset();
+    assert_eq!(smith.prediction(), DF::zero());
+    assert_eq!(smith.feedback(DF::from(3)), DF::from(3));
+    let mut no_delay = SmithPredictor::<0>::new(gain, tau, dt);
+    no_delay.update(DF::from(5));
+    assert_eq!(no_delay.feedback(DF::from(3)), DF::from(3));
+}
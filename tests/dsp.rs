@@ -0,0 +1,235 @@
+use dyadic::control::Transition;
+use dyadic::dsp::{EnvelopeFollower, PhaseAccumulator, Pll, Prbs, Shape, Waveform, ZeroCross};
+use dyadic::{assert_df_approx_eq, DF};
+
+#[test]
+fn test_prbs() {
+    let mut prbs = Prbs::new(1, DF::from(-1), DF::from(1));
+    assert!(prbs.next_bit());
+    assert!(prbs.next_bit());
+    assert_eq!(prbs.next_level(), DF::from(-1));
+
+    let mut locked = Prbs::new(0, DF::zero(), DF::from(1));
+    assert!((0..64).any(|_| locked.next_bit()));
+
+    let low = DF::new(-5, 1);
+    let high = DF::from(3);
+    let samples = Prbs::new(0xACE1, low, high);
+    let (mut below, mut above) = (0, 0);
+    for sample in samples.take(1000) {
+        assert!(sample >= low && sample < high);
+        if sample < DF::new(1, 2) {
+            below += 1;
+        } else {
+            above += 1;
+        }
+    }
+    assert!(below > 400 && above > 400);
+
+    let a: Vec<_> = Prbs::new(7, low, high).take(4).collect();
+    let b: Vec<_> = Prbs::new(7, low, high).take(4).collect();
+    assert_eq!(a, b);
+}
+
+#[test]
+fn test_phase_accumulator() {
+    let mut nco = PhaseAccumulator::new(DF::new(3, 3));
+    assert!(!nco.tick());
+    assert_eq!(nco.turns(), DF::new(3, 3));
+    assert!(!nco.tick());
+    assert!(nco.tick());
+    assert_eq!(nco.turns(), DF::new(1, 3));
+    assert_eq!(nco.increment(), DF::new(3, 3));
+
+    nco.set_increment(DF::new(-1, 2));
+    assert!(nco.tick());
+    assert_eq!(nco.turns(), DF::new(7, 3));
+    assert!(!nco.tick());
+    assert_eq!(nco.turns(), DF::new(5, 3));
+
+    nco.set_turns(DF::new(5, 1));
+    assert_eq!(nco.turns(), DF::new(1, 1));
+    assert_eq!(nco.radians(), DF::new(1686629713, 29));
+
+    let mut clock = PhaseAccumulator::from_frequency(DF::from(50), DF::from(1000));
+    let pulses = (0..1001).filter(|_| clock.tick()).count();
+    assert_eq!(pulses, 50);
+}
+
+/// `a - b` in turns, wrapped into `[-1/2, 1/2)`.
+fn phase_diff(a: DF, b: DF) -> DF {
+    let diff = DF::sum_exact(&[a, -b]);
+    if diff >= DF::new(1, 1) {
+        DF::sum_exact(&[diff, DF::from(-1)])
+    } else if diff < DF::new(-1, 1) {
+        DF::sum_exact(&[diff, DF::from(1)])
+    } else {
+        diff
+    }
+}
+
+#[test]
+fn test_pll() {
+    let mut reference = PhaseAccumulator::new(DF::from_ratio(1, 100, 40));
+    let mut pll = Pll::new(DF::from_ratio(1, 90, 40), DF::new(1, 3), DF::new(1, 6));
+    assert_eq!(pll.gains(), (DF::new(1, 3), DF::new(1, 6)));
+    assert_eq!(pll.phase(), DF::zero());
+    for _ in 0..400 {
+        reference.tick();
+        pll.update(phase_diff(reference.turns(), pll.phase()));
+    }
+    assert!(pll.phase_error().abs() < DF::new(1, 20));
+    assert!(pll.increment().abs_diff(reference.increment()) < DF::new(1, 24));
+    // The oscillator has already advanced to the next tick.
+    reference.tick();
+    assert!(phase_diff(reference.turns(), pll.phase()).abs() < DF::new(1, 16));
+
+    pll.reset();
+    assert_eq!(pll.phase(), DF::zero());
+    assert_eq!(pll.increment(), DF::from_ratio(1, 90, 40));
+    pll.set_gains(DF::zero(), DF::zero());
+    pll.update(DF::new(1, 2));
+    assert_eq!(pll.increment(), DF::from_ratio(1, 90, 40));
+    assert_eq!(pll.oscillator().turns(), pll.phase());
+}
+
+#[test]
+fn test_pll_crossing() {
+    // Mains at 50 Hz sampled at 5 kHz, starting from 49 Hz and out of phase.
+    let mut reference = PhaseAccumulator::new(DF::from_ratio(1, 100, 40));
+    reference.set_turns(DF::new(1, 1));
+    let mut pll = Pll::new(DF::from_ratio(49, 5000, 40), DF::new(1, 8), DF::new(1, 12));
+    for _ in 0..50_000 {
+        let crossed = reference.tick();
+        pll.update_crossing(crossed);
+    }
+    assert!(pll.increment().abs_diff(reference.increment()) < DF::new(1, 24));
+    assert!(phase_diff(reference.turns(), pll.phase()).abs() < DF::new(1, 10));
+    assert!(pll.phase_error().abs() < DF::new(1, 10));
+}
+
+#[test]
+fn test_waveform() {
+    let osc = PhaseAccumulator::new(DF::new(1, 3));
+    let levels = |shape| -> Vec<DF> {
+        Waveform::new(shape, osc, DF::from(2), DF::from(1))
+            .take(9)
+            .collect()
+    };
+    let ints = |vals: &[i32]| -> Vec<DF> { vals.iter().map(|&v| DF::from(v)).collect() };
+    assert_eq!(levels(Shape::Triangle), ints(&[1, 2, 3, 2, 1, 0, -1, 0, 1]));
+    assert_eq!(
+        levels(Shape::Square),
+        ints(&[3, 3, 3, 3, -1, -1, -1, -1, 3])
+    );
+    let saw: Vec<DF> = [2, 3, 4, 5, -2, -1, 0, 1, 2]
+        .iter()
+        .map(|&v| DF::new(v, 1))
+        .collect();
+    assert_eq!(levels(Shape::Sawtooth), saw);
+
+    // CORDIC is accurate to a few units in the last of its 32 bits.
+    let sine = levels(Shape::Sine);
+    let peak = DF::sum_exact(&[DF::from(1), dyadic::consts::sqrt_2(30)]);
+    let tol = DF::new(1, 28);
+    assert_df_approx_eq!(sine[0], DF::from(1), tol);
+    assert_df_approx_eq!(sine[1], peak, tol);
+    assert_df_approx_eq!(sine[2], DF::from(3), tol);
+    assert_df_approx_eq!(sine[6], DF::from(-1), tol);
+    assert_df_approx_eq!(sine[7], DF::sum_exact(&[DF::from(2), -peak]), tol);
+
+    let mut wave = Waveform::new(Shape::default(), osc, DF::from(1), DF::zero());
+    assert_eq!(wave.shape(), Shape::Sine);
+    wave.set_shape(Shape::Square);
+    wave.set_amplitude(DF::new(1, 1));
+    wave.set_offset(DF::new(1, 1));
+    assert_eq!(wave.amplitude(), DF::new(1, 1));
+    assert_eq!(wave.offset(), DF::new(1, 1));
+    assert_eq!(wave.sample(), DF::from(1));
+    wave.oscillator_mut().set_turns(DF::new(3, 2));
+    assert_eq!(wave.next_sample(), DF::zero());
+    assert_eq!(wave.oscillator().turns(), DF::new(7, 3));
+}
+
+#[test]
+fn test_envelope_follower() {
+    let mut env = EnvelopeFollower::new(DF::new(1, 1), DF::new(1, 3));
+    assert_eq!(env.coefficients(), (DF::new(1, 1), DF::new(1, 3)));
+    assert_eq!(env.update(DF::from(-8)), DF::from(4));
+    assert_eq!(env.update(DF::from(8)), DF::from(6));
+    assert_eq!(env.update(DF::zero()), DF::new(21, 2));
+    assert_eq!(env.value(), DF::new(21, 2));
+
+    // A sine is held close to its peak with a fast attack.
+    let osc = PhaseAccumulator::new(DF::new(1, 5));
+    let sine = Waveform::new(Shape::Sine, osc, DF::from(2), DF::zero());
+    env.set_coefficients(DF::from(1), DF::new(1, 8));
+    env.reset(DF::zero());
+    for sample in sine.take(320) {
+        env.update(sample);
+    }
+    assert!(env.value() > DF::new(7, 2) && env.value() <= DF::from(2));
+
+    env.reset(DF::from(3));
+    assert_eq!(env.update(DF::from(-3)), DF::from(3));
+    assert_eq!(
+        env.update(i32::MIN.into()),
+        DF::new(i32::MIN, 0).abs_diff(DF::zero())
+    );
+}
+
+#[test]
+fn test_zero_cross() {
+    let mut zc = ZeroCross::new(DF::zero());
+    assert_eq!(zc.update(DF::from(-2)), None);
+    let rising = zc.update(DF::from(1)).unwrap();
+    assert_eq!(rising.transition, Transition::Rising);
+    assert_df_approx_eq!(rising.delay, DF::from_ratio(1, 3, 40), DF::new(1, 30));
+    assert_eq!(zc.update(DF::from(3)), None);
+    // A sample at exactly zero is where the signal crossed.
+    assert_eq!(zc.update(DF::zero()), None);
+    let falling = zc.update(DF::from(-1)).unwrap();
+    assert_eq!(falling.transition, Transition::Falling);
+    assert_eq!(falling.delay, DF::from(1));
+    assert_eq!(zc.period(), None);
+
+    // Noise inside the band is ignored; the last sign change counts.
+    let mut zc = ZeroCross::new(DF::from(-1));
+    assert_eq!(zc.hysteresis(), DF::from(1));
+    for sample in [DF::from(-2), DF::new(1, 1), DF::new(-1, 1), DF::new(1, 1)] {
+        assert_eq!(zc.update(sample), None);
+    }
+    let crossing = zc.update(DF::from(2)).unwrap();
+    assert_eq!(crossing.delay, DF::new(3, 1));
+    zc.reset();
+    assert_eq!(zc.update(DF::from(2)), None);
+    zc.set_hysteresis(DF::zero());
+    assert!(zc.update(DF::from(-2)).is_some());
+}
+
+#[test]
+fn test_zero_cross_sine() {
+    // 20 samples per period, starting 3/32 of a turn in.
+    let mut osc = PhaseAccumulator::new(DF::from_ratio(1, 20, 40));
+    osc.set_turns(DF::new(3, 5));
+    let sine = Waveform::new(Shape::Sine, osc, DF::from(1), DF::zero());
+    let mut zc = ZeroCross::new(DF::new(1, 4));
+    let mut crossings = 0;
+    for (idx, sample) in sine.take(200).enumerate() {
+        let Some(crossing) = zc.update(sample) else {
+            continue;
+        };
+        let at = DF::sum_exact(&[DF::from(idx as i32), -crossing.delay]);
+        // Crossings at `20 m - 15/8` rising and `20 m + 65/8` falling.
+        let offset = match crossing.transition {
+            Transition::Rising => DF::new(15, 3),
+            Transition::Falling => DF::new(-65, 3),
+        };
+        let cycles = DF::sum_exact(&[at, offset]);
+        let nearest = DF::from((cycles.floor() + 10).div_euclid(20) * 20);
+        assert!(cycles.abs_diff(nearest) < DF::new(1, 5), "{}", at);
+        crossings += 1;
+    }
+    assert_eq!(crossings, 20);
+    assert!(zc.period().unwrap().abs_diff(DF::from(20)) < DF::new(1, 10));
+}
@@ -0,0 +1,279 @@
+use dyadic::filter::{
+    Biquad, DcBlocker, Fir, Kalman1d, LowPass1, Median, MovingAverage, SavGol, SavGolFit,
+    WindowMinMax,
+};
+use dyadic::DF;
+
+#[test]
+fn test_moving_average() {
+    let mut avg = MovingAverage::<4>::new();
+    assert_eq!(avg.value(), DF::zero());
+    assert_eq!(avg.update(DF::from(4)), DF::from(4));
+    assert_eq!(avg.update(DF::from(8)), DF::from(6));
+    assert_eq!(avg.update(DF::from(1)), DF::new(1163220309, 28));
+    assert_eq!(avg.update(DF::from(3)), DF::from(4));
+    assert_eq!(avg.update(DF::from(12)), DF::from(6));
+    assert_eq!(avg.update(DF::new(1, 1)), DF::new(33, 3));
+
+    let mut avg = MovingAverage::<256>::new();
+    for _ in 0..1000 {
+        avg.update(DF::from(i32::MAX));
+    }
+    assert_eq!(avg.value(), DF::from(i32::MAX));
+    avg.reset();
+    assert_eq!(avg.update(DF::new(-1, 3)), DF::new(-1, 3));
+}
+
+#[test]
+fn test_median() {
+    let mut median = Median::<3>::new();
+    assert_eq!(median.value(), DF::zero());
+    assert_eq!(median.update(DF::from(20)), DF::from(20));
+    assert_eq!(median.update(DF::from(21)), DF::from(21));
+    assert_eq!(median.update(DF::from(900)), DF::from(21));
+    assert_eq!(median.update(DF::from(22)), DF::from(22));
+    assert_eq!(median.update(DF::from(22)), DF::from(22));
+    assert_eq!(median.update(DF::from(-500)), DF::from(22));
+    assert_eq!(median.update(DF::new(47, 1)), DF::from(22));
+
+    let mut median = Median::<5>::new();
+    for sample in [5, 1, 4, 2, 3, 9, 9] {
+        median.update(DF::from(sample));
+    }
+    assert_eq!(median.value(), DF::from(4));
+    median.reset();
+    assert_eq!(median.update(DF::new(-1, 2)), DF::new(-1, 2));
+}
+
+#[test]
+fn test_window_min_max() {
+    let mut env = WindowMinMax::<3>::new();
+    assert_eq!((env.min(), env.max()), (DF::zero(), DF::zero()));
+    let samples = [5, 3, 4, 8, 1, 1, 6, 7, 9, 2];
+    let expected = [
+        (5, 5),
+        (3, 5),
+        (3, 5),
+        (3, 8),
+        (1, 8),
+        (1, 8),
+        (1, 6),
+        (1, 7),
+        (6, 9),
+        (2, 9),
+    ];
+    for (sample, (min, max)) in samples.into_iter().zip(expected) {
+        assert_eq!(env.update(DF::from(sample)), (DF::from(min), DF::from(max)));
+    }
+    env.reset();
+    assert_eq!(env.update(DF::new(-1, 3)), (DF::new(-1, 3), DF::new(-1, 3)));
+}
+
+#[test]
+fn test_low_pass() {
+    let mut lpf = LowPass1::new(DF::new(1, 2));
+    assert_eq!(lpf.alpha(), DF::new(1, 2));
+    assert_eq!(lpf.update(DF::from(16)), DF::from(4));
+    assert_eq!(lpf.update(DF::from(16)), DF::from(7));
+    assert_eq!(lpf.update(DF::zero()), DF::new(21, 2));
+    lpf.reset(DF::from(-3));
+    assert_eq!(lpf.value(), DF::from(-3));
+    assert_eq!(lpf.update(DF::from(-3)), DF::from(-3));
+
+    let mut lpf = LowPass1::new(DF::zero());
+    lpf.set_alpha(DF::from(1));
+    assert_eq!(lpf.update(DF::new(5, 3)), DF::new(5, 3));
+
+    let lpf = LowPass1::from_cutoff(DF::from(10), DF::from(1000));
+    assert_eq!(lpf.alpha().round(10), DF::new(60, 10));
+    assert_eq!(
+        LowPass1::from_cutoff(DF::zero(), DF::from(1000)).alpha(),
+        DF::zero()
+    );
+}
+
+#[test]
+fn test_dc_blocker() {
+    let mut dc = DcBlocker::new(DF::new(1, 1));
+    assert_eq!(dc.pole(), DF::new(1, 1));
+    assert_eq!(dc.update(DF::from(8)), DF::from(8));
+    assert_eq!(dc.update(DF::from(8)), DF::from(4));
+    assert_eq!(dc.update(DF::from(8)), DF::from(2));
+    assert_eq!(dc.update(DF::from(4)), DF::from(-3));
+
+    dc.reset(DF::from(100));
+    assert_eq!(dc.update(DF::from(101)), DF::from(1));
+    assert_eq!(dc.update(DF::from(99)), DF::new(-3, 1));
+
+    let mut dc = DcBlocker::default();
+    dc.set_pole(DF::new(255, 8));
+    for _ in 0..4000 {
+        dc.update(DF::from(1000));
+    }
+    assert!(dc.value() > DF::zero() && dc.value() < DF::new(1, 12));
+}
+
+#[test]
+fn test_biquad() {
+    const AVERAGE: Biquad = Biquad::new(
+        [DF::new(1, 2), DF::new(1, 1), DF::new(1, 2)],
+        [DF::zero(); 2],
+    );
+    let mut fir = AVERAGE;
+    assert_eq!(fir.update(DF::from(8)), DF::from(2));
+    assert_eq!(fir.update(DF::from(8)), DF::from(6));
+    assert_eq!(fir.update(DF::from(8)), DF::from(8));
+    assert_eq!(fir.update(DF::zero()), DF::from(6));
+    fir.reset();
+    assert_eq!(fir.value(), DF::zero());
+    assert_eq!(fir.b(), [DF::new(1, 2), DF::new(1, 1), DF::new(1, 2)]);
+
+    let mut iir = Biquad::new(
+        [DF::from(1), DF::zero(), DF::zero()],
+        [DF::new(-1, 1), DF::new(1, 2)],
+    );
+    assert_eq!(iir.a(), [DF::new(-1, 1), DF::new(1, 2)]);
+    assert_eq!(iir.update(DF::from(16)), DF::from(16));
+    assert_eq!(iir.update(DF::zero()), DF::from(8));
+    assert_eq!(iir.update(DF::zero()), DF::zero());
+    assert_eq!(iir.update(DF::zero()), DF::from(-2));
+}
+
+#[test]
+fn test_fir() {
+    let mut fir = Fir::new([DF::from(1), DF::from(2), DF::from(3)]);
+    let impulse = [1, 0, 0, 0, 0].map(|x| fir.update(DF::from(x)));
+    assert_eq!(impulse, [1, 2, 3, 0, 0].map(DF::from));
+    let step = [1, 1, 1, 1].map(|x| fir.update(DF::from(x)));
+    assert_eq!(step, [1, 3, 6, 6].map(DF::from));
+    assert_eq!(fir.update(DF::new(1, 1)), DF::new(11, 1));
+    fir.reset();
+    assert_eq!(fir.value(), DF::zero());
+    assert_eq!(fir.update(DF::from(-2)), DF::from(-2));
+    assert_eq!(fir.taps()[2], DF::from(3));
+
+    let mut decim = Fir::new([DF::new(1, 2); 4]);
+    for _ in 0..8 {
+        decim.update(DF::from(i32::MAX));
+    }
+    assert_eq!(decim.value(), DF::from(i32::MAX));
+}
+
+#[cfg(feature = "float")]
+fn assert_close(actual: &[DF], expected: &[f64]) {
+    for (&val, &reference) in actual.iter().zip(expected) {
+        let err = (f64::from(val) - reference).abs();
+        assert!(err < 2f64.powi(-26), "{} vs {}", val, reference);
+    }
+}
+
+#[cfg(feature = "float")]
+#[test]
+fn test_biquad_presets() {
+    let f0 = DF::new(1, 4);
+    let notch = Biquad::notch(f0, DF::from(1));
+    assert_close(
+        &notch.b(),
+        &[0.8393897287541753, -1.5509899803923666, 0.8393897287541753],
+    );
+    assert_close(&notch.a(), &[-1.5509899803923666, 0.6787794575083506]);
+    let low = Biquad::low_pass(f0, DF::from(1));
+    assert_close(
+        &low.b(),
+        &[
+            0.031947369278996024,
+            0.06389473855799205,
+            0.031947369278996024,
+        ],
+    );
+    let high = Biquad::high_pass(f0, DF::from(1));
+    assert_close(
+        &high.b(),
+        &[0.8074423594751793, -1.6148847189503586, 0.8074423594751793],
+    );
+}
+
+#[test]
+fn test_biquad_notch() {
+    let mut hum = Biquad::notch(DF::new(1, 2), DF::from(4));
+    let mut peak = DF::zero();
+    for n in 0..400 {
+        let out = hum.update([0, 100, 0, -100][n % 4].into());
+        if n > 300 {
+            peak = DF::max(peak, out.abs());
+        }
+    }
+    assert!(peak < DF::new(1, 8));
+
+    let mut dc = Biquad::low_pass(DF::new(1, 5), DF::new(181, 8));
+    for _ in 0..200 {
+        dc.update(DF::from(100));
+    }
+    assert!((dc.value() - DF::from(100)).abs() < DF::new(1, 16));
+}
+
+#[test]
+fn test_kalman() {
+    let mut kf = Kalman1d::new(DF::zero(), DF::from(1), DF::zero(), DF::from(1));
+    assert_eq!(kf.update(DF::from(10)), DF::from(5));
+    assert_eq!(kf.variance(), DF::new(1, 1));
+    kf.predict();
+    assert_eq!(kf.variance(), DF::new(1, 1));
+    assert_eq!(kf.update(DF::from(8)), DF::from(6));
+    assert!((kf.variance() - DF::from_ratio(1, 3, i8::MAX)).abs() < DF::new(1, 30));
+
+    kf.set_process_noise(DF::new(1, 4));
+    assert_eq!(kf.process_noise(), DF::new(1, 4));
+    kf.reset(DF::from(20), DF::zero());
+    kf.predict();
+    assert_eq!(kf.variance(), DF::new(1, 4));
+    for _ in 0..200 {
+        kf.predict();
+        kf.update(DF::from(25));
+    }
+    assert!((kf.estimate() - DF::from(25)).abs() < DF::new(1, 20));
+
+    let mut exact = Kalman1d::new(DF::from(3), DF::zero(), DF::zero(), DF::zero());
+    assert_eq!(exact.update(DF::from(7)), DF::from(3));
+    assert_eq!(exact.measurement_noise(), DF::zero());
+}
+
+#[test]
+fn test_sav_gol() {
+    assert!(SavGol::<5>::new(SavGolFit::Quartic).is_none());
+    assert!(SavGol::<4>::new(SavGolFit::Quadratic).is_none());
+    assert!(SavGol::<0>::new(SavGolFit::Quadratic).is_none());
+
+    // Quadratic samples `t^2` at `t = k / 2` come out exact, delayed.
+    let mut sg = SavGol::<5>::new(SavGolFit::Quadratic).unwrap();
+    assert_eq!(sg.delay(), 2);
+    assert_eq!(sg.fit(), SavGolFit::Quadratic);
+    assert_eq!(sg.value(), DF::zero());
+    assert_eq!(sg.update(DF::from(3)), DF::from(3));
+    assert_eq!(sg.derivative(DF::new(1, 1)), DF::zero());
+    sg.reset();
+    for k in 0..12 {
+        sg.update(DF::new(k * k, 2));
+        if k >= 4 {
+            assert_eq!(sg.value(), DF::new((k - 2) * (k - 2), 2));
+            assert_eq!(sg.derivative(DF::new(1, 1)), DF::from(k - 2));
+        }
+    }
+
+    // A quartic fit follows cubics exactly.
+    let mut sg = SavGol::<9>::new(SavGolFit::Quartic).unwrap();
+    for k in 0..16 {
+        sg.update(DF::from(k * k * k - 5 * k));
+    }
+    assert_eq!(sg.value(), DF::from(11 * 11 * 11 - 55));
+    assert_eq!(sg.derivative(DF::from(1)), DF::from(3 * 11 * 11 - 5));
+
+    // Alternating noise is strongly attenuated.
+    let mut sg = SavGol::<11>::new(SavGolFit::Quadratic).unwrap();
+    let mut sign = 1;
+    for _ in 0..20 {
+        sg.update(DF::from(10 + sign));
+        sign = -sign;
+    }
+    assert!(sg.value().abs_diff(DF::from(10)) < DF::new(1, 2));
+}
@@ -0,0 +1,34 @@
+use dyadic::{DyadicFixed, Rounding, DF};
+
+type Q8 = DyadicFixed<8>;
+
+#[test]
+fn test_fixed_convert() {
+    assert_eq!(Q8::from(DF::new(3, 3)).raw(), 96);
+    assert_eq!(Q8::from(DF::new(-1, 10)).raw(), -1);
+    assert_eq!(Q8::from_df(DF::new(-1, 10), Rounding::Nearest).raw(), 0);
+    assert_eq!(Q8::from(DF::from(1 << 30)), Q8::MAX);
+    assert_eq!(Q8::from_raw(96).to_df(), DF::new(3, 3));
+    assert_eq!(DF::from(DyadicFixed::<-2>::from_raw(3)), DF::from(12));
+}
+
+#[test]
+fn test_fixed_ops() {
+    let a = Q8::from(DF::new(3, 1));
+    let b = Q8::from(DF::new(-5, 2));
+    assert_eq!((a + b).to_df(), DF::new(1, 2));
+    assert_eq!((a - b).to_df(), DF::new(11, 2));
+    assert_eq!((a * b).to_df(), DF::new(-15, 3));
+    assert_eq!((-b).to_df(), DF::new(5, 2));
+    assert_eq!(Q8::from_raw(1) * Q8::from_raw(-1), Q8::from_raw(-1));
+    assert_eq!(Q8::MAX + a, Q8::MAX);
+    assert_eq!(-Q8::MIN, Q8::MAX);
+    assert!(b < a);
+
+    let mut acc = Q8::ZERO;
+    acc += a;
+    acc *= a;
+    acc -= b;
+    assert_eq!(acc.to_df(), DF::new(7, 1));
+    assert_eq!(format!("{:#}", acc), "3.5");
+}
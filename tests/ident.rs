@@ -0,0 +1,48 @@
+use dyadic::ident::FirstOrderFit;
+use dyadic::sim::FirstOrderPlant;
+use dyadic::{assert_df_approx_eq, DF};
+
+/// Step response of a plant from rest, one sample per time step.
+fn record<const DELAY: usize>(
+    mut plant: FirstOrderPlant<DELAY>,
+    start: DF,
+    step: DF,
+    count: i32,
+) -> Vec<(DF, DF)> {
+    let mut samples = vec![(start, plant.value())];
+    for k in 1..count {
+        let time = DF::sum_exact(&[start, plant.dt() * k]);
+        samples.push((time, plant.update(step)));
+    }
+    samples
+}
+
+#[test]
+fn test_first_order_fit() {
+    let dt = DF::new(1, 2);
+    let plant = FirstOrderPlant::<8>::new(DF::new(5, 1), DF::from(3), dt);
+    let samples = record(plant, DF::from(10), DF::from(4), 160);
+    let fit = FirstOrderFit::fit(&samples, DF::from(4)).unwrap();
+    let tol = DF::new(1, 6);
+    assert_df_approx_eq!(fit.gain(), DF::new(5, 1), DF::new(1, 12));
+    assert_df_approx_eq!(fit.tau(), DF::from(3), tol);
+    assert_df_approx_eq!(fit.dead_time(), DF::from(2), tol);
+
+    // A negative step and gain give the same time constants.
+    let plant = FirstOrderPlant::<0>::new(DF::new(-3, 2), DF::new(3, 1), DF::new(1, 3));
+    let samples = record(plant, DF::zero(), DF::from(-2), 120);
+    let fit = FirstOrderFit::fit(&samples, DF::from(-2)).unwrap();
+    assert_df_approx_eq!(fit.gain(), DF::new(-3, 2), DF::new(1, 8));
+    assert_df_approx_eq!(fit.tau(), DF::new(3, 1), DF::new(1, 8));
+    assert_df_approx_eq!(fit.dead_time(), DF::zero(), DF::new(1, 8));
+
+    // The fit parameterizes a matching model.
+    let model = FirstOrderPlant::<0>::new(fit.gain(), fit.tau(), DF::new(1, 3));
+    assert_eq!(model.gain(), fit.gain());
+
+    let flat = [(DF::zero(), DF::from(1)); 4];
+    assert_eq!(FirstOrderFit::fit(&flat, DF::from(1)), None);
+    assert_eq!(FirstOrderFit::fit(&samples[..2], DF::from(-2)), None);
+    assert_eq!(FirstOrderFit::fit(&samples, DF::zero()), None);
+    assert_eq!(FirstOrderFit::fit(&[], DF::from(1)), None);
+}
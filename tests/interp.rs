@@ -0,0 +1,122 @@
+use dyadic::interp::{self, Edge, Lut1d, Lut2d};
+use dyadic::DF;
+
+#[test]
+fn test_lut1d() {
+    // Battery voltage in millivolts to state of charge in percent.
+    let battery = Lut1d::new(
+        [3300, 3600, 3700, 4200].map(DF::from),
+        [0, 10, 50, 100].map(DF::from),
+    )
+    .unwrap();
+    assert_eq!(battery.lookup(DF::from(3000)), DF::zero());
+    assert_eq!(battery.lookup(DF::from(3300)), DF::zero());
+    assert_eq!(battery.lookup(DF::from(3450)), DF::from(5));
+    assert_eq!(battery.lookup(DF::from(3700)), DF::from(50));
+    assert_eq!(battery.lookup(DF::from(3950)), DF::from(75));
+    assert_eq!(battery.lookup(DF::from(4200)), DF::from(100));
+    assert_eq!(battery.lookup(DF::from(5000)), DF::from(100));
+
+    let table = Lut1d::new([0, 4, 8].map(DF::from), [0, 1, -3].map(DF::from)).unwrap();
+    assert_eq!(table.lookup(DF::from(1)), DF::new(1, 2));
+    assert_eq!(table.lookup(DF::from(6)), DF::from(-1));
+    assert_eq!(table.lookup(DF::from(7)), DF::from(-2));
+    assert_eq!(table.lookup(DF::new(-1, 3)), DF::zero());
+
+    assert!(Lut1d::new([0, 2, 2].map(DF::from), [DF::zero(); 3]).is_none());
+    assert!(Lut1d::new([0, 2, 1].map(DF::from), [DF::zero(); 3]).is_none());
+    assert!(Lut1d::<0>::new([], []).is_none());
+    let single = Lut1d::new([DF::from(1)], [DF::from(7)]).unwrap();
+    assert_eq!(single.lookup(DF::from(-5)), DF::from(7));
+    assert_eq!(single.lookup(DF::from(5)), DF::from(7));
+}
+
+#[test]
+fn test_lut1d_uniform() {
+    let table = Lut1d::uniform(DF::from(-1), DF::new(1, 1), [0, 2, 3, -1].map(DF::from)).unwrap();
+    assert_eq!(table.lookup(DF::from(-2)), DF::zero());
+    assert_eq!(table.lookup(DF::from(-1)), DF::zero());
+    assert_eq!(table.lookup(DF::new(-3, 2)), DF::from(1));
+    assert_eq!(table.lookup(DF::zero()), DF::from(3));
+    assert_eq!(table.lookup(DF::new(1, 2)), DF::from(1));
+    assert_eq!(table.lookup(DF::new(1, 1)), DF::from(-1));
+    assert_eq!(table.lookup(DF::from(10)), DF::from(-1));
+
+    // Matches the same table with explicit breakpoints.
+    let explicit = Lut1d::new(
+        [DF::from(-1), DF::new(-1, 1), DF::zero(), DF::new(1, 1)],
+        [0, 2, 3, -1].map(DF::from),
+    )
+    .unwrap();
+    for i in -20..20 {
+        let x = DF::new(i, 4);
+        assert_eq!(table.lookup(x), explicit.lookup(x));
+    }
+
+    let thirds = Lut1d::uniform(DF::zero(), DF::from(3), [0, 3].map(DF::from)).unwrap();
+    assert_eq!(thirds.lookup(DF::from(1)), DF::from(1));
+    assert_eq!(thirds.lookup(DF::from(2)), DF::from(2));
+
+    assert!(Lut1d::uniform(DF::zero(), DF::zero(), [DF::zero(); 2]).is_none());
+    assert!(Lut1d::uniform(DF::zero(), DF::from(-1), [DF::zero(); 2]).is_none());
+}
+
+#[test]
+fn test_lut2d() {
+    let x = [1000, 2000, 4000].map(DF::from);
+    let y = [0, 50, 100].map(DF::from);
+    let values = [[10, 20, 40], [20, 30, 60], [40, 50, 100]].map(|row| row.map(DF::from));
+    let mut map = Lut2d::new(x, y, values, Edge::Clamp).unwrap();
+    assert_eq!(map.edge(), Edge::Clamp);
+    assert_eq!(map.lookup(DF::from(1000), DF::zero()), DF::from(10));
+    assert_eq!(map.lookup(DF::from(2000), DF::from(50)), DF::from(30));
+    assert_eq!(map.lookup(DF::from(1500), DF::zero()), DF::from(15));
+    assert_eq!(map.lookup(DF::from(2000), DF::from(25)), DF::from(25));
+    assert_eq!(map.lookup(DF::from(1500), DF::from(25)), DF::from(20));
+    assert_eq!(map.lookup(DF::from(3000), DF::from(75)), DF::from(60));
+    assert_eq!(map.lookup(DF::from(4000), DF::from(100)), DF::from(100));
+
+    assert_eq!(map.lookup(DF::zero(), DF::from(-10)), DF::from(10));
+    assert_eq!(map.lookup(DF::from(9000), DF::from(200)), DF::from(100));
+    assert_eq!(map.lookup(DF::from(500), DF::from(50)), DF::from(20));
+
+    map.set_edge(Edge::Extrapolate);
+    assert_eq!(map.lookup(DF::from(500), DF::from(50)), DF::from(15));
+    assert_eq!(map.lookup(DF::from(5000), DF::zero()), DF::from(50));
+    assert_eq!(map.lookup(DF::from(1000), DF::from(-50)), DF::zero());
+    assert_eq!(map.lookup(DF::from(2000), DF::from(50)), DF::from(30));
+
+    let line = Lut2d::new(
+        [DF::zero()],
+        y,
+        [[1], [2], [4]].map(|row| row.map(DF::from)),
+        Edge::Extrapolate,
+    )
+    .unwrap();
+    assert_eq!(line.lookup(DF::from(7), DF::from(75)), DF::from(3));
+    assert_eq!(line.lookup(DF::from(-7), DF::from(150)), DF::from(6));
+
+    assert!(Lut2d::new(x, [DF::zero(); 3], values, Edge::Clamp).is_none());
+    assert!(Lut2d::<0, 3>::new(x, [], [], Edge::Clamp).is_none());
+}
+
+#[test]
+fn test_cubic() {
+    let squares = [0, 1, 4, 9, 16].map(DF::from);
+    assert_eq!(interp::cubic(&squares, DF::from(3)), DF::from(9));
+    assert_eq!(interp::cubic(&squares, DF::new(5, 1)), DF::new(25, 2));
+    assert_eq!(interp::cubic(&squares, DF::new(11, 2)), DF::new(121, 4));
+    assert_eq!(interp::cubic(&squares, DF::new(1, 1)), DF::new(5, 4));
+    assert_eq!(interp::cubic(&squares, DF::from(-1)), DF::zero());
+    assert_eq!(interp::cubic(&squares, DF::from(4)), DF::from(16));
+    assert_eq!(interp::cubic(&squares, DF::from(100)), DF::from(16));
+
+    let flat = [DF::from(i32::MAX); 4];
+    assert_eq!(interp::cubic(&flat, DF::new(3, 1)), DF::from(i32::MAX));
+    let step = [-1, -1, 1, 1].map(DF::from);
+    assert_eq!(interp::cubic(&step, DF::new(3, 1)), DF::zero());
+    assert_eq!(interp::cubic(&step, DF::new(5, 2)), DF::new(-19, 5));
+
+    assert_eq!(interp::cubic(&[DF::from(3)], DF::new(1, 1)), DF::from(3));
+    assert_eq!(interp::cubic(&[], DF::from(1)), DF::zero());
+}
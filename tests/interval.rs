@@ -0,0 +1,40 @@
+use dyadic::{DFInterval, DF};
+
+#[test]
+fn test_interval_ops() {
+    let a = DFInterval::new(DF::from(2), DF::from(1));
+    let b = DFInterval::around(DF::from(3), DF::new(1, 2));
+    assert_eq!(a.lo, DF::from(1));
+    assert_eq!(b, DFInterval::new(DF::new(11, 2), DF::new(13, 2)));
+
+    assert_eq!(a + b, DFInterval::new(DF::new(15, 2), DF::new(21, 2)));
+    assert_eq!(a - b, DFInterval::new(DF::new(-9, 2), DF::new(-3, 2)));
+    assert_eq!(a * -b, DFInterval::new(DF::new(-13, 1), DF::new(-11, 2)));
+    assert_eq!(b.width(), DF::new(1, 1));
+    assert_eq!(format!("{}", a), "[1, 2]");
+}
+
+#[test]
+fn test_interval_outward() {
+    let x = DFInterval::point(DF::new(i32::MAX, 0));
+    let sum = x + x + DFInterval::point(DF::new(1, 4));
+    assert!(sum.contains(DF::new(i32::MAX, -1)));
+    assert!(sum.lo < sum.hi);
+
+    let third = DFInterval::point(DF::from_ratio(1, 3, 20));
+    let rounded = third.round_outward(4);
+    assert_eq!(rounded, DFInterval::new(DF::new(5, 4), DF::new(6, 4)));
+    assert!(rounded.contains_interval(third));
+}
+
+#[test]
+fn test_interval_sets() {
+    let a = DFInterval::new(DF::from(0), DF::from(4));
+    let b = DFInterval::new(DF::from(2), DF::from(6));
+    let c = DFInterval::new(DF::from(8), DF::from(9));
+    assert_eq!(a.intersection(b), Some(DFInterval::new(DF::from(2), DF::from(4))));
+    assert_eq!(a.intersection(c), None);
+    assert_eq!(a.hull(c), DFInterval::new(DF::from(0), DF::from(9)));
+    assert!(a.contains(DF::from(4)));
+    assert!(!a.contains(DF::from(5)));
+}
@@ -0,0 +1,93 @@
+use dyadic::{Mat2, Mat3, Vec2, Vec3, DF};
+
+fn v3(x: i32, y: i32, z: i32) -> Vec3 {
+    Vec3::new(DF::from(x), DF::from(y), DF::from(z))
+}
+
+#[test]
+fn test_vec_ops() {
+    let a = v3(1, 2, 3);
+    let b = v3(4, -5, 6);
+    assert_eq!(a + b, v3(5, -3, 9));
+    assert_eq!(a - b, v3(-3, 7, -3));
+    assert_eq!(-a, v3(-1, -2, -3));
+    assert_eq!(a * DF::from(2), v3(2, 4, 6));
+    assert_eq!(a.dot(b), DF::from(12));
+    assert_eq!(a.cross(b), v3(27, 6, -13));
+    assert_eq!(a.norm_sqr(), DF::from(14));
+
+    let p = Vec2::new(DF::from(3), DF::from(4));
+    let q = Vec2::new(DF::from(1), DF::from(0));
+    assert_eq!(p.norm(8), DF::from(5));
+    assert_eq!(p.cross(q), DF::from(-4));
+    assert_eq!(
+        Vec2::lerp(p, q, DF::new(1, 1)),
+        Vec2::new(DF::from(2), DF::from(2))
+    );
+}
+
+#[test]
+fn test_vec_wide() {
+    let big = Vec2::new(DF::from(1 << 30), DF::from(1 << 30));
+    assert_eq!(big.dot(big), DF::new(1, -61));
+    assert_eq!(big.norm(0), DF::from(1518500249));
+
+    let fine = Vec3::new(DF::new(1, 20), DF::new(1, 20), DF::zero());
+    assert_eq!(fine.norm_sqr(), DF::new(1, 39));
+}
+
+fn m3(rows: [[i32; 3]; 3]) -> Mat3 {
+    let [x, y, z] = rows.map(|[a, b, c]| v3(a, b, c));
+    Mat3::from_rows(x, y, z)
+}
+
+#[test]
+fn test_mat_ops() {
+    let a = m3([[1, 2, 3], [0, 1, 4], [5, 6, 0]]);
+    let b = m3([[1, 2, 0], [0, 1, 0], [4, 0, 1]]);
+    assert_eq!(a * b, m3([[13, 4, 3], [16, 1, 4], [5, 16, 0]]));
+    assert_eq!(a * Mat3::identity(), a);
+    assert_eq!(a * v3(1, -1, 2), v3(5, 7, -1));
+    assert_eq!(a.transpose(), m3([[1, 0, 5], [2, 1, 6], [3, 4, 0]]));
+    assert_eq!(a.determinant(), DF::from(1));
+    assert_eq!(b.determinant(), DF::from(1));
+
+    let m = Mat2::from_rows(
+        Vec2::new(DF::from(4), DF::from(7)),
+        Vec2::new(DF::from(2), DF::from(6)),
+    );
+    assert_eq!(m.determinant(), DF::from(10));
+    assert_eq!(m.transpose().transpose(), m);
+    assert_eq!(m * Mat2::identity(), m);
+}
+
+#[test]
+fn test_mat_solve() {
+    let a = m3([[1, 2, 3], [0, 1, 4], [5, 6, 0]]);
+    let inv = a.inverse(8).unwrap();
+    assert_eq!(inv, m3([[-24, 18, 5], [20, -15, -4], [-5, 4, 1]]));
+    assert_eq!(a * inv, Mat3::identity());
+    assert_eq!(a.solve(v3(5, 7, -1), 8), Some(v3(1, -1, 2)));
+    assert_eq!(Mat3::zero().inverse(8), None);
+
+    let m = Mat2::from_rows(
+        Vec2::new(DF::from(4), DF::from(7)),
+        Vec2::new(DF::from(2), DF::from(6)),
+    );
+    assert_eq!(
+        m.inverse(4).unwrap(),
+        Mat2::from_rows(
+            Vec2::new(DF::new(10, 4), DF::new(-11, 4)),
+            Vec2::new(DF::new(-3, 4), DF::new(6, 4)),
+        )
+    );
+    assert_eq!(
+        m.solve(Vec2::new(DF::from(1), DF::from(0)), 10),
+        Some(Vec2::new(DF::new(614, 10), DF::new(-205, 10)))
+    );
+    let singular = Mat2::from_rows(
+        Vec2::new(DF::from(1), DF::from(2)),
+        Vec2::new(DF::from(2), DF::from(4)),
+    );
+    assert_eq!(singular.solve(Vec2::zero(), 8), None);
+}
@@ -0,0 +1,73 @@
+use dyadic::{assert_df_approx_eq, assert_df_eq, df, DF};
+
+#[test]
+fn test_assert_df_eq() {
+    assert_df_eq!(DF::new(2, 2), DF::new(1, 1));
+    assert_df_eq!(DF::from(3), DF::new(6, 1), "{} halves", 6);
+}
+
+#[test]
+#[should_panic(
+    expected = "assertion `left == right` failed\n  left: 0.375 (3/8)\n right: 0.5 (1/2)"
+)]
+fn test_assert_df_eq_fails() {
+    assert_df_eq!(DF::new(3, 3), DF::new(1, 1));
+}
+
+#[test]
+#[should_panic(expected = "assertion `left == right` failed: sample 4\n  left: -1 (-1)")]
+fn test_assert_df_eq_message() {
+    assert_df_eq!(DF::from(-1), DF::zero(), "sample {}", 4);
+}
+
+#[test]
+fn test_assert_df_approx_eq() {
+    assert_df_approx_eq!(DF::new(3, 3), DF::new(1, 1), DF::new(1, 3));
+    assert_df_approx_eq!(DF::new(1, 1), DF::new(3, 3), DF::new(1, 3));
+    assert_df_approx_eq!(
+        DF::from(i32::MAX),
+        DF::from(i32::MIN),
+        DF::new(1, -32),
+        "extremes"
+    );
+}
+
+#[test]
+#[should_panic(expected = "right: 0.5 (1/2)\n  diff: 0.125 > 0.0625")]
+fn test_assert_df_approx_eq_fails() {
+    assert_df_approx_eq!(DF::new(3, 3), DF::new(1, 1), DF::new(1, 4));
+}
+
+#[test]
+fn test_abs_diff() {
+    assert_eq!(DF::from(3).abs_diff(DF::from(-2)), DF::from(5));
+    assert_eq!(DF::from(-2).abs_diff(DF::from(3)), DF::from(5));
+    assert_eq!(
+        DF::from(i32::MAX).abs_diff(DF::from(i32::MIN)),
+        DF::new(1, -32)
+    );
+    assert_eq!(
+        DF::from(1 << 30).abs_diff(DF::new(-1, 30)),
+        DF::from(1073741825)
+    );
+    assert_eq!(DF::new(7, 2).abs_diff(DF::new(7, 2)), DF::zero());
+}
+
+const HALF: DF = df!("1/2");
+
+#[test]
+fn test_df() {
+    assert_eq!(HALF, DF::new(1, 1));
+    assert_eq!(df!("7/2^5"), DF::new(7, 5));
+    assert_eq!(df!(1.375), DF::new(11, 3));
+    assert_eq!(df!(-0.25), DF::new(-1, 2));
+    assert_eq!(df!(3 / 8), DF::new(3, 3));
+    assert_eq!(df!(-3 / 8), DF::new(-3, 3));
+    assert_eq!(df!(-12), DF::from(-12));
+    assert_eq!(df!(1099511627776), DF::new(1, -40));
+
+    // Inexact literals round to nearest at the given precision.
+    assert_eq!(df!(0.1, 16), DF::new(3277, 15));
+    assert_eq!(df!(-1 / 3, 4), DF::new(-5, 4));
+    assert_eq!(df!("2.2", 2), DF::new(9, 2));
+}
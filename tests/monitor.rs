@@ -0,0 +1,103 @@
+use dyadic::monitor::{Alarms, Debounce, Limits, RateOfChange, Threshold};
+use dyadic::DF;
+
+#[test]
+fn test_rate_of_change() {
+    // Sampled every 1/2 s, allowing up to 2 units/s either way.
+    let mut roc = RateOfChange::<4>::new(DF::new(1, 1), DF::from(2), DF::from(-2));
+    assert_eq!(roc.limits(), (DF::from(-2), DF::from(2)));
+    assert_eq!(roc.dt(), DF::new(1, 1));
+    assert_eq!(roc.update(DF::from(10)), DF::zero());
+    assert_eq!(roc.update(DF::from(11)), DF::from(2));
+    assert!(!roc.is_exceeded());
+    assert_eq!(roc.update(DF::from(10)), DF::zero());
+    assert_eq!(roc.update(DF::from(13)), DF::from(2));
+    // The window now drops the first sample.
+    assert_eq!(roc.update(DF::from(17)), DF::from(4));
+    assert!(roc.is_exceeded());
+    assert_eq!(roc.rate(), DF::from(4));
+
+    roc.reset();
+    assert_eq!(roc.rate(), DF::zero());
+    assert!(!roc.is_exceeded());
+    roc.update(DF::from(20));
+    assert_eq!(roc.update(DF::from(18)), DF::from(-4));
+    assert!(roc.is_exceeded());
+    roc.set_limits(DF::from(-5), DF::from(5));
+    assert!(!roc.is_exceeded());
+}
+
+#[test]
+fn test_limits() {
+    // Heater protection: warn at 80 and trip at 90, with 5 and 10 of
+    // hysteresis; the trip latches. Low warning below 10.
+    let mut limits = Limits::new();
+    limits.set_high_warning(Some(Threshold::new(DF::from(80), DF::from(-5), false)));
+    limits.set_high_trip(Some(Threshold::new(DF::from(90), DF::from(10), true)));
+    limits.set_low_warning(Some(Threshold::new(DF::from(10), DF::from(2), false)));
+    assert_eq!(limits.high_warning().unwrap().hysteresis, DF::from(5));
+    assert_eq!(limits.low_trip(), None);
+
+    assert_eq!(limits.update(DF::from(50)), Alarms::NONE);
+    assert_eq!(limits.update(DF::from(80)), Alarms::HIGH_WARNING);
+    assert_eq!(limits.update(DF::new(151, 1)), Alarms::HIGH_WARNING);
+    let tripped = limits.update(DF::from(90));
+    assert_eq!(tripped, Alarms::HIGH_WARNING | Alarms::HIGH_TRIP);
+    assert!(tripped.is_tripped());
+    assert_eq!(tripped.bits(), 0b1010);
+    assert_eq!(Alarms::from_bits(0xFA), tripped);
+
+    // Acknowledging before the signal has dropped to 80 keeps the latch.
+    limits.update(DF::from(85));
+    limits.acknowledge();
+    assert_eq!(limits.status(), Alarms::HIGH_WARNING | Alarms::HIGH_TRIP);
+    // The warning releases at 75, the trip stays latched until acknowledged.
+    assert_eq!(limits.update(DF::from(75)), Alarms::HIGH_TRIP);
+    assert_eq!(limits.update(DF::from(70)), Alarms::HIGH_TRIP);
+    limits.acknowledge();
+    assert!(limits.status().is_empty());
+
+    assert_eq!(limits.update(DF::from(10)), Alarms::LOW_WARNING);
+    assert_eq!(limits.update(DF::from(11)), Alarms::LOW_WARNING);
+    assert_eq!(limits.update(DF::from(12)), Alarms::NONE);
+
+    limits.update(DF::from(100));
+    assert!(limits.status().contains(Alarms::HIGH_TRIP));
+    limits.set_high_trip(None);
+    assert_eq!(limits.status(), Alarms::HIGH_WARNING);
+    limits.reset();
+    assert_eq!(limits.status(), Alarms::NONE);
+    assert_eq!(limits.update(DF::from(100)), Alarms::HIGH_WARNING);
+}
+
+#[test]
+fn test_debounce() {
+    let mut debounce = Debounce::new(3, 1);
+    assert!(!debounce.update(true));
+    assert!(!debounce.update(true));
+    // A dropout restarts the count.
+    assert!(!debounce.update(false));
+    assert!(!debounce.update(true));
+    assert!(!debounce.update(true));
+    assert!(debounce.update(true));
+    assert!(debounce.update(true));
+    assert!(!debounce.update(false));
+    assert!(!debounce.state());
+
+    debounce.reset(true);
+    assert!(debounce.state());
+    debounce.set_ticks(0, 2);
+    assert_eq!(debounce.ticks(), (0, 2));
+    assert!(debounce.update(false));
+    assert!(!debounce.update(false));
+    assert!(debounce.update(true));
+
+    // Sampled every 0.1 s: assert after 1/4 s, deassert after 1 s.
+    let dt = DF::from_ratio(1, 10, 30);
+    let debounce = Debounce::from_durations(DF::new(1, 2), DF::from(1), dt);
+    assert_eq!(debounce.ticks(), (3, 10));
+    let debounce = Debounce::from_durations(DF::from(2), DF::zero(), DF::new(1, 1));
+    assert_eq!(debounce.ticks(), (4, 0));
+    let debounce = Debounce::from_durations(DF::from(2), DF::from(2), DF::zero());
+    assert_eq!(debounce.ticks(), (0, 0));
+}
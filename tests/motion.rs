@@ -0,0 +1,134 @@
+use dyadic::motion::{SCurveProfile, TrapezoidProfile};
+use dyadic::DF;
+
+/// Runs `profile` until it settles, checking the limits on the way, and
+/// returns the number of ticks.
+fn run(profile: &mut TrapezoidProfile, max_v: DF, max_a: DF) -> usize {
+    let mut prev = profile.velocity();
+    for tick in 1..10_000 {
+        let (_, velocity) = profile.update();
+        assert!(velocity.abs() <= max_v, "{}", velocity);
+        if profile.is_done() {
+            return tick;
+        }
+        let dv = DF::sum_exact(&[velocity, -prev]);
+        assert!(dv.abs() <= max_a, "{} -> {}", prev, velocity);
+        prev = velocity;
+    }
+    panic!("profile did not settle");
+}
+
+#[test]
+fn test_trapezoid_profile() {
+    let (max_v, max_a) = (DF::from(4), DF::new(1, 1));
+    let mut profile = TrapezoidProfile::new(max_v, max_a);
+    assert!(profile.is_done());
+    assert_eq!(profile.update(), (DF::zero(), DF::zero()));
+
+    profile.set_target(DF::from(100));
+    assert_eq!(profile.update(), (DF::new(1, 1), DF::new(1, 1)));
+    assert_eq!(profile.update(), (DF::new(3, 1), DF::from(1)));
+    let ticks = run(&mut profile, max_v, max_a);
+    assert!((30..40).contains(&ticks), "{}", ticks);
+    assert_eq!(profile.position(), DF::from(100));
+    assert_eq!(profile.velocity(), DF::zero());
+
+    // Reversing mid-move decelerates before heading back.
+    profile.set_target(DF::zero());
+    for _ in 0..10 {
+        profile.update();
+    }
+    assert_eq!(profile.velocity(), DF::from(-4));
+    profile.set_target(DF::from(200));
+    let (_, velocity) = profile.update();
+    assert_eq!(velocity, DF::new(-7, 1));
+    run(&mut profile, max_v, max_a);
+    assert_eq!(profile.position(), DF::from(200));
+
+    profile.reset(DF::from(-5));
+    assert!(profile.is_done());
+    profile.set_limits(DF::new(1, 2), DF::new(-1, 4));
+    profile.set_target(DF::new(-21, 2));
+    run(&mut profile, DF::new(1, 2), DF::new(1, 4));
+    assert_eq!(profile.position(), DF::new(-21, 2));
+}
+
+/// Runs `profile` until it settles, checking every limit on the way, and
+/// returns the number of ticks.
+fn run_s_curve(profile: &mut SCurveProfile, max_v: DF, max_a: DF, max_j: DF) -> usize {
+    let mut prev = profile.acceleration();
+    for tick in 1..10_000 {
+        let (_, velocity) = profile.update();
+        let accel = profile.acceleration();
+        assert!(velocity.abs() <= max_v, "{}", velocity);
+        assert!(accel.abs() <= max_a, "{}", accel);
+        let jerk = DF::sum_exact(&[accel, -prev]);
+        assert!(jerk.abs() <= max_j, "{} -> {}", prev, accel);
+        prev = accel;
+        if profile.is_done() {
+            assert_eq!(accel, DF::zero());
+            return tick;
+        }
+    }
+    panic!("profile did not settle");
+}
+
+#[test]
+fn test_s_curve_profile() {
+    let (max_v, max_a, max_j) = (DF::from(4), DF::new(1, 1), DF::new(1, 3));
+    let mut profile = SCurveProfile::new(max_v, max_a, max_j);
+    assert!(profile.is_done());
+    assert_eq!(profile.update(), (DF::zero(), DF::zero()));
+
+    profile.set_target(DF::from(100));
+    let ticks = run_s_curve(&mut profile, max_v, max_a, max_j);
+    assert!((30..45).contains(&ticks), "{}", ticks);
+    assert_eq!(profile.position(), DF::from(100));
+    assert_eq!(profile.velocity(), DF::zero());
+
+    // Short moves never reach full acceleration.
+    profile.set_target(DF::new(397, 2));
+    run_s_curve(&mut profile, max_v, max_a, max_j);
+    assert_eq!(profile.position(), DF::new(397, 2));
+
+    // A new target mid-move is taken up once the current move has stopped.
+    profile.set_target(DF::from(-50));
+    for _ in 0..5 {
+        profile.update();
+    }
+    profile.set_target(DF::from(-20));
+    let mut rest = None;
+    while !profile.is_done() {
+        let (position, velocity) = profile.update();
+        if velocity == DF::zero() && rest.is_none() {
+            rest = Some(position);
+        }
+    }
+    assert_eq!(rest, Some(DF::from(-50)));
+    assert_eq!(profile.position(), DF::from(-20));
+
+    profile.reset(DF::from(7));
+    assert!(profile.is_done());
+    assert_eq!(profile.update(), (DF::from(7), DF::zero()));
+
+    let (max_v, max_a, max_j) = (DF::from(30), DF::from(2), DF::new(3, 4));
+    profile.set_limits(max_v, -max_a, max_j);
+    profile.set_target(DF::from(10_007));
+    run_s_curve(&mut profile, max_v, max_a, max_j);
+    assert_eq!(profile.position(), DF::from(10_007));
+
+    profile.set_limits(max_v, DF::zero(), max_j);
+    profile.set_target(DF::zero());
+    profile.update();
+    assert!(!profile.is_done());
+    assert_eq!(profile.position(), DF::from(10_007));
+}
+
+#[test]
+fn test_s_curve_unplannable() {
+    // Far more cruise ticks than the profile can count.
+    let mut profile = SCurveProfile::new(DF::new(1, 100), DF::from(1), DF::from(1));
+    profile.set_target(DF::from(1 << 20));
+    assert_eq!(profile.update(), (DF::zero(), DF::zero()));
+    assert!(!profile.is_done());
+}
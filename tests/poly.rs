@@ -0,0 +1,50 @@
+use dyadic::poly::{self, Polynomial};
+use dyadic::DF;
+
+#[test]
+fn test_eval() {
+    let coeffs = [DF::from(1), DF::from(2), DF::from(3)];
+    assert_eq!(poly::eval(&coeffs, DF::new(1, 1)), DF::new(11, 2));
+    assert_eq!(poly::eval(&coeffs, DF::from(-2)), DF::from(9));
+    assert_eq!(poly::eval(&coeffs, DF::zero()), DF::from(1));
+    assert_eq!(poly::eval(&[], DF::from(5)), DF::zero());
+
+    // (x - 1)^2 with x^2 well outside of i32.
+    let square = [DF::from(1), DF::from(-2), DF::from(1)];
+    assert_eq!(poly::eval(&square, DF::from(46341)), DF::from(2147395600));
+    assert_eq!(
+        poly::eval(&[DF::zero(), DF::zero(), DF::from(1)], DF::new(1, -20)),
+        DF::new(1, -40)
+    );
+
+    // Narrowed once, rounding towards negative infinity.
+    let tiny = DF::new(3, 100);
+    assert_eq!(
+        poly::eval(&[DF::zero(), DF::zero(), DF::from(1)], tiny),
+        DF::zero()
+    );
+    assert_eq!(
+        poly::eval(&[DF::zero(), DF::zero(), DF::from(-1)], tiny),
+        DF::new(-1, 127)
+    );
+}
+
+#[test]
+fn test_polynomial() {
+    // 4th-order Taylor series of e^x around zero.
+    let exp = Polynomial::new([
+        DF::from(1),
+        DF::from(1),
+        DF::new(1, 1),
+        DF::from_ratio(1, 6, 31),
+        DF::from_ratio(1, 24, 31),
+    ]);
+    assert_eq!(exp.eval(DF::zero()), DF::from(1));
+    let approx = exp.eval(DF::new(1, 3));
+    assert!(
+        (approx - DF::new(1188192, 20)).abs() <= DF::new(1, 20),
+        "{}",
+        approx
+    );
+    assert_eq!(Polynomial::<3>::default().eval(DF::from(7)), DF::zero());
+}
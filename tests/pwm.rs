@@ -0,0 +1,68 @@
+use dyadic::pwm::Duty;
+use dyadic::{Rounding, DF};
+
+#[test]
+fn test_to_duty() {
+    assert_eq!(DF::new(1, 1).to_duty(1000), 500);
+    assert_eq!(DF::new(1, 2).to_duty(999), 250);
+    assert_eq!(DF::new(3, 3).to_duty(999), 375);
+    assert_eq!(DF::zero().to_duty(1000), 0);
+    assert_eq!(DF::from(1).to_duty(1000), 1000);
+    assert_eq!(DF::new(-1, 4).to_duty(1000), 0);
+    assert_eq!(DF::from(3).to_duty(1000), 1000);
+    assert_eq!(DF::new(i32::MAX, 31).to_duty(u16::MAX), u16::MAX);
+    assert_eq!(DF::new(1, 100).to_duty(u16::MAX), 0);
+}
+
+#[test]
+fn test_duty() {
+    let ratio = DF::from_ratio(1, 3, 31);
+    assert_eq!(Duty::new(100, Rounding::Nearest).compare(ratio), 33);
+    assert_eq!(Duty::new(100, Rounding::Floor).compare(ratio), 33);
+    assert_eq!(Duty::new(100, Rounding::Ceil).compare(ratio), 34);
+    assert_eq!(Duty::new(101, Rounding::Nearest).compare(ratio), 34);
+    assert_eq!(Duty::new(101, Rounding::TowardZero).compare(ratio), 33);
+
+    let duty = Duty::new(1024, Rounding::Floor);
+    assert_eq!(duty.top(), 1024);
+    assert_eq!(duty.rounding(), Rounding::Floor);
+    assert_eq!(duty.ratio(256, 16), DF::new(1, 2));
+    assert_eq!(duty.ratio(2000, 16), DF::from(1));
+    assert_eq!(Duty::new(3, Rounding::Floor).ratio(1, 8), DF::new(85, 8));
+    assert_eq!(Duty::new(0, Rounding::Floor).ratio(1, 8), DF::zero());
+}
+
+#[cfg(feature = "embedded-hal")]
+#[test]
+fn test_duty_output() {
+    use core::convert::Infallible;
+    use dyadic::pwm::DutyOutput;
+    use embedded_hal::pwm::{ErrorType, SetDutyCycle};
+
+    struct Channel(u16);
+
+    impl ErrorType for Channel {
+        type Error = Infallible;
+    }
+
+    impl SetDutyCycle for Channel {
+        fn max_duty_cycle(&self) -> u16 {
+            999
+        }
+
+        fn set_duty_cycle(&mut self, duty: u16) -> Result<(), Infallible> {
+            self.0 = duty;
+            Ok(())
+        }
+    }
+
+    let mut output = DutyOutput::new(Channel(0), Rounding::Floor);
+    output.set(DF::new(1, 1)).unwrap();
+    assert_eq!(output.inner().0, 499);
+    output.set(DF::from(2)).unwrap();
+    assert_eq!(output.inner().0, 999);
+    output.set(DF::new(-1, 1)).unwrap();
+    assert_eq!(output.inner().0, 0);
+    output.inner_mut().set_duty_cycle_percent(10).unwrap();
+    assert_eq!(output.release().0, 99);
+}
@@ -0,0 +1,24 @@
+use dyadic::{Ratio, DF};
+
+#[test]
+fn test_div_exact() {
+    assert_eq!(DF::from(1).div_exact(DF::from(3)), Some(Ratio::new(1, 3)));
+    assert_eq!(DF::new(3, 2).div_exact(DF::new(-9, 5)), Some(Ratio::new(-8, 3)));
+    assert_eq!(DF::new(5, -3).div_exact(DF::from(10)), Some(Ratio::new(4, 1)));
+    assert_eq!(DF::zero().div_exact(DF::from(7)), Some(Ratio::new(0, 1)));
+    assert_eq!(DF::from(1).div_exact(DF::zero()), None);
+    assert_eq!(DF::new(1, -100).div_exact(DF::from(3)), None);
+    assert_eq!(DF::new(3, -40).div_exact(DF::new(3, 20)), None);
+}
+
+#[test]
+fn test_ratio() {
+    let third = Ratio::new(2, -6);
+    assert_eq!(third.reduced(), Ratio::new(-1, 3));
+    assert_eq!(Ratio::new(i32::MIN, -1).reduced(), Ratio::new(i32::MIN, -1));
+    assert_eq!(third.to_df(8), DF::new(-85, 8));
+    assert_eq!(Ratio::new(1, 3).mul_df(DF::from(3), 8), DF::from(1));
+    assert_eq!(Ratio::new(1, 3).mul_df(DF::new(1, 2), 4), DF::new(1, 4));
+    assert_eq!(Ratio::from(5), Ratio::new(5, 1));
+    assert_eq!(format!("{}", third), "2/-6");
+}
@@ -0,0 +1,226 @@
+#![cfg(feature = "serde")]
+
+use dyadic::DF;
+use serde::de::value::{Error, SeqDeserializer, StrDeserializer};
+use serde::ser::{Impossible, SerializeTuple};
+use serde::{forward_to_deserialize_any, Deserialize, Deserializer, Serialize, Serializer};
+use std::cmp::Ordering;
+
+#[derive(Debug, PartialEq)]
+enum Token {
+    Str(String),
+    Int(i64),
+}
+
+/// Records strings and integers, the only shapes a dyadic fraction uses.
+struct Capture {
+    human: bool,
+    tokens: Vec<Token>,
+}
+
+macro_rules! unsupported {
+    ($($fn:ident($($ty:ty),*) -> $ok:ty;)*) => {
+        $(
+            fn $fn(self, $(_: $ty),*) -> Result<$ok, Error> {
+                Err(serde::ser::Error::custom("unsupported"))
+            }
+        )*
+    };
+}
+
+impl Serializer for &mut Capture {
+    type Ok = ();
+    type Error = Error;
+    type SerializeSeq = Impossible<(), Error>;
+    type SerializeTuple = Self;
+    type SerializeTupleStruct = Impossible<(), Error>;
+    type SerializeTupleVariant = Impossible<(), Error>;
+    type SerializeMap = Impossible<(), Error>;
+    type SerializeStruct = Impossible<(), Error>;
+    type SerializeStructVariant = Impossible<(), Error>;
+
+    fn is_human_readable(&self) -> bool {
+        self.human
+    }
+
+    fn serialize_i8(self, val: i8) -> Result<(), Error> {
+        self.serialize_i64(val as i64)
+    }
+
+    fn serialize_i32(self, val: i32) -> Result<(), Error> {
+        self.serialize_i64(val as i64)
+    }
+
+    fn serialize_i64(self, val: i64) -> Result<(), Error> {
+        self.tokens.push(Token::Int(val));
+        Ok(())
+    }
+
+    fn serialize_str(self, val: &str) -> Result<(), Error> {
+        self.tokens.push(Token::Str(val.into()));
+        Ok(())
+    }
+
+    fn collect_str<T: std::fmt::Display + ?Sized>(self, val: &T) -> Result<(), Error> {
+        self.serialize_str(&val.to_string())
+    }
+
+    fn serialize_tuple(self, _: usize) -> Result<Self, Error> {
+        Ok(self)
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, _: &T) -> Result<(), Error> {
+        Err(serde::ser::Error::custom("unsupported"))
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        _: &T,
+    ) -> Result<(), Error> {
+        Err(serde::ser::Error::custom("unsupported"))
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: &T,
+    ) -> Result<(), Error> {
+        Err(serde::ser::Error::custom("unsupported"))
+    }
+
+    unsupported! {
+        serialize_bool(bool) -> ();
+        serialize_i16(i16) -> ();
+        serialize_u8(u8) -> ();
+        serialize_u16(u16) -> ();
+        serialize_u32(u32) -> ();
+        serialize_u64(u64) -> ();
+        serialize_f32(f32) -> ();
+        serialize_f64(f64) -> ();
+        serialize_char(char) -> ();
+        serialize_bytes(&[u8]) -> ();
+        serialize_none() -> ();
+        serialize_unit() -> ();
+        serialize_unit_struct(&'static str) -> ();
+        serialize_unit_variant(&'static str, u32, &'static str) -> ();
+        serialize_seq(Option<usize>) -> Self::SerializeSeq;
+        serialize_tuple_struct(&'static str, usize) -> Self::SerializeTupleStruct;
+        serialize_tuple_variant(&'static str, u32, &'static str, usize) -> Self::SerializeTupleVariant;
+        serialize_map(Option<usize>) -> Self::SerializeMap;
+        serialize_struct(&'static str, usize) -> Self::SerializeStruct;
+        serialize_struct_variant(&'static str, u32, &'static str, usize) -> Self::SerializeStructVariant;
+    }
+}
+
+impl SerializeTuple for &mut Capture {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, val: &T) -> Result<(), Error> {
+        val.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+/// Forwards to the wrapped deserializer while claiming a compact format.
+struct Compact<D>(D);
+
+impl<'de, D: Deserializer<'de>> Deserializer<'de> for Compact<D> {
+    type Error = D::Error;
+
+    fn deserialize_any<V: serde::de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, D::Error> {
+        self.0.deserialize_any(visitor)
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+fn serialize(val: DF, human: bool) -> Vec<Token> {
+    let mut capture = Capture {
+        human,
+        tokens: Vec::new(),
+    };
+    val.serialize(&mut capture).unwrap();
+    capture.tokens
+}
+
+fn from_str(src: &str) -> Result<DF, Error> {
+    DF::deserialize(StrDeserializer::<Error>::new(src))
+}
+
+fn from_tuple(num: i32, power: i8) -> Result<DF, Error> {
+    let raw = SeqDeserializer::<_, Error>::new([num as i64, power as i64].into_iter());
+    DF::deserialize(Compact(raw))
+}
+
+#[test]
+fn test_human_readable() {
+    let cases = [
+        (DF::new(3, 3), "3/8"),
+        (DF::new(-6, 4), "-3/8"),
+        (DF::new(10, 0), "10"),
+        (DF::zero(), "0"),
+        (DF::new(5, -40), "5497558138880"),
+        (DF::new(1, 64), "1/18446744073709551616"),
+        (DF::new(-7, 100), "-7/2^100"),
+        (DF::new(1, 127), "1/2^127"),
+    ];
+    for (val, text) in cases {
+        assert_eq!(serialize(val, true), [Token::Str(text.into())]);
+        // Compared by value, as parsing may pick a different representation.
+        assert_eq!(from_str(text).map(|res| res.cmp(&val)), Ok(Ordering::Equal));
+    }
+
+    // Too large for the string form, so the tuple is used instead.
+    let huge = DF::new(i32::MAX, -100);
+    let tokens = serialize(huge, true);
+    assert_eq!(tokens, [Token::Int(i32::MAX as i64), Token::Int(-100)]);
+    let raw = SeqDeserializer::<_, Error>::new([i32::MAX as i64, -100].into_iter());
+    assert_eq!(DF::deserialize(raw), Ok(huge));
+
+    assert_eq!(from_str("0.375"), Ok(DF::new(3, 3)));
+    assert!(from_str("1/3").is_err());
+    assert!(from_str("").is_err());
+}
+
+#[test]
+fn test_compact() {
+    let vals = [
+        DF::new(3, 3),
+        DF::new(-6, 4),
+        DF::zero(),
+        DF::new(i32::MIN, -128),
+        DF::new(1, 127),
+    ];
+    for val in vals {
+        let canonical = val.canonical();
+        let expected = [
+            Token::Int(canonical.numerator() as i64),
+            Token::Int(canonical.denominator_power() as i64),
+        ];
+        assert_eq!(serialize(val, false), expected);
+        assert_eq!(
+            from_tuple(canonical.numerator(), canonical.denominator_power()),
+            Ok(canonical)
+        );
+    }
+
+    // Non-canonical tuples are accepted.
+    assert_eq!(from_tuple(6, 4), Ok(DF::new(3, 3)));
+    let short = SeqDeserializer::<_, Error>::new([3i64].into_iter());
+    assert!(DF::deserialize(Compact(short)).is_err());
+}
@@ -0,0 +1,49 @@
+use dyadic::sim::FirstOrderPlant;
+use dyadic::DF;
+
+#[test]
+fn test_first_order_plant() {
+    let mut plant = FirstOrderPlant::<0>::new(DF::from(3), DF::from(2), DF::new(1, 2));
+    assert_eq!(plant.gain(), DF::from(3));
+    assert_eq!(plant.tau(), DF::from(2));
+    assert_eq!(plant.dt(), DF::new(1, 2));
+    assert_eq!(plant.delay(), 0);
+
+    // After one time constant the step response reaches 1 - 1/e.
+    let mut output = DF::zero();
+    for _ in 0..8 {
+        output = plant.update(DF::from(2));
+    }
+    assert!(output > DF::from_ratio(3792723, 1000000, 30));
+    assert!(output < DF::from_ratio(3792724, 1000000, 30));
+    for _ in 0..200 {
+        output = plant.update(DF::from(2));
+    }
+    assert!(DF::sum_exact(&[output, DF::from(-6)]).abs() < DF::new(1, 20));
+    assert_eq!(plant.value(), output);
+
+    plant.reset();
+    assert_eq!(plant.value(), DF::zero());
+
+    let mut static_gain = FirstOrderPlant::<0>::new(DF::new(1, 1), DF::zero(), DF::from(1));
+    assert_eq!(static_gain.update(DF::from(6)), DF::from(3));
+}
+
+#[test]
+fn test_first_order_plant_delay() {
+    let mut delayed = FirstOrderPlant::<3>::new(DF::from(1), DF::from(1), DF::new(1, 3));
+    let mut plain = FirstOrderPlant::<0>::new(DF::from(1), DF::from(1), DF::new(1, 3));
+    assert_eq!(delayed.delay(), 3);
+    for _ in 0..3 {
+        assert_eq!(delayed.update(DF::from(5)), DF::zero());
+    }
+    for i in 0..20 {
+        let input = if i < 10 { DF::from(5) } else { DF::from(-1) };
+        let expected = plain.update(input);
+        let input = if i < 7 { DF::from(5) } else { DF::from(-1) };
+        assert_eq!(delayed.update(input), expected);
+    }
+
+    delayed.reset();
+    assert_eq!(delayed.update(DF::from(5)), DF::zero());
+}
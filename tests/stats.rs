@@ -0,0 +1,73 @@
+use dyadic::stats::{Rms, Running, SlidingRms};
+use dyadic::DF;
+
+#[test]
+fn test_running() {
+    let mut stats = Running::new();
+    assert_eq!(stats.count(), 0);
+    assert_eq!(stats.mean(), DF::zero());
+    assert_eq!(stats.variance(), DF::zero());
+    assert_eq!(stats.min(), None);
+
+    for sample in [2, 4, 4, 4, 5, 5, 7, 9] {
+        stats.push(DF::from(sample));
+    }
+    assert_eq!(stats.count(), 8);
+    assert_eq!(stats.mean(), DF::from(5));
+    assert_eq!(stats.variance(), DF::from(4));
+    assert_eq!(stats.sample_variance(), DF::new(1227133513, 28));
+    assert_eq!(stats.min(), Some(DF::from(2)));
+    assert_eq!(stats.max(), Some(DF::from(9)));
+
+    stats.reset();
+    stats.push(DF::new(-3, 2));
+    assert_eq!(stats.mean(), DF::new(-3, 2));
+    assert_eq!(stats.sample_variance(), DF::zero());
+}
+
+#[test]
+fn test_running_precision() {
+    let mut stats = Running::new();
+    for sample in [1, 2, 3] {
+        stats.push(DF::from(sample));
+    }
+    assert_eq!(stats.mean(), DF::from(2));
+    assert_eq!(stats.variance(), DF::from_ratio(2, 3, i8::MAX));
+
+    let mut stats = Running::new();
+    for _ in 0..1000 {
+        stats.push(DF::from(i32::MAX));
+        stats.push(DF::from(i32::MIN + 1));
+    }
+    assert_eq!(stats.mean(), DF::zero());
+    assert_eq!(stats.variance(), DF::new(i32::MAX - 1, -31));
+}
+
+#[test]
+fn test_rms() {
+    let mut rms = Rms::new();
+    assert_eq!(rms.rms(8), DF::zero());
+    for sample in [3, -4, 3, -4] {
+        rms.push(DF::from(sample));
+    }
+    assert_eq!(rms.count(), 4);
+    assert_eq!(rms.mean_square(), DF::new(25, 1));
+    assert_eq!(rms.rms(8), DF::new(905, 8));
+    rms.reset();
+    rms.push(DF::from(i32::MIN));
+    assert_eq!(rms.rms(0), DF::new(1, -31));
+}
+
+#[test]
+fn test_sliding_rms() {
+    let mut rms = SlidingRms::<2>::new();
+    assert!(rms.is_empty());
+    rms.push(DF::from(6));
+    assert_eq!(rms.rms(8), DF::from(6));
+    rms.push(DF::from(-8));
+    assert_eq!(rms.mean_square(), DF::from(50));
+    rms.push(DF::zero());
+    assert_eq!(rms.len(), 2);
+    assert_eq!(rms.mean_square(), DF::from(32));
+    assert_eq!(rms.rms(4), DF::new(90, 4));
+}
@@ -0,0 +1,47 @@
+use dyadic::interp::Lut1d;
+use dyadic::trig::Angle;
+use dyadic::{assert_df_approx_eq, table, DF};
+
+const SINE: [DF; 9] = table::sine_quarter(24);
+
+const EXP_START: DF = DF::new(-1, 0);
+const EXP_STEP: DF = DF::new(1, 2);
+const EXP: Lut1d<9> = match Lut1d::uniform(
+    EXP_START,
+    EXP_STEP,
+    table::exp_segments(EXP_START, EXP_STEP, 20),
+) {
+    Some(lut) => lut,
+    None => panic!("invalid table"),
+};
+
+#[test]
+fn test_sine_quarter() {
+    let tol = DF::new(1, 22);
+    for (i, &val) in SINE.iter().enumerate() {
+        let angle = Angle::from_degrees(DF::new(45 * i as i32, 2));
+        assert_df_approx_eq!(val, angle.sin_cos(24).0, tol);
+    }
+    assert_eq!(SINE[0], DF::zero());
+    assert_df_approx_eq!(SINE[8], DF::from(1), tol);
+    assert_eq!(table::sine_quarter::<1>(16), [DF::zero()]);
+    assert_eq!(table::sine_quarter::<0>(16), []);
+}
+
+#[test]
+fn test_exp_segments() {
+    let table: [DF; 5] = table::exp_segments(DF::new(-3, 1), DF::new(3, 2), 24);
+    for (i, &val) in table.iter().enumerate() {
+        let x = DF::sum_exact(&[DF::new(-3, 1), DF::new(3 * i as i32, 2)]);
+        assert_eq!(val, x.exp(24));
+    }
+    assert_eq!(table[2], DF::from(1));
+
+    assert_eq!(EXP.lookup(DF::zero()), DF::from(1));
+    assert_df_approx_eq!(
+        EXP.lookup(DF::new(1, 3)),
+        DF::from_ratio(11331, 10000, 20),
+        DF::new(1, 6)
+    );
+    assert_eq!(EXP.lookup(DF::from(5)), DF::from(1).exp(20));
+}
@@ -1,11 +1,12 @@
-use dyadic::DF;
+use dyadic::{BufferTooSmall, Flags, OutOfRange, ParseError, Rounding, DF};
+use std::ops::{Add, Mul};
 
 #[test]
 fn test_add() {
     let a = DF::from(2);
     let b = DF::new(4, 3);
     let c = a + b;
-    let d = c * 1000.into();
+    let d = c * DF::from(1000);
     assert_eq!(2500, d.floor())
 }
 
@@ -14,14 +15,14 @@ fn test_sub() {
     let a = DF::from(2);
     let b = DF::new(4, 3);
     let c = a - b;
-    let d = c * 1000.into();
+    let d = c * DF::from(1000);
     assert_eq!(1500, d.floor())
 }
 
 #[test]
 fn test_mul() {
     let mut a = DF::new(3, 2);
-    a *= 100.into();
+    a *= DF::from(100);
     assert_eq!(75, a.floor())
 }
 
@@ -88,6 +89,25 @@ fn test_scale() {
     assert_eq!(a.scale(-1000), -375);
     assert_eq!(a.scale(100), 37);
     assert_eq!(a.scale(-100), -38);
+
+    // The product is kept exact instead of saturating the numerator.
+    assert_eq!(DF::new(i32::MAX, 20).scale(1000), 2047999);
+    assert_eq!(DF::new(-1_500_000_001, 30).scale(1_000_000), -1396984);
+    assert_eq!(DF::from(3_000_000).scale(1000), i32::MAX);
+    assert_eq!(DF::from(-3_000_000).scale(1000), i32::MIN);
+    assert_eq!(DF::new(1, 100).scale(DF::new(1, 100)), 0);
+    assert_eq!(DF::new(-1, 100).scale(DF::new(1, 100)), -1);
+}
+
+#[test]
+fn test_scale_i64() {
+    assert_eq!(DF::from(3_000_000).scale_i64(1000), 3_000_000_000);
+    assert_eq!(DF::from(i32::MIN).scale_i64(i32::MIN), 1 << 62);
+    assert_eq!(DF::new(i32::MAX, -20).scale_i64(1000), 2_251_799_812_636_672_000);
+    assert_eq!(DF::new(i32::MAX, -40).scale_i64(1), i64::MAX);
+    assert_eq!(DF::new(-1, -100).scale_i64(1), i64::MIN);
+    assert_eq!(DF::new(3, 3).scale_i64(-100), -38);
+    assert_eq!(DF::zero().scale_i64(DF::new(1, -128)), 0);
 }
 
 #[test]
@@ -101,6 +121,11 @@ fn test_div_by_two() {
     let a = DF::new(3, 3);
     assert_eq!(a.scale(100), 37);
     assert_eq!(a.div_by_two().scale(100), 18);
+    assert_eq!(DF::new(6, 127).div_by_two(), DF::new(3, 127));
+    assert_eq!(DF::new(-1, 126).div_by_two(), DF::new(-1, 127));
+    if cfg!(not(all(feature = "strict-overflow", debug_assertions))) {
+        assert_eq!(DF::new(1, 127).div_by_two(), DF::new(1, 127));
+    }
 }
 
 #[test]
@@ -110,3 +135,688 @@ fn test_round() {
     assert_eq!(DF::new(143, 5).round(4), DF::new(71, 4));
     assert_eq!(DF::new(145, 5).round(3), DF::new(9, 1));
 }
+
+#[test]
+fn test_from_ratio() {
+    assert_eq!(DF::from_ratio(3, 7, 8), DF::new(55, 7));
+    assert_eq!(DF::from_ratio(1, 3, 4), DF::new(5, 4));
+    assert_eq!(DF::from_ratio(-1, 3, 4), DF::new(-5, 4));
+    assert_eq!(DF::from_ratio(1, -3, 4), DF::new(-5, 4));
+    assert_eq!(DF::from_ratio(6, 4, 16), DF::new(3, 1));
+    assert_eq!(DF::from_ratio(3, 7, 40), DF::new(920350135, 31));
+    assert_eq!(DF::from_ratio(0, 7, 8), DF::zero());
+}
+
+#[test]
+fn test_from_float() {
+    assert_eq!(DF::from(0.375f32), DF::new(3, 3));
+    assert_eq!(DF::from(-1.5f32), DF::new(-3, 1));
+    assert_eq!(DF::from(1e9f32), DF::from(1_000_000_000));
+    assert_eq!(DF::from(2f32.powi(40)), DF::new(1 << 30, -10));
+    assert_eq!(DF::from(f32::NAN), DF::zero());
+    assert_eq!(DF::from(f32::INFINITY).numerator(), i32::MAX);
+    assert_eq!(DF::from(f32::NEG_INFINITY).numerator(), i32::MIN);
+
+    assert_eq!(DF::try_from(0.375f64), Ok(DF::new(3, 3)));
+    assert_eq!(DF::try_from(-0.0f64), Ok(DF::zero()));
+    assert_eq!(DF::try_from(0.1f64), Ok(DF::new(1717986918, 34)));
+    assert!(DF::try_from(1e40f64).is_ok());
+    assert_eq!(DF::try_from(1e60f64), Err(OutOfRange));
+    assert_eq!(DF::try_from(f64::NAN), Err(OutOfRange));
+    assert_eq!(DF::try_from(f64::NEG_INFINITY), Err(OutOfRange));
+}
+
+#[cfg(feature = "float")]
+#[test]
+fn test_into_float() {
+    assert_eq!(f64::from(DF::new(3, 3)), 0.375);
+    assert_eq!(f32::from(DF::new(-3, 1)), -1.5);
+    assert_eq!(f64::from(DF::new(1, 127)), 2f64.powi(-127));
+    assert_eq!(f64::from(DF::new(i32::MAX, -128)), i32::MAX as f64 * 2f64.powi(128));
+    assert_eq!(f32::from(DF::new(i32::MAX, -128)), f32::INFINITY);
+    assert_eq!(f32::from(DF::new(1, 127)), 2f32.powi(-127));
+    assert_eq!(f64::from(DF::from(0.1f32)), 0.1f32 as f64);
+}
+
+#[test]
+fn test_parse() {
+    assert_eq!("3/8".parse(), Ok(DF::new(3, 3)));
+    assert_eq!("-1.25".parse(), Ok(DF::new(-5, 2)));
+    assert_eq!("7/2^5".parse(), Ok(DF::new(7, 5)));
+    assert_eq!("+42".parse(), Ok(DF::from(42)));
+    assert_eq!("-12/16".parse(), Ok(DF::new(-3, 2)));
+    assert_eq!("0.1".parse(), Ok(DF::new(1717986918, 34)));
+    assert_eq!(DF::parse("0.1", 8), Ok(DF::new(13, 7)));
+    assert_eq!(DF::parse("-0.1", 8), Ok(DF::new(-13, 7)));
+
+    assert_eq!("".parse::<DF>(), Err(ParseError::Empty));
+    assert_eq!("-".parse::<DF>(), Err(ParseError::InvalidDigit));
+    assert_eq!("1.".parse::<DF>(), Err(ParseError::InvalidDigit));
+    assert_eq!("1.5x".parse::<DF>(), Err(ParseError::InvalidDigit));
+    assert_eq!("3/7".parse::<DF>(), Err(ParseError::NotDyadic));
+    assert_eq!("3/0".parse::<DF>(), Err(ParseError::NotDyadic));
+    assert_eq!(
+        "1000000000000000000000000000000000000000".parse::<DF>(),
+        Err(ParseError::OutOfRange)
+    );
+}
+
+#[test]
+fn test_display_decimal() {
+    assert_eq!(format!("{}", DF::new(3, 3)), "3/8");
+    assert_eq!(format!("{:#}", DF::new(3, 3)), "0.375");
+    assert_eq!(format!("{:#}", DF::new(-5, 0)), "-5");
+    assert_eq!(format!("{:#}", DF::new(1, 20)), "0.00000095367431640625");
+    assert_eq!(format!("{:.2}", DF::new(3, 3)), "0.38");
+    assert_eq!(format!("{:.1}", DF::new(-3, 3)), "-0.4");
+    assert_eq!(format!("{:.5}", DF::new(3, 3)), "0.37500");
+    assert_eq!(format!("{:.0}", DF::new(5, 1)), "3");
+    assert_eq!(format!("{:.2}", DF::new(1023, 10)), "1.00");
+    assert_eq!(format!("{:.1}", DF::from(7)), "7.0");
+}
+
+#[test]
+fn test_display_radix() {
+    assert_eq!(format!("{:b}", DF::new(10, 4)), "101 * 2^-3");
+    assert_eq!(format!("{:#b}", DF::new(-5, 3)), "-0b101 * 2^-3");
+    assert_eq!(format!("{:x}", DF::new(255, 0)), "ff * 2^0");
+    assert_eq!(format!("{:#X}", DF::new(171, -4)), "0xAB * 2^4");
+}
+
+#[test]
+fn test_display_exp() {
+    assert_eq!(format!("{:e}", DF::new(3, 3)), "3.75e-1");
+    assert_eq!(format!("{:E}", DF::from(-1250)), "-1.25E3");
+    assert_eq!(format!("{:e}", DF::new(1, 20)), "9.5367431640625e-7");
+    assert_eq!(format!("{:e}", DF::new(1, -100)), "1.267650600228229401496703205376e30");
+    assert_eq!(format!("{:.1e}", DF::new(3, 3)), "3.8e-1");
+    assert_eq!(format!("{:.2e}", DF::from(9999)), "1.00e4");
+    assert_eq!(format!("{:.3e}", DF::from(2)), "2.000e0");
+    assert_eq!(format!("{:e}", DF::zero()), "0e0");
+}
+
+#[test]
+fn test_write_to() {
+    let mut buf = [0u8; 16];
+    assert_eq!(DF::new(-3, 3).write_to(&mut buf), Ok("-3/8"));
+    assert_eq!(DF::from(42).write_to(&mut buf), Ok("42"));
+    assert_eq!(DF::new(3, 3).write_decimal_to(&mut buf, None), Ok("0.375"));
+    assert_eq!(DF::new(3, 3).write_decimal_to(&mut buf, Some(2)), Ok("0.38"));
+    assert_eq!(DF::new(-1, 1).write_decimal_to(&mut buf, Some(3)), Ok("-0.500"));
+
+    let mut small = [0u8; 4];
+    assert_eq!(DF::new(1, 20).write_decimal_to(&mut small, None), Err(BufferTooSmall));
+    assert_eq!(DF::new(-3, 4).write_to(&mut small), Err(BufferTooSmall));
+}
+
+#[test]
+fn test_debug() {
+    assert_eq!(
+        format!("{:?}", DF::new(3, 3)),
+        "DyadicFraction { value: 3/2^3, num: 3, power: 3 }"
+    );
+    assert_eq!(
+        format!("{:?}", DF::from(2).div_by_two()),
+        "DyadicFraction { value: 1/2^0, num: 2, power: 1 }"
+    );
+}
+
+#[test]
+fn test_try_from_int() {
+    assert_eq!(DF::try_from(42u32), Ok(DF::from(42)));
+    assert_eq!(DF::try_from(3u32 << 30), Ok(DF::new(3 << 29, -1)));
+    assert_eq!(DF::try_from(u32::MAX), Err(OutOfRange));
+    assert_eq!(DF::try_from(-5i64), Ok(DF::from(-5)));
+    assert_eq!(DF::try_from(i64::MIN), Ok(DF::new(i32::MIN, -32)));
+    assert_eq!(DF::try_from(i64::MAX), Err(OutOfRange));
+    assert_eq!(DF::try_from(1u64 << 63), Ok(DF::new(1 << 30, -33)));
+    assert_eq!(DF::try_from(0u64), Ok(DF::zero()));
+    assert_eq!(DF::try_from(1000usize), Ok(DF::from(1000)));
+}
+
+#[test]
+fn test_into_wide_int() {
+    assert_eq!(i64::from(DF::new(3, -32)), 3 << 32);
+    assert_eq!(i64::from(DF::new(-7, 1)), -4);
+    assert_eq!(i64::from(DF::new(1, -70)), i64::MAX);
+    assert_eq!(i64::from(DF::new(-1, -70)), i64::MIN);
+    assert_eq!(i128::from(DF::new(1, -70)), 1 << 70);
+    assert_eq!(i128::from(DF::new(-1, -120)), -1 << 120);
+    assert_eq!(i128::from(DF::new(1, -100)), 1 << 100);
+    assert_eq!(i128::from(DF::new(1, -126)), 1 << 126);
+    assert_eq!(i128::from(DF::new(-1, -127)), i128::MIN);
+    assert_eq!(i128::from(DF::new(1, -127)), i128::MAX);
+    assert_eq!(i128::from(DF::new(3, -126)), i128::MAX);
+    assert_eq!(i128::from(DF::new(-3, -126)), i128::MIN);
+    assert_eq!(i128::from(DF::new(i32::MAX, -96)), (i32::MAX as i128) << 96);
+    assert_eq!(i128::from(DF::new(i32::MAX, -97)), i128::MAX);
+    assert_eq!(i128::from(DF::new(5, 100)), 0);
+    assert_eq!(i128::from(DF::new(-5, 100)), -1);
+
+    assert_eq!(DF::new(11, 2).to_int_and_frac(), (2, DF::new(3, 2)));
+    assert_eq!(DF::new(-11, 2).to_int_and_frac(), (-3, DF::new(1, 2)));
+    assert_eq!(DF::from(-8).to_int_and_frac(), (-8, DF::zero()));
+    assert_eq!(DF::new(-1, 40).to_int_and_frac(), (-1, DF::new(i32::MAX, 31)));
+    assert_eq!(DF::new(5, 127).to_int_and_frac(), (0, DF::new(5, 127)));
+    assert_eq!(DF::new(-1, 127).to_int_and_frac(), (-1, DF::new(i32::MAX, 31)));
+}
+
+#[test]
+fn test_bits() {
+    assert_eq!(DF::new(3, 3).to_bits(), 0x0303);
+    assert_eq!(DF::new(-1, -2).to_bits(), 0xff_ffff_fffe);
+    assert_eq!(DF::new(6, 4).to_bits(), DF::new(3, 3).to_bits());
+    for val in [
+        DF::zero(),
+        DF::new(3, 3),
+        DF::new(-5, 127),
+        DF::new(i32::MAX, -128),
+        DF::new(i32::MIN, 0),
+    ] {
+        assert_eq!(DF::from_bits(val.to_bits()), val);
+        assert_eq!(DF::from_bits(val.to_bits()).to_bits(), val.to_bits());
+    }
+}
+
+#[test]
+fn test_bytes() {
+    // The wire format is part of the public contract and must never change.
+    assert_eq!(DF::new(3, 3).to_le_bytes(), [0x03, 0x03, 0x00, 0x00, 0x00]);
+    assert_eq!(DF::new(3, 3).to_be_bytes(), [0x00, 0x00, 0x00, 0x03, 0x03]);
+    assert_eq!(DF::new(-2, -1).to_le_bytes(), [0xff, 0xfe, 0xff, 0xff, 0xff]);
+    assert_eq!(DF::new(-2, -1).to_be_bytes(), [0xff, 0xff, 0xff, 0xfe, 0xff]);
+    assert_eq!(
+        DF::new(i32::MIN + 1, 127).to_le_bytes(),
+        [0x7f, 0x01, 0x00, 0x00, 0x80]
+    );
+
+    for val in [DF::zero(), DF::new(-5, 7), DF::new(i32::MAX, -128)] {
+        assert_eq!(DF::from_le_bytes(val.to_le_bytes()), Some(val));
+        assert_eq!(DF::from_be_bytes(val.to_be_bytes()), Some(val));
+    }
+    assert_eq!(DF::from_le_bytes([0x04, 0x06, 0x00, 0x00, 0x00]), None);
+    assert_eq!(DF::from_be_bytes([0x00, 0x00, 0x00, 0x00, 0x01]), None);
+}
+
+#[test]
+fn test_q_format() {
+    assert_eq!(DF::new(1, 1).to_q15(), 0x4000);
+    assert_eq!(DF::from(1).to_q15(), i16::MAX);
+    assert_eq!(DF::from(-1).to_q15(), i16::MIN);
+    assert_eq!(DF::from(-2).to_q15(), i16::MIN);
+    assert_eq!(DF::new(3, 17).to_q15(), 1);
+    assert_eq!(DF::new(-3, 17).to_q15(), -1);
+    assert_eq!(DF::from_q15(0x4000), DF::new(1, 1));
+    assert_eq!(DF::new(-1, 2).to_q31(), -(1 << 29));
+    assert_eq!(DF::from_q31(1 << 29), DF::new(1, 2));
+
+    let val = DF::new(-11, 4);
+    assert_eq!(val.to_q(3, 2, Rounding::Floor), -3);
+    assert_eq!(val.to_q(3, 2, Rounding::Ceil), -2);
+    assert_eq!(val.to_q(3, 2, Rounding::TowardZero), -2);
+    assert_eq!(val.to_q(3, 2, Rounding::Nearest), -3);
+    assert_eq!(DF::new(5, 3).to_q(3, 2, Rounding::Nearest), 3);
+    assert_eq!(DF::new(-5, 3).to_q(3, 2, Rounding::Nearest), -3);
+    assert_eq!(DF::from(100).to_q(3, 2, Rounding::Nearest), 31);
+    assert_eq!(DF::new(1, 100).to_q(3, 2, Rounding::Ceil), 1);
+    assert_eq!(DF::from_q(-3, 2), DF::new(-3, 2));
+}
+
+#[test]
+fn test_cmp_exact() {
+    assert_eq!(DF::new(3, -2), DF::from(12));
+    assert!(DF::new(1, -31) > DF::from(i32::MAX));
+    assert!(DF::new(-1, -31) < DF::from(i32::MIN + 1));
+    assert!(DF::new(1, 127) > DF::zero());
+    assert!(DF::new(-1, -128) < DF::new(-1, 127));
+    assert!(DF::new(1, -100) > DF::new(i32::MAX, -60));
+}
+
+#[test]
+fn test_flags() {
+    let (sum, flags) = DF::from(3).add_with_flags(DF::new(1, 2));
+    assert_eq!((sum, flags), (DF::new(13, 2), Flags::NONE));
+    assert!(flags.is_empty());
+
+    let (sum, flags) = DF::from(i32::MAX).add_with_flags(DF::from(1));
+    assert_eq!(sum, DF::from(i32::MAX));
+    assert_eq!(flags, Flags::NUMERATOR);
+    let (_, flags) = DF::from(i32::MIN).sub_with_flags(DF::from(1));
+    assert_eq!(flags, Flags::NUMERATOR);
+    let (_, flags) = DF::from(1).add_with_flags(DF::new(1, 40));
+    assert_eq!(flags, Flags::NUMERATOR);
+    let (_, flags) = DF::zero().add_with_flags(DF::new(1, 40));
+    assert!(flags.is_empty());
+
+    let (_, flags) = DF::from(1 << 16).mul_with_flags(DF::from(1 << 16));
+    assert_eq!(flags, Flags::NUMERATOR);
+    let (_, flags) = DF::new(1, 100).mul_with_flags(DF::new(1, 100));
+    assert_eq!(flags, Flags::POWER);
+
+    let mut sticky = Flags::NONE;
+    sticky |= DF::from(2).mul_with_flags(DF::from(3)).1;
+    sticky |= Flags::POWER | Flags::NUMERATOR;
+    assert!(sticky.contains(Flags::POWER));
+    assert!(sticky.contains(Flags::NUMERATOR));
+}
+
+#[cfg(all(feature = "strict-overflow", debug_assertions))]
+#[test]
+#[should_panic(expected = "attempt to add with overflow")]
+fn test_strict_overflow() {
+    assert_eq!(DF::from(3) * DF::new(1, 2), DF::new(3, 2));
+    let _ = DF::from(i32::MAX) + DF::from(1);
+}
+
+#[cfg(all(feature = "strict-overflow", debug_assertions))]
+#[test]
+#[should_panic(expected = "attempt to divide by two with overflow")]
+fn test_strict_div_by_two() {
+    let _ = DF::new(1, 127).div_by_two();
+}
+
+#[test]
+fn test_sum_exact() {
+    let window = [DF::from(i32::MAX); 256];
+    assert_eq!(DF::sum_exact(&window), DF::new(i32::MAX, -8));
+    assert_eq!(DF::sum_exact(&[DF::new(1, 2), DF::new(-3, 4), DF::from(2)]), DF::new(33, 4));
+    assert_eq!(DF::sum_exact(&[DF::from(1 << 30), DF::new(1, 40)]), DF::from(1 << 30));
+    assert_eq!(DF::sum_exact(&[DF::from(1), DF::new(1, 40), DF::from(-1)]), DF::new(1, 40));
+    assert_eq!(DF::sum_exact(&[]), DF::zero());
+}
+
+#[test]
+fn test_dot() {
+    let taps = [DF::new(1, 2), DF::new(1, 1), DF::new(1, 2)];
+    let samples = [DF::from(4), DF::from(-8), DF::new(3, 3)];
+    assert_eq!(DF::dot(&taps, &samples), DF::new(-93, 5));
+    let big = [DF::from(i32::MAX); 4];
+    assert_eq!(DF::dot(&big, &big), DF::new(1073741823, -34));
+    assert_eq!(DF::dot(&taps, &samples[..1]), DF::from(1));
+    assert_eq!(DF::dot(&[], &samples), DF::zero());
+}
+
+#[test]
+fn test_exp2_log2() {
+    let near = |actual: DF, num: i32, power: i8| {
+        assert!((actual - DF::new(num, power)).abs() <= DF::new(1, power), "{}", actual);
+    };
+    assert_eq!(DF::from(3).exp2(16), DF::from(8));
+    assert_eq!(DF::from(-10).exp2(16), DF::new(1, 10));
+    assert_eq!(DF::zero().exp2(0), DF::from(1));
+    near(DF::new(1, 1).exp2(16), 92682, 16);
+    near(DF::new(-5, 2).exp2(16), 27554, 16);
+    near(DF::new(21, 1).exp2(16), 94906266, 16);
+    near(DF::new(-1, 2).exp2(30), 902905651, 30);
+    assert_eq!(DF::from(40).exp2(16), DF::new(1, -40));
+    assert_eq!(DF::from(1000).exp2(16), DF::new(i32::MAX, -128));
+    assert_eq!(DF::from(-1000).exp2(16), DF::zero());
+
+    assert_eq!(DF::from(8).log2(16), DF::from(3));
+    assert_eq!(DF::new(1, 10).log2(16), DF::from(-10));
+    near(DF::from(3).log2(20), 1661954, 20);
+    near(DF::from(10).log2(20), 3483294, 20);
+    near(DF::from_ratio(1, 10, 31).log2(20), -3483294, 20);
+    near(DF::from(12345).log2(20), 14251867, 20);
+    assert_eq!(DF::from(5).log2(0), DF::from(2));
+    assert_eq!(DF::zero().log2(16), DF::new(i32::MIN, -128));
+    assert_eq!(DF::from(-4).log2(16), DF::new(i32::MIN, -128));
+}
+
+#[test]
+fn test_exp_ln() {
+    let near = |actual: DF, num: i32, power: i8| {
+        assert!((actual - DF::new(num, power)).abs() <= DF::new(1, power), "{}", actual);
+    };
+    assert_eq!(DF::zero().exp(16), DF::from(1));
+    near(DF::from(1).exp(16), 178145, 16);
+    near(DF::new(-3, 1).exp(20), 233969, 20);
+    near(DF::from(10).exp(8), 5638775, 8);
+    near(DF::new(1, 2).exp(30), 1378711793, 30);
+    assert_eq!(DF::from(1000).exp(16), DF::new(i32::MAX, -128));
+    assert_eq!(DF::from(-1000).exp(16), DF::zero());
+    assert_eq!(DF::new(1, -40).exp(16), DF::new(i32::MAX, -128));
+    assert_eq!(DF::new(-1, -40).exp(16), DF::zero());
+
+    assert_eq!(DF::from(1).ln(16), DF::zero());
+    near(DF::from(2).ln(20), 726817, 20);
+    near(DF::from(10).ln(20), 2414435, 20);
+    near(DF::from_ratio(1, 10, 31).ln(20), -2414435, 20);
+    near(DF::from(1_000_000).ln(24), 231785805, 24);
+    near(DF::from(3).ln(30), 1179625963, 30);
+    assert_eq!(DF::zero().ln(16), DF::new(i32::MIN, -128));
+    assert_eq!(DF::from(-1).ln(16), DF::new(i32::MIN, -128));
+}
+
+#[test]
+fn test_consts() {
+    use dyadic::consts::*;
+
+    // Rounded to nearest at a denominator of 2^16.
+    let exact = [
+        (FRAC_PI_6, core::f64::consts::FRAC_PI_6),
+        (FRAC_PI_8, core::f64::consts::FRAC_PI_8),
+        (FRAC_1_SQRT_PI, 0.5641895835477563),
+        (SQRT_3, 1.7320508075688772),
+        (FRAC_1_SQRT_3, 0.5773502691896257),
+        (CBRT_2, 1.2599210498948732),
+        (EGAMMA, 0.5772156649015329),
+        (FRAC_1_3, 1.0 / 3.0),
+        (FRAC_1_5, 0.2),
+        (FRAC_1_10, 0.1),
+    ];
+    for (approx, val) in exact {
+        let error = DF::sum_exact(&[approx, -DF::try_from(val).unwrap()]);
+        assert!(error.abs() <= DF::new(1, 17), "{approx:?} != {val}");
+    }
+}
+
+#[test]
+fn test_consts_at_precision() {
+    use dyadic::consts;
+
+    assert_eq!(consts::pi(i8::MAX), DF::new(1686629713, 29));
+    assert_eq!(consts::pi(16), DF::new(205887, 16));
+    assert_eq!(consts::pi(10), consts::PI);
+    assert_eq!(consts::pi(0), DF::from(3));
+    assert_eq!(consts::pi(-2), DF::from(4));
+    assert_eq!(consts::tau(i8::MAX), DF::new(1686629713, 28));
+    assert_eq!(consts::e(16), consts::E);
+    assert_eq!(consts::e(-1), DF::from(2));
+    assert_eq!(consts::sqrt_2(i8::MAX), DF::new(759250125, 29));
+    assert_eq!(consts::ln_2(i8::MAX), DF::new(372130559, 29));
+    assert_eq!(consts::ln_10(i8::MAX), DF::new(1236190959, 29));
+    assert_eq!(consts::log2_e(i8::MAX), DF::new(1549082005, 30));
+    assert_eq!(consts::log2_10(i8::MAX), DF::new(891723283, 28));
+    assert_eq!(consts::log2_10(16), DF::new(108853, 15));
+
+    const HALF_TURN: DF = consts::pi(20);
+    assert_eq!(HALF_TURN, DF::new(3294199, 20));
+}
+
+#[test]
+fn test_percent() {
+    assert_eq!(DF::from_percent(50), DF::new(1, 1));
+    assert_eq!(DF::from_percent(-125), DF::new(-5, 2));
+    assert_eq!(DF::from_percent(0), DF::zero());
+    assert_eq!(DF::from_percent(1), DF::new(687194767, 36));
+    assert_eq!(DF::from_permille(375), DF::new(3, 3));
+    assert_eq!(DF::from_permille(1), DF::new(274877907, 38));
+    assert_eq!(DF::from_ppm(15625), DF::new(1, 6));
+    assert_eq!(DF::from_ppm(-1), DF::new(-562949953, 49));
+    assert_eq!(DF::from_ppm(i32::MAX), DF::new(562949953, 18));
+
+    assert_eq!(DF::new(3, 3).to_percent(), DF::new(75, 1));
+    assert_eq!(DF::new(3, 3).to_permille(), DF::from(375));
+    assert_eq!(DF::new(1, 6).to_ppm(), DF::from(15625));
+    assert_eq!(DF::from_ppm(250).to_ppm(), DF::from(250));
+    assert_eq!(DF::from_percent(7).to_percent(), DF::from(7));
+    assert_eq!(DF::from(i32::MAX).to_ppm(), DF::new(i32::MAX, 0).to_ppm());
+}
+
+#[test]
+fn test_db() {
+    assert_eq!(DF::from(1).to_db(16), DF::zero());
+    assert_eq!(DF::from(1000).to_db(16), DF::from(60));
+    assert_eq!(DF::new(1, 1).to_db(16), DF::new(-394566, 16));
+    assert_eq!(DF::from(2).to_db(i8::MAX), DF::new(1616142483, 28));
+    assert_eq!(DF::from(100).to_db_power(16), DF::from(20));
+    assert_eq!(DF::from(2).to_db_power(16), DF::new(197283, 16));
+    assert_eq!(DF::zero().to_db(16), DF::new(i32::MIN, -128));
+    assert_eq!(DF::from(-1).to_db_power(16), DF::new(i32::MIN, -128));
+
+    assert_eq!(DF::from_db(DF::zero(), 16), DF::from(1));
+    assert_eq!(DF::from_db(DF::from(-40), 16), DF::new(655, 16));
+    assert_eq!(DF::from_db(DF::from(6), 16), DF::new(65381, 15));
+    assert_eq!(DF::from_db_power(DF::from(-3), 16), DF::new(32846, 16));
+    assert_eq!(DF::from_db_power(DF::from(30), 16), DF::from(1000));
+    assert_eq!(DF::from_db(DF::from(1000), 16), DF::new(i32::MAX, -128));
+    assert_eq!(DF::from_db(DF::from(-1000), 16), DF::zero());
+
+    for db in [-60, -6, 0, 3, 20, 100] {
+        let gain = DF::from_db(DF::from(db), i8::MAX);
+        let error = DF::sum_exact(&[gain.to_db(20), DF::from(-db)]);
+        assert!(error.abs() <= DF::new(1, 18), "{db} dB");
+    }
+}
+
+#[test]
+fn test_display_extreme() {
+    assert_eq!(DF::new(1, -40).to_string(), "1099511627776");
+    assert_eq!(DF::new(-3, -32).to_string(), "-12884901888");
+    assert_eq!(DF::new(1, 40).to_string(), "1/1099511627776");
+    assert_eq!(
+        DF::new(-1, 127).to_string(),
+        "-1/170141183460469231731687303715884105728"
+    );
+    assert_eq!(
+        DF::new(i32::MAX, -128).to_string(),
+        "730750818325169092180903952894766902396198060032"
+    );
+    assert_eq!(
+        format!("{:#}", DF::new(i32::MIN, -128)),
+        "-730750818665451459101842416358141509827966271488"
+    );
+    assert_eq!(
+        format!("{:e}", DF::new(i32::MAX, -128)),
+        "7.30750818325169092180903952894766902396198060032e47"
+    );
+    assert_eq!(format!("{:.3e}", DF::new(i32::MAX, -128)), "7.308e47");
+    assert_eq!(
+        format!("{:#}", DF::new(1, 127)),
+        "0.0000000000000000000000000000000000000058774717541114375398436826861112283890933277838604376075437585313920862972736358642578125"
+    );
+    assert_eq!(format!("{:.2}", DF::new(1, 127)), "0.00");
+    assert_eq!(format!("{:.0}", DF::new(19, 1)), "10");
+    assert_eq!(format!("{:.1}", DF::new(-1999, 10)), "-2.0");
+    assert_eq!(
+        format!("{:e}", DF::new(1, 127)),
+        "5.8774717541114375398436826861112283890933277838604376075437585313920862972736358642578125e-39"
+    );
+
+    let mut buf = [0u8; 64];
+    assert_eq!(
+        DF::new(i32::MAX, -128).write_to(&mut buf),
+        Ok("730750818325169092180903952894766902396198060032")
+    );
+    assert_eq!(
+        DF::new(1, -100).write_decimal_to(&mut buf, Some(1)),
+        Ok("1267650600228229401496703205376.0")
+    );
+}
+
+#[test]
+fn test_unsigned() {
+    assert_eq!(DF::new(5, 1).to_u8_clamped(Rounding::Floor), 2);
+    assert_eq!(DF::new(5, 1).to_u8_clamped(Rounding::Nearest), 3);
+    assert_eq!(DF::from(300).to_u8_clamped(Rounding::Nearest), 255);
+    assert_eq!(DF::new(-1, 1).to_u8_clamped(Rounding::Nearest), 0);
+    assert_eq!(DF::new(-1, 1).to_u8_clamped(Rounding::TowardZero), 0);
+    assert_eq!(DF::new(131071, 1).to_u16_clamped(Rounding::Ceil), 65535);
+    assert_eq!(DF::new(131069, 1).to_u16_clamped(Rounding::Ceil), 65535);
+    assert_eq!(DF::new(131069, 1).to_u16_clamped(Rounding::Floor), 65534);
+    assert_eq!(DF::new(1, -31).to_u32_clamped(Rounding::Floor), 1 << 31);
+    assert_eq!(DF::new(1, -40).to_u32_clamped(Rounding::Floor), u32::MAX);
+    assert_eq!(DF::from(i32::MIN).to_u32_clamped(Rounding::Floor), 0);
+
+    assert_eq!(u8::try_from(DF::new(511, 1)), Ok(255));
+    assert_eq!(u8::try_from(DF::from(256)), Err(OutOfRange));
+    assert_eq!(u16::try_from(DF::new(-1, 4)), Err(OutOfRange));
+    assert_eq!(u16::try_from(DF::zero()), Ok(0));
+    assert_eq!(u32::try_from(DF::new(i32::MAX, -1)), Ok(u32::MAX - 1));
+    assert_eq!(u32::try_from(DF::new(1, -32)), Err(OutOfRange));
+
+    // Large unsigned values convert exactly when representable.
+    assert_eq!(DF::try_from(u32::MAX), Err(OutOfRange));
+    assert_eq!(DF::try_from(0xFFFF_0000u32), Ok(DF::new(0xFFFF, -16)));
+}
+
+#[test]
+fn test_int_ops() {
+    let err = DF::new(3, 2);
+    assert_eq!(err * 3 + DF::new(1, 1), DF::new(11, 2));
+    assert_eq!(err + 2, DF::new(11, 2));
+    assert_eq!(err - 1, DF::new(-1, 2));
+    assert_eq!(2 + err, DF::new(11, 2));
+    assert_eq!(1 - err, DF::new(1, 2));
+    assert_eq!(-4 * err, DF::from(-3));
+    assert_eq!((err * 1000).floor(), 750);
+    assert_eq!(err * 4i16, DF::from(3));
+    assert_eq!(err - 1i8, DF::new(-1, 2));
+    assert_eq!(2i8 - err, DF::new(5, 2));
+    assert_eq!(-2i16 * err, DF::new(-3, 1));
+
+    let mut acc = DF::new(1, 1);
+    acc += 3;
+    acc *= -2;
+    acc -= 1;
+    assert_eq!(acc, DF::from(-8));
+    acc *= 3i16;
+    acc += 4i8;
+    assert_eq!(acc, DF::from(-20));
+
+    // Same results as converting first.
+    let fine = DF::new(12345, 20);
+    assert_eq!(fine + 1000, fine + DF::from(1000));
+    assert_eq!(fine * 1000, fine * DF::from(1000));
+    assert_eq!(-1000 - fine, DF::from(-1000) - fine);
+    assert_eq!(fine * i16::MIN, fine * DF::from(i16::MIN));
+    assert_eq!(i8::MAX - fine, DF::from(i8::MAX) - fine);
+}
+
+/// Generic code that only holds references, as with the standard numeric types.
+fn mul_add_refs<T>(a: &T, b: &T, c: &T) -> T
+where
+    for<'a> &'a T: Mul<&'a T, Output = T>,
+    for<'a> T: Add<&'a T, Output = T>,
+{
+    a * b + c
+}
+
+#[test]
+fn test_ref_ops() {
+    let a = DF::new(3, 2);
+    let b = DF::new(-5, 3);
+    assert_eq!(mul_add_refs(&a, &b, &a), a * b + a);
+    assert_eq!(-&b, DF::new(5, 3));
+
+    let mut acc = a;
+    acc += &b;
+    acc -= &a;
+    acc *= &b;
+    assert_eq!(acc, b * b);
+
+    let values = [a, b, DF::from(2)];
+    let total = values.iter().fold(DF::zero(), |acc, x| acc + x);
+    assert_eq!(total, DF::new(17, 3));
+    let offsets: Vec<DF> = values.iter().map(|x| x - DF::new(1, 1)).collect();
+    assert_eq!(offsets, [DF::new(1, 2), DF::new(-9, 3), DF::new(3, 1)]);
+    let dot = values.iter().zip(&values).fold(DF::zero(), |acc, (x, y)| acc + x * y);
+    assert_eq!(dot, DF::sum_exact(&[a * a, b * b, DF::from(4)]));
+}
+
+#[test]
+fn test_ilog2() {
+    assert_eq!(DF::zero().ilog2(), None);
+    assert_eq!(DF::from(1).ilog2(), Some(0));
+    assert_eq!(DF::from(255).ilog2(), Some(7));
+    assert_eq!(DF::from(-256).ilog2(), Some(8));
+    assert_eq!(DF::new(3, 4).ilog2(), Some(-3));
+    assert_eq!(DF::new(1, 127).ilog2(), Some(-127));
+    assert_eq!(DF::new(i32::MIN, -128).ilog2(), Some(159));
+    assert_eq!(DF::new(i32::MAX, -128).ilog2(), Some(158));
+
+    assert_eq!(DF::zero().magnitude_bits(), i16::MIN);
+    assert_eq!(DF::from(255).magnitude_bits(), 8);
+    assert_eq!(DF::from(256).magnitude_bits(), 9);
+    assert_eq!(DF::new(-1, 1).magnitude_bits(), 0);
+    assert_eq!(DF::new(7, 10).magnitude_bits(), -7);
+}
+
+#[test]
+fn test_parse_exact() {
+    assert_eq!(DF::parse_exact("1.375"), Ok(DF::new(11, 3)));
+    assert_eq!(DF::parse_exact("-3/8"), Ok(DF::new(-3, 3)));
+    assert_eq!(DF::parse_exact("7/2^5"), Ok(DF::new(7, 5)));
+    assert_eq!(DF::parse_exact("2.500"), Ok(DF::new(5, 1)));
+    assert_eq!(DF::parse_exact("-0.0"), Ok(DF::zero()));
+    assert_eq!(DF::parse_exact("-2147483648"), Ok(DF::from(i32::MIN)));
+    assert_eq!(DF::parse_exact("1099511627776"), Ok(DF::new(1, -40)));
+    assert_eq!(DF::parse_exact("1/2^127"), Ok(DF::new(1, 127)));
+
+    assert_eq!(DF::parse_exact("0.1"), Err(ParseError::NotDyadic));
+    assert_eq!(DF::parse_exact("1/3"), Err(ParseError::NotDyadic));
+    assert_eq!(DF::parse_exact("1/2^128"), Err(ParseError::OutOfRange));
+    assert_eq!(DF::parse_exact("4294967297"), Err(ParseError::OutOfRange));
+    assert_eq!(
+        DF::parse_exact("0.0000000000000000000000000000000000000011"),
+        Err(ParseError::OutOfRange)
+    );
+    assert_eq!(DF::parse_exact("1."), Err(ParseError::InvalidDigit));
+    // Digits beyond the parsed precision are dropped when rounding.
+    assert_eq!(
+        DF::parse("1.0000000000000000000000000000000000000000001", 8),
+        Ok(DF::from(1))
+    );
+}
+
+#[test]
+fn test_display_flags() {
+    assert_eq!(format!("{:>8}", DF::new(3, 3)), "     3/8");
+    assert_eq!(format!("{:<6}|", DF::new(-3, 3)), "-3/8  |");
+    assert_eq!(format!("{:^7}", DF::from(5)), "   5   ");
+    assert_eq!(format!("{:*^8}", DF::new(-1, 1)), "**-1/2**");
+    assert_eq!(format!("{:+}", DF::new(3, 3)), "+3/8");
+    assert_eq!(format!("{:+}", DF::zero()), "+0");
+    assert_eq!(format!("{:+}", DF::new(-3, 3)), "-3/8");
+    assert_eq!(format!("{:06}", DF::new(-3, 3)), "-003/8");
+    assert_eq!(format!("{:2}", DF::new(3, 3)), "3/8");
+
+    // Decimal forms.
+    assert_eq!(format!("{:>8.2}", DF::new(3, 3)), "    0.38");
+    assert_eq!(format!("{:<#8}|", DF::new(-3, 3)), "-0.375  |");
+    assert_eq!(format!("{:+#}", DF::from(7)), "+7");
+    assert_eq!(format!("{:+08.3}", DF::new(3, 3)), "+000.375");
+    assert_eq!(format!("{:>7e}", DF::new(3, 3)), "3.75e-1");
+    assert_eq!(format!("{:>+9.1e}", DF::new(3, 3)), "  +3.8e-1");
+    assert_eq!(format!("{:08.1E}", DF::from(-1250)), "-001.3E3");
+
+    // Widths line up mixed signs in a column.
+    let rows: Vec<String> = [DF::new(5, 1), DF::new(-1, 2), DF::from(12)]
+        .iter()
+        .map(|val| format!("{:>6.2}|", val))
+        .collect();
+    assert_eq!(rows, ["  2.50|", " -0.25|", " 12.00|"]);
+}
+
+#[test]
+fn test_round_with_remainder() {
+    assert_eq!(DF::new(11, 4).round_with_remainder(2), (DF::new(3, 2), DF::new(-1, 4)));
+    assert_eq!(DF::new(9, 4).round_with_remainder(2), (DF::new(1, 1), DF::new(1, 4)));
+    // Ties go away from zero.
+    assert_eq!(DF::new(3, 2).round_with_remainder(1), (DF::from(1), DF::new(-1, 2)));
+    assert_eq!(DF::new(-3, 2).round_with_remainder(1), (DF::from(-1), DF::new(1, 2)));
+    assert_eq!(DF::new(-5, 3).round_with_remainder(0), (DF::from(-1), DF::new(3, 3)));
+    assert_eq!(DF::from(13).round_with_remainder(-2), (DF::from(12), DF::from(1)));
+    assert_eq!(DF::new(3, 3).round_with_remainder(5), (DF::new(3, 3), DF::zero()));
+    assert_eq!(DF::new(1, 100).round_with_remainder(0), (DF::zero(), DF::new(1, 100)));
+    assert_eq!(
+        DF::new(i32::MIN, 40).round_with_remainder(8),
+        (DF::new(-1, 8), DF::new(1, 9))
+    );
+    assert_eq!(
+        DF::new(i32::MAX, 10).round_with_remainder(0),
+        (DF::from(2097152), DF::new(-1, 10))
+    );
+
+    // Diffusing the error keeps the running total exact.
+    let step = DF::new(5, 6);
+    let mut carry = DF::zero();
+    let mut total = DF::zero();
+    for _ in 0..64 {
+        let (out, rem) = DF::sum_exact(&[step, carry]).round_with_remainder(2);
+        carry = rem;
+        total = DF::sum_exact(&[total, out]);
+    }
+    assert_eq!(DF::sum_exact(&[total, carry]), DF::from(5));
+}
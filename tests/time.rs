@@ -0,0 +1,78 @@
+use dyadic::time::{secs_to_ticks, ticks_to_secs, Frequency, Period};
+use dyadic::{Rounding, DF};
+
+#[test]
+fn test_secs_to_ticks() {
+    const CLOCK: u32 = 72_000_000;
+    assert_eq!(
+        secs_to_ticks(DF::from(1), CLOCK, Rounding::Nearest),
+        72_000_000
+    );
+    assert_eq!(secs_to_ticks(DF::new(1, 10), CLOCK, Rounding::Floor), 70312);
+    assert_eq!(
+        secs_to_ticks(DF::new(1, 10), CLOCK, Rounding::Nearest),
+        70313
+    );
+    assert_eq!(secs_to_ticks(DF::new(1, 10), CLOCK, Rounding::Ceil), 70313);
+    assert_eq!(
+        secs_to_ticks(DF::from_ratio(1, 1000, 40), CLOCK, Rounding::Nearest),
+        72_000
+    );
+    assert_eq!(
+        secs_to_ticks(DF::from(3600), CLOCK, Rounding::Nearest),
+        259_200_000_000
+    );
+    assert_eq!(secs_to_ticks(DF::new(-1, 1), CLOCK, Rounding::Nearest), 0);
+    assert_eq!(
+        secs_to_ticks(DF::new(1, -100), CLOCK, Rounding::Nearest),
+        u64::MAX
+    );
+    assert_eq!(secs_to_ticks(DF::new(1, 100), CLOCK, Rounding::Ceil), 1);
+    assert_eq!(secs_to_ticks(DF::from(5), 0, Rounding::Nearest), 0);
+}
+
+#[test]
+fn test_ticks_to_secs() {
+    const CLOCK: u32 = 72_000_000;
+    assert_eq!(ticks_to_secs(72_000_000, CLOCK, 16), DF::from(1));
+    assert_eq!(
+        ticks_to_secs(36_000, CLOCK, i8::MAX),
+        DF::from_ratio(1, 2000, i8::MAX)
+    );
+    assert_eq!(ticks_to_secs(1, CLOCK, 16), DF::zero());
+    assert_eq!(ticks_to_secs(u64::MAX, 1, 0), DF::new(1, -64));
+    assert_eq!(ticks_to_secs(5, 0, 0), DF::new(i32::MAX, -128));
+
+    // One rounding on the way there and back.
+    for ms in [1, 7, 250, 1000, 60_000] {
+        let secs = ticks_to_secs(72_000 * ms, CLOCK, i8::MAX);
+        assert_eq!(secs_to_ticks(secs, CLOCK, Rounding::Nearest), 72_000 * ms);
+    }
+}
+
+#[test]
+fn test_frequency_period() {
+    let pwm = Frequency::new(DF::from(20_000));
+    assert_eq!(pwm.hz(), DF::from(20_000));
+    let period = pwm.period(i8::MAX);
+    assert_eq!(period.secs(), DF::from_ratio(1, 20_000, i8::MAX));
+    assert_eq!(period.frequency(16), pwm);
+    assert_eq!(pwm.cycles(DF::new(1, 1), Rounding::Nearest), 10_000);
+    assert_eq!(pwm.cycles(DF::new(1, 10), Rounding::Floor), 19);
+    assert_eq!(pwm.cycles(DF::new(1, 10), Rounding::Ceil), 20);
+    assert_eq!(pwm.cycles(DF::from(-1), Rounding::Nearest), 0);
+
+    let loop_rate = Frequency::new(DF::from(1024));
+    assert_eq!(loop_rate.period(i8::MAX), Period::new(DF::new(1, 10)));
+    assert_eq!(loop_rate.period(4), Period::new(DF::zero()));
+    assert_eq!(Period::new(DF::new(1, 10)).duration(3072), DF::from(3));
+    assert_eq!(
+        Period::new(DF::new(3, 1)).frequency(i8::MAX).hz(),
+        DF::from_ratio(2, 3, i8::MAX)
+    );
+    assert_eq!(Period::new(DF::from(1)).duration(u32::MAX), DF::new(1, -32));
+    assert_eq!(
+        Frequency::default().period(16).secs(),
+        DF::new(i32::MAX, -128)
+    );
+}
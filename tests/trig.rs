@@ -0,0 +1,114 @@
+use dyadic::trig::{self, Angle};
+use dyadic::DF;
+
+fn assert_near(actual: DF, expected: i32, power: i8) {
+    let err = (actual - DF::new(expected, power)).abs();
+    assert!(
+        err <= DF::new(2, power),
+        "{} vs {}/2^{}",
+        actual,
+        expected,
+        power
+    );
+}
+
+#[test]
+fn test_sin_cos() {
+    let cases = [
+        (DF::new(1, 1), 502714, 920212),
+        (DF::from(1), 882346, 566548),
+        (DF::from(2), 953467, -436362),
+        (DF::from(3), 147975, -1038082),
+        (DF::new(-5, 1), -627544, -840060),
+        (DF::from(100), -530963, 904207),
+        (DF::from(1000000), -366995, 982256),
+    ];
+    for (angle, sin, cos) in cases {
+        let (s, c) = trig::sin_cos(angle, 20);
+        assert_near(s, sin, 20);
+        assert_near(c, cos, 20);
+        assert_eq!(trig::sin(angle, 20), s);
+        assert_eq!(trig::cos(angle, 20), c);
+    }
+    assert_eq!(trig::sin_cos(DF::zero(), 20), (DF::zero(), DF::from(1)));
+    assert_eq!(trig::sin(DF::new(1, 1), 4), DF::new(8, 4));
+}
+
+#[test]
+fn test_atan2() {
+    let one = DF::from(1);
+    assert_near(trig::atan2(one, one, 20), 823550, 20);
+    assert_near(trig::atan2(one, -one, 20), 2470649, 20);
+    assert_near(trig::atan2(-one, -one, 20), -2470649, 20);
+    assert_near(trig::atan2(DF::zero(), -one, 20), 3294199, 20);
+    assert_near(trig::atan2(DF::from(3), DF::from(4), 20), 674760, 20);
+    assert_near(trig::atan2(-one, DF::zero(), 20), -1647099, 20);
+    assert_near(trig::atan2(DF::new(1, 100), -one, 20), 3294199, 20);
+    assert_near(trig::atan2(DF::new(-1, 100), -one, 20), -3294199, 20);
+    assert_near(
+        trig::atan2(DF::new(3, -100), DF::new(4, -100), 20),
+        674760,
+        20,
+    );
+    assert_eq!(trig::atan2(DF::zero(), DF::zero(), 20), DF::zero());
+}
+
+fn assert_close(actual: DF, expected: DF) {
+    let err = (actual - expected).abs();
+    assert!(err <= DF::new(1, 22), "{} vs {}", actual, expected);
+}
+
+#[test]
+fn test_angle() {
+    let right = Angle::from_degrees(DF::from(90));
+    assert_eq!(right.radians(), DF::new(1686629713, 30));
+    assert_eq!(Angle::from_degrees(DF::from(270)), -right);
+    assert_eq!(Angle::from_degrees(DF::from(-450)), -right);
+    assert_close(right.radians_positive(), DF::new(1686629713, 30));
+    assert_close((-right).radians_positive(), DF::new(1264972285, 28));
+    assert_close(
+        Angle::from_degrees(DF::new(45, 0)).to_degrees(),
+        DF::from(45),
+    );
+    assert_eq!(
+        Angle::from_degrees(DF::from(180)).to_degrees(),
+        DF::from(-180)
+    );
+
+    // The largest stored angle rounds onto 180 degrees, which wraps.
+    let edge = Angle::from_radians(DF::new(1686629713, 29));
+    assert_eq!(edge.radians(), DF::new(1686629713, 29));
+    assert_eq!(edge.to_degrees(), DF::from(-180));
+    assert_eq!(
+        Angle::from_degrees(DF::from(-180)).to_degrees(),
+        DF::from(-180)
+    );
+    assert_eq!(
+        Angle::from_radians(DF::new(-1, 40)).radians_positive(),
+        DF::new(1686629713, 28)
+    );
+
+    let east = Angle::from_degrees(DF::from(170));
+    let west = Angle::from_degrees(DF::from(-170));
+    assert_close(east.diff(west).to_degrees(), DF::from(-20));
+    assert_close(west.diff(east).to_degrees(), DF::from(20));
+
+    let three = Angle::from_radians(DF::from(3));
+    assert_close(
+        (three + Angle::from_radians(DF::from(1))).radians(),
+        DF::new(-1225775778, 29),
+    );
+
+    let step = Angle::from_degrees(DF::new(45, 1));
+    let mut heading = Angle::zero();
+    for _ in 0..999 {
+        heading += step;
+    }
+    let drift = heading.to_degrees() - DF::new(315, 1);
+    assert!(drift.abs() < DF::new(1, 16));
+    heading -= Angle::from_degrees(DF::from(360 + 45));
+    assert!((heading.to_degrees() - DF::new(225, 1)).abs() < DF::new(1, 16));
+
+    let (sin, cos) = right.sin_cos(20);
+    assert_eq!((sin, cos), (DF::from(1), DF::zero()));
+}
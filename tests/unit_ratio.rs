@@ -0,0 +1,46 @@
+use dyadic::{UnitRatio, DF};
+
+#[test]
+fn test_unit_ratio() {
+    assert_eq!(UnitRatio::new(DF::new(3, 2)).value(), DF::new(3, 2));
+    assert_eq!(UnitRatio::new(DF::from(-2)), UnitRatio::zero());
+    assert_eq!(UnitRatio::new(DF::new(5, 2)), UnitRatio::one());
+    assert_eq!(DF::from(UnitRatio::one()), DF::from(1));
+
+    assert_eq!(UnitRatio::from_percent(DF::from(25)).value(), DF::new(1, 2));
+    assert_eq!(UnitRatio::from_percent(DF::from(150)), UnitRatio::one());
+    assert_eq!(UnitRatio::from_percent(DF::from(-5)), UnitRatio::zero());
+    let third = UnitRatio::from_percent(DF::from_ratio(100, 3, 24));
+    assert!((third.value() - DF::from_ratio(1, 3, 31)).abs() <= DF::new(1, 24));
+    assert_eq!(UnitRatio::new(DF::new(3, 3)).as_percent(), DF::new(75, 1));
+    assert_eq!(UnitRatio::one().as_percent(), DF::from(100));
+
+    let half = UnitRatio::new(DF::new(1, 1));
+    let quarter = UnitRatio::new(DF::new(1, 2));
+    assert_eq!(half + quarter, UnitRatio::new(DF::new(3, 2)));
+    assert_eq!(half + half + half, UnitRatio::one());
+    assert_eq!(quarter - half, UnitRatio::zero());
+    assert_eq!(half * quarter, UnitRatio::new(DF::new(1, 3)));
+    assert_eq!(quarter.complement(), UnitRatio::new(DF::new(3, 2)));
+    assert!(quarter < half);
+
+    let mut val = UnitRatio::zero();
+    val += half;
+    val -= quarter;
+    val *= half;
+    assert_eq!(val, UnitRatio::new(DF::new(1, 3)));
+}
+
+#[test]
+fn test_unit_ratio_codes() {
+    let val = UnitRatio::new(DF::new(3, 2));
+    assert_eq!(val.to_duty(1000), 750);
+    assert_eq!(val.to_dac_code(12), 3071);
+    assert_eq!(val.to_dac_code(8), 191);
+    assert_eq!(UnitRatio::one().to_dac_code(12), 4095);
+    assert_eq!(UnitRatio::one().to_dac_code(24), u16::MAX);
+    assert_eq!(UnitRatio::zero().to_dac_code(16), 0);
+    assert_eq!(val.to_dac_code(0), 0);
+    assert_eq!(UnitRatio::one().to_dac_code(0), 0);
+    assert_eq!(UnitRatio::one().to_dac_code(1), 1);
+}